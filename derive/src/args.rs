@@ -60,6 +60,8 @@ pub struct SimpleObjectField {
     #[darling(default)]
     pub owned: bool,
     #[darling(default)]
+    pub global_id: bool,
+    #[darling(default)]
     pub cache_control: CacheControl,
     #[darling(default)]
     pub external: bool,
@@ -71,6 +73,8 @@ pub struct SimpleObjectField {
     pub guard: Option<Meta>,
     #[darling(default)]
     pub post_guard: Option<Meta>,
+    #[darling(default)]
+    pub visible: Option<syn::Path>,
 }
 
 #[derive(FromDeriveInput)]
@@ -89,6 +93,10 @@ pub struct SimpleObject {
     pub cache_control: CacheControl,
     #[darling(default)]
     pub extends: bool,
+    #[darling(default)]
+    pub guard: Option<Meta>,
+    #[darling(default)]
+    pub visible: Option<syn::Path>,
 }
 
 #[derive(FromMeta, Default)]
@@ -99,6 +107,7 @@ pub struct Argument {
     pub default: Option<DefaultValue>,
     pub default_with: Option<LitStr>,
     pub validator: Option<Meta>,
+    pub deprecation: Option<String>,
     pub key: bool, // for entity
 }
 
@@ -109,6 +118,8 @@ pub struct Object {
     pub name: Option<String>,
     pub cache_control: CacheControl,
     pub extends: bool,
+    pub guard: Option<Meta>,
+    pub visible: Option<syn::Path>,
 }
 
 #[derive(FromMeta, Default)]
@@ -116,6 +127,8 @@ pub struct Object {
 pub struct ObjectField {
     pub skip: bool,
     pub entity: bool,
+    pub node: bool,
+    pub global_id: bool,
     pub name: Option<String>,
     pub deprecation: Option<String>,
     pub cache_control: CacheControl,
@@ -124,6 +137,7 @@ pub struct ObjectField {
     pub requires: Option<String>,
     pub guard: Option<Meta>,
     pub post_guard: Option<Meta>,
+    pub visible: Option<syn::Path>,
 }
 
 #[derive(FromDeriveInput)]
@@ -140,6 +154,8 @@ pub struct Enum {
     pub name: Option<String>,
     #[darling(default)]
     pub remote: Option<String>,
+    #[darling(default)]
+    pub visible: Option<syn::Path>,
 }
 
 #[derive(FromVariant)]
@@ -153,6 +169,41 @@ pub struct EnumItem {
     pub name: Option<String>,
     #[darling(default)]
     pub deprecation: Option<String>,
+    #[darling(default)]
+    pub visible: Option<syn::Path>,
+}
+
+#[derive(FromField)]
+#[darling(attributes(graphql))]
+pub struct GraphQLErrorField {
+    pub ident: Option<Ident>,
+
+    #[darling(default)]
+    pub skip: bool,
+    #[darling(default)]
+    pub name: Option<String>,
+}
+
+#[derive(FromVariant)]
+#[darling(attributes(graphql))]
+pub struct GraphQLErrorItem {
+    pub ident: Ident,
+    pub fields: Fields<GraphQLErrorField>,
+
+    pub message: String,
+    #[darling(default)]
+    pub code: Option<String>,
+}
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(graphql))]
+pub struct GraphQLError {
+    pub ident: Ident,
+    pub generics: Generics,
+    pub data: Data<GraphQLErrorItem, Ignored>,
+
+    #[darling(default)]
+    pub internal: bool,
 }
 
 #[derive(FromDeriveInput)]
@@ -167,6 +218,10 @@ pub struct Union {
     pub internal: bool,
     #[darling(default)]
     pub name: Option<String>,
+    #[darling(default)]
+    pub guard: Option<Meta>,
+    #[darling(default)]
+    pub visible: Option<syn::Path>,
 }
 
 #[derive(FromVariant)]
@@ -197,6 +252,8 @@ pub struct InputObjectField {
     pub validator: Option<Meta>,
     #[darling(default)]
     pub flatten: bool,
+    #[darling(default)]
+    pub deprecation: Option<String>,
 }
 
 #[derive(FromDeriveInput)]
@@ -211,6 +268,10 @@ pub struct InputObject {
     pub internal: bool,
     #[darling(default)]
     pub name: Option<String>,
+    #[darling(default)]
+    pub validator: Option<syn::Path>,
+    #[darling(default)]
+    pub visible: Option<syn::Path>,
 }
 
 #[derive(FromMeta)]
@@ -224,6 +285,8 @@ pub struct InterfaceFieldArgument {
     pub default: Option<DefaultValue>,
     #[darling(default)]
     pub default_with: Option<LitStr>,
+    #[darling(default)]
+    pub deprecation: Option<String>,
 }
 
 #[derive(FromMeta)]
@@ -245,6 +308,8 @@ pub struct InterfaceField {
     pub provides: Option<String>,
     #[darling(default)]
     pub requires: Option<String>,
+    #[darling(default)]
+    pub visible: Option<syn::Path>,
 }
 
 #[derive(FromVariant)]
@@ -269,6 +334,10 @@ pub struct Interface {
     pub fields: Vec<InterfaceField>,
     #[darling(default)]
     pub extends: bool,
+    #[darling(default)]
+    pub guard: Option<Meta>,
+    #[darling(default)]
+    pub visible: Option<syn::Path>,
 }
 
 #[derive(FromMeta, Default)]
@@ -276,6 +345,7 @@ pub struct Interface {
 pub struct Scalar {
     pub internal: bool,
     pub name: Option<String>,
+    pub specified_by_url: Option<String>,
 }
 
 #[derive(FromMeta, Default)]
@@ -293,6 +363,7 @@ pub struct SubscriptionFieldArgument {
     pub default: Option<DefaultValue>,
     pub default_with: Option<LitStr>,
     pub validator: Option<Meta>,
+    pub deprecation: Option<String>,
 }
 
 #[derive(FromMeta, Default)]
@@ -1,5 +1,5 @@
 use crate::args;
-use crate::utils::{get_crate_name, get_rustdoc, GeneratorResult};
+use crate::utils::{generate_visible, get_crate_name, get_rustdoc, GeneratorResult};
 use darling::ast::Data;
 use inflector::Inflector;
 use proc_macro::TokenStream;
@@ -20,6 +20,7 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
     let desc = get_rustdoc(&enum_args.attrs)?
         .map(|s| quote! { Some(#s) })
         .unwrap_or_else(|| quote! {None});
+    let visible = generate_visible(&enum_args.visible);
 
     let mut enum_items = Vec::new();
     let mut items = Vec::new();
@@ -51,6 +52,7 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
         let item_desc = get_rustdoc(&variant.attrs)?
             .map(|s| quote! { Some(#s) })
             .unwrap_or_else(|| quote! {None});
+        let item_visible = generate_visible(&variant.visible);
 
         enum_items.push(item_ident);
         items.push(quote! {
@@ -64,6 +66,7 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
                 name: #gql_item_name,
                 description: #item_desc,
                 deprecation: #item_deprecation,
+                visible: #item_visible,
             });
         });
     }
@@ -132,6 +135,7 @@ pub fn generate(enum_args: &args::Enum) -> GeneratorResult<TokenStream> {
                             #(#schema_enum_items)*
                             enum_items
                         },
+                        visible: #visible,
                     }
                 })
             }
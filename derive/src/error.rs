@@ -0,0 +1,187 @@
+use crate::args;
+use crate::utils::{get_crate_name, GeneratorResult};
+use darling::ast::{Data, Style};
+use inflector::Inflector;
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{parse_quote, Error, Ident};
+
+pub fn generate(error_args: &args::GraphQLError) -> GeneratorResult<TokenStream> {
+    let crate_name = get_crate_name(error_args.internal);
+    let ident = &error_args.ident;
+    let (impl_generics, ty_generics, where_clause) = error_args.generics.split_for_impl();
+    let e = match &error_args.data {
+        Data::Enum(e) => e,
+        _ => {
+            return Err(
+                Error::new_spanned(ident, "GraphQLError can only be applied to an enum.").into(),
+            )
+        }
+    };
+
+    let mut arms = Vec::new();
+
+    for variant in e {
+        let item_ident = &variant.ident;
+        let code = variant
+            .code
+            .clone()
+            .unwrap_or_else(|| item_ident.unraw().to_string().to_screaming_snake_case());
+        let (message, message_args) = parse_message(&variant.message)
+            .map_err(|msg| Error::new_spanned(item_ident, msg))?;
+
+        let mut patterns = Vec::new();
+        let mut format_args = Vec::new();
+        let mut extensions = Vec::new();
+
+        for (idx, field) in variant.fields.iter().enumerate() {
+            let field_name = match &field.ident {
+                Some(ident) => ident.unraw().to_string(),
+                None => idx.to_string(),
+            };
+            let binding = match &field.ident {
+                Some(ident) => ident.clone(),
+                None => Ident::new(&format!("__field{}", idx), Span::call_site()),
+            };
+            let in_message = message_args.contains(&field_name);
+            let extension_name = if field.skip {
+                None
+            } else {
+                match (&field.name, &field.ident) {
+                    (Some(name), _) => Some(name.clone()),
+                    (None, Some(ident)) => Some(ident.unraw().to_string().to_camel_case()),
+                    (None, None) => None,
+                }
+            };
+
+            if !in_message && extension_name.is_none() {
+                if variant.fields.style == Style::Tuple {
+                    patterns.push(quote! { _ });
+                }
+                continue;
+            }
+
+            match &field.ident {
+                Some(ident) => patterns.push(quote! { #ident }),
+                None => patterns.push(quote! { #binding }),
+            }
+            if in_message {
+                format_args.push(quote! { #binding = #binding });
+            }
+            if let Some(name) = extension_name {
+                extensions.push(quote! {
+                    extensions.insert(
+                        #name.to_string(),
+                        #crate_name::serde_json::to_value(&#binding).unwrap_or_default(),
+                    );
+                });
+            }
+        }
+
+        for arg in &message_args {
+            let exists = variant.fields.iter().enumerate().any(|(idx, field)| {
+                field
+                    .ident
+                    .as_ref()
+                    .map(|ident| ident.unraw().to_string())
+                    .unwrap_or_else(|| idx.to_string())
+                    == *arg
+            });
+            if !exists {
+                return Err(Error::new_spanned(
+                    item_ident,
+                    format!("The message refers to an unknown field \"{}\".", arg),
+                )
+                .into());
+            }
+        }
+
+        let pattern = match variant.fields.style {
+            Style::Unit => quote! { #ident::#item_ident },
+            Style::Tuple => quote! { #ident::#item_ident(#(#patterns),*) },
+            Style::Struct => quote! { #ident::#item_ident { #(#patterns,)* .. } },
+        };
+
+        arms.push(quote! {
+            #pattern => {
+                let mut extensions = #crate_name::serde_json::Map::new();
+                extensions.insert("code".to_string(), #code.into());
+                #(#extensions)*
+                let mut error = #crate_name::Error::new(::std::format!(#message, #(#format_args),*));
+                error.extensions = ::std::option::Option::Some(extensions);
+                error
+            }
+        });
+    }
+
+    let mut resolver_generics = error_args.generics.clone();
+    resolver_generics
+        .params
+        .push(parse_quote! { __T: #crate_name::OutputValueType + Send + Sync });
+    let (resolver_impl_generics, _, resolver_where_clause) = resolver_generics.split_for_impl();
+
+    let expanded = quote! {
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #resolver_impl_generics #crate_name::ResolverError<__T> for #ident #ty_generics #resolver_where_clause {
+            type Output = __T;
+
+            fn into_output(res: ::std::result::Result<__T, Self>, ctx: &#crate_name::Context<'_>) -> #crate_name::ServerResult<__T> {
+                res.map_err(|err| ctx.convert_error(err, ctx.item.pos))
+            }
+        }
+
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #impl_generics ::std::convert::From<#ident #ty_generics> for #crate_name::Error #where_clause {
+            fn from(err: #ident #ty_generics) -> Self {
+                match err {
+                    #(#arms)*
+                }
+            }
+        }
+    };
+    Ok(expanded.into())
+}
+
+/// Returns the message template with the positional placeholders of tuple fields renamed to
+/// their bindings, and the names of all the fields that the template refers to.
+fn parse_message(template: &str) -> Result<(String, Vec<String>), String> {
+    let mut message = String::new();
+    let mut args = Vec::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        message.push(c);
+        match c {
+            '{' if chars.peek() == Some(&'{') => message.push(chars.next().unwrap()),
+            '}' if chars.peek() == Some(&'}') => message.push(chars.next().unwrap()),
+            '{' => {
+                let mut name = String::new();
+                while let Some(c) = chars.peek() {
+                    if *c == '}' || *c == ':' {
+                        break;
+                    }
+                    name.push(*c);
+                    chars.next();
+                }
+                let name = name.trim().to_string();
+                if name.is_empty() {
+                    return Err(
+                        "Placeholders in the message must refer to a field by name.".to_string(),
+                    );
+                }
+                if name.chars().all(|c| c.is_ascii_digit()) {
+                    message.push_str("__field");
+                }
+                message.push_str(&name);
+                if !args.contains(&name) {
+                    args.push(name);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok((message, args))
+}
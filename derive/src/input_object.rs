@@ -1,6 +1,7 @@
 use crate::args;
 use crate::utils::{
-    generate_default, generate_validator, get_crate_name, get_rustdoc, GeneratorResult,
+    generate_default, generate_validator, generate_visible, get_crate_name, get_rustdoc,
+    GeneratorResult,
 };
 use darling::ast::Data;
 use inflector::Inflector;
@@ -45,6 +46,7 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
     let desc = get_rustdoc(&object_args.attrs)?
         .map(|s| quote! { Some(#s) })
         .unwrap_or_else(|| quote! {None});
+    let visible = generate_visible(&object_args.visible);
 
     let mut get_fields = Vec::new();
     let mut put_fields = Vec::new();
@@ -98,6 +100,11 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
         let desc = get_rustdoc(&field.attrs)?
             .map(|s| quote! { Some(#s) })
             .unwrap_or_else(|| quote! {None});
+        let deprecation = field
+            .deprecation
+            .as_ref()
+            .map(|s| quote! {Some(#s)})
+            .unwrap_or_else(|| quote! {None});
         let default = generate_default(&field.default, &field.default_with)?;
         let schema_default = default
             .as_ref()
@@ -137,11 +144,19 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
                 description: #desc,
                 ty: <#ty as #crate_name::Type>::create_type_info(registry),
                 default_value: #schema_default,
+                deprecation: #deprecation,
                 validator: #validator,
             });
         })
     }
 
+    let validator = match &object_args.validator {
+        Some(path) => quote! {
+            Some(::std::sync::Arc::new(#crate_name::validators::InputObjectValidator::<Self>(#path)))
+        },
+        None => quote!(None),
+    };
+
     let expanded = quote! {
         #[allow(clippy::all, clippy::pedantic)]
         impl #crate_name::Type for #ident {
@@ -157,7 +172,9 @@ pub fn generate(object_args: &args::InputObject) -> GeneratorResult<TokenStream>
                         let mut fields = #crate_name::indexmap::IndexMap::new();
                         #(#schema_fields)*
                         fields
-                    }
+                    },
+                    validator: #validator,
+                    visible: #visible,
                 })
             }
         }
@@ -1,7 +1,10 @@
 use crate::args;
 use crate::args::{InterfaceField, InterfaceFieldArgument};
 use crate::output_type::OutputType;
-use crate::utils::{generate_default, get_crate_name, get_rustdoc, GeneratorResult};
+use crate::utils::{
+    generate_default, generate_type_guard, generate_visible, get_crate_name, get_rustdoc,
+    GeneratorResult,
+};
 use darling::ast::{Data, Style};
 use inflector::Inflector;
 use proc_macro::TokenStream;
@@ -34,11 +37,13 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
     let desc = get_rustdoc(&interface_args.attrs)?
         .map(|s| quote! { Some(#s) })
         .unwrap_or_else(|| quote! {None});
+    let visible = generate_visible(&interface_args.visible);
 
     let mut registry_types = Vec::new();
     let mut possible_types = Vec::new();
     let mut get_introspection_typename = Vec::new();
     let mut collect_all_fields = Vec::new();
+    let mut check_guards = Vec::new();
 
     for variant in s {
         let enum_name = &variant.ident;
@@ -93,12 +98,16 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             });
 
             get_introspection_typename.push(quote! {
-                #ident::#enum_name(obj) => <#p as #crate_name::Type>::type_name()
+                #ident::#enum_name(obj) => #crate_name::Type::introspection_type_name(obj)
             });
 
             collect_all_fields.push(quote! {
                 #ident::#enum_name(obj) => obj.collect_all_fields(ctx, fields)
             });
+
+            check_guards.push(quote! {
+                #ident::#enum_name(obj) => obj.check_guard(ctx).await
+            });
         } else {
             return Err(Error::new_spanned(ty, "Invalid type").into());
         }
@@ -118,6 +127,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         external,
         provides,
         requires,
+        visible: field_visible,
     } in &interface_args.fields
     {
         let (name, method_name) = if let Some(method) = method {
@@ -153,6 +163,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             ty,
             default,
             default_with,
+            deprecation,
         } in args
         {
             let ident = Ident::new(name, Span::call_site());
@@ -183,12 +194,17 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                     quote! {Some( <#ty as #crate_name::InputValueType>::to_value(&#value).to_string() )}
                 })
                 .unwrap_or_else(|| quote! {None});
+            let deprecation = deprecation
+                .as_ref()
+                .map(|s| quote! {Some(#s)})
+                .unwrap_or_else(|| quote! {None});
             schema_args.push(quote! {
                 args.insert(#name, #crate_name::registry::MetaInputValue {
                     name: #name,
                     description: #desc,
                     ty: <#ty as #crate_name::Type>::create_type_info(registry),
                     default_value: #schema_default,
+                    deprecation: #deprecation,
                     validator: None,
                 });
             });
@@ -208,6 +224,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
             .as_ref()
             .map(|s| quote! {Some(#s)})
             .unwrap_or_else(|| quote! {None});
+        let field_visible = generate_visible(field_visible);
 
         let oty = OutputType::parse(&ty)?;
         let ty = match oty {
@@ -240,12 +257,13 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                 external: #external,
                 provides: #provides,
                 requires: #requires,
+                visible: #field_visible,
             });
         });
 
         let resolve_obj = quote! {
             self.#method_name(#(#use_params),*).await.
-                map_err(|err| err.into_server_error().at(ctx.item.pos))?
+                map_err(|err| ctx.convert_error(err, ctx.item.pos))?
         };
 
         resolvers.push(quote! {
@@ -267,6 +285,10 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
         }
     };
 
+    let type_guard = generate_type_guard(&crate_name, &interface_args.guard)?.map(|guard| {
+        quote! { #guard?; }
+    });
+
     let expanded = quote! {
         #(#type_into_impls)*
 
@@ -304,6 +326,7 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                         },
                         extends: #extends,
                         keys: None,
+                        visible: #visible,
                     }
                 })
             }
@@ -322,12 +345,20 @@ pub fn generate(interface_args: &args::Interface) -> GeneratorResult<TokenStream
                     #(#collect_all_fields),*
                 }
             }
+
+            async fn check_guard(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::ServerResult<()> {
+                #type_guard
+                match self {
+                    #(#check_guards),*
+                }
+            }
         }
 
         #[allow(clippy::all, clippy::pedantic)]
         #[#crate_name::async_trait::async_trait]
         impl #generics #crate_name::OutputValueType for #ident #generics {
-            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::ServerResult<#crate_name::serde_json::Value> {
+            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::ServerResult<#crate_name::serde_json::Value> {
+                #crate_name::resolver_utils::ContainerType::check_guard(self, &ctx.with_parent_field(field)).await?;
                 #crate_name::resolver_utils::resolve_container(ctx, self).await
             }
         }
@@ -5,6 +5,7 @@ extern crate proc_macro;
 
 mod args;
 mod r#enum;
+mod error;
 mod input_object;
 mod interface;
 mod merged_object;
@@ -61,6 +62,19 @@ pub fn derive_enum(input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(GraphQLError, attributes(graphql))]
+pub fn derive_graphql_error(input: TokenStream) -> TokenStream {
+    let error_args =
+        match args::GraphQLError::from_derive_input(&parse_macro_input!(input as DeriveInput)) {
+            Ok(error_args) => error_args,
+            Err(err) => return TokenStream::from(err.write_errors()),
+        };
+    match error::generate(&error_args) {
+        Ok(expanded) => expanded,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
 #[proc_macro_derive(InputObject, attributes(field, graphql))]
 pub fn derive_input_object(input: TokenStream) -> TokenStream {
     let object_args =
@@ -82,6 +82,7 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
                         cache_control,
                         extends: #extends,
                         keys: None,
+                        visible: None,
                     }
                 })
             }
@@ -93,12 +94,21 @@ pub fn generate(object_args: &args::MergedObject) -> GeneratorResult<TokenStream
             async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::ServerResult<::std::option::Option<#crate_name::serde_json::Value>> {
                 #create_merged_obj.resolve_field(ctx).await
             }
+
+            async fn check_guard(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::ServerResult<()> {
+                #create_merged_obj.check_guard(ctx).await
+            }
+
+            async fn find_node(&self, ctx: &#crate_name::Context<'_>, id: &#crate_name::GlobalId) -> #crate_name::ServerResult<::std::option::Option<#crate_name::serde_json::Value>> {
+                #create_merged_obj.find_node(ctx, id).await
+            }
         }
 
         #[allow(clippy::all, clippy::pedantic)]
         #[#crate_name::async_trait::async_trait]
         impl #crate_name::OutputValueType for #ident {
-            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::ServerResult<#crate_name::serde_json::Value> {
+            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::ServerResult<#crate_name::serde_json::Value> {
+                #crate_name::resolver_utils::ContainerType::check_guard(self, &ctx.with_parent_field(field)).await?;
                 #crate_name::resolver_utils::resolve_container(ctx, self).await
             }
         }
@@ -70,6 +70,7 @@ pub fn generate(object_args: &args::MergedSubscription) -> GeneratorResult<Token
                         cache_control: ::std::default::Default::default(),
                         extends: false,
                         keys: None,
+                        visible: None,
                     }
                 })
             }
@@ -1,9 +1,9 @@
 use crate::args;
 use crate::output_type::OutputType;
 use crate::utils::{
-    generate_default, generate_guards, generate_post_guards, generate_validator, get_cfg_attrs,
-    get_crate_name, get_param_getter_ident, get_rustdoc, parse_graphql_attrs, remove_graphql_attrs,
-    GeneratorResult,
+    generate_default, generate_guards, generate_post_guards, generate_type_guard,
+    generate_validator, generate_visible, get_cfg_attrs, get_crate_name, get_param_getter_ident,
+    get_rustdoc, parse_graphql_attrs, remove_graphql_attrs, split_async_validator, GeneratorResult,
 };
 use inflector::Inflector;
 use proc_macro::TokenStream;
@@ -30,6 +30,7 @@ pub fn generate(
     let generics = &item_impl.generics;
     let where_clause = &item_impl.generics.where_clause;
     let extends = object_args.extends;
+    let visible = generate_visible(&object_args.visible);
     let gql_typename = object_args
         .name
         .clone()
@@ -44,6 +45,9 @@ pub fn generate(
     let mut find_entities = Vec::new();
     let mut add_keys = Vec::new();
     let mut create_entity_types = Vec::new();
+    let mut find_nodes = Vec::new();
+    let mut create_node_types = Vec::new();
+    let mut add_node_type = None;
 
     for item in &mut item_impl.items {
         if let ImplItem::Method(method) = item {
@@ -191,7 +195,7 @@ pub fn generate(
                         syn::parse2::<ReturnType>(quote! { -> #crate_name::Result<#inner_ty> })
                             .expect("invalid result type");
                 }
-                let do_find = quote! { self.#field_ident(ctx, #(#use_keys),*).await.map_err(|err| err.into_server_error().at(ctx.item.pos))? };
+                let do_find = quote! { self.#field_ident(ctx, #(#use_keys),*).await.map_err(|err| ctx.convert_error(err, ctx.item.pos))? };
 
                 find_entities.push((
                     args.len(),
@@ -206,6 +210,116 @@ pub fn generate(
                         }
                     },
                 ));
+            } else if method_args.node {
+                let cfg_attrs = get_cfg_attrs(&method.attrs);
+
+                if method.sig.asyncness.is_none() {
+                    return Err(Error::new_spanned(&method, "Must be asynchronous").into());
+                }
+
+                let ty = match &method.sig.output {
+                    ReturnType::Type(_, ty) => OutputType::parse(ty)?,
+                    ReturnType::Default => {
+                        return Err(Error::new_spanned(&method.sig.output, "Missing type").into())
+                    }
+                };
+                let mut create_ctx = true;
+                let mut args = Vec::new();
+
+                for (idx, arg) in method.sig.inputs.iter_mut().enumerate() {
+                    if let FnArg::Receiver(receiver) = arg {
+                        if idx != 0 {
+                            return Err(Error::new_spanned(
+                                receiver,
+                                "The self receiver must be the first parameter.",
+                            )
+                            .into());
+                        }
+                    } else if let FnArg::Typed(pat) = arg {
+                        if idx == 0 {
+                            return Err(Error::new_spanned(
+                                pat,
+                                "The self receiver must be the first parameter.",
+                            )
+                            .into());
+                        }
+
+                        match (&*pat.pat, &*pat.ty) {
+                            (Pat::Ident(arg_ident), Type::Path(arg_ty)) => {
+                                args.push((arg_ident.clone(), arg_ty.clone()));
+                                remove_graphql_attrs(&mut pat.attrs);
+                            }
+                            (arg, Type::Reference(TypeReference { elem, .. })) => {
+                                if let Type::Path(path) = elem.as_ref() {
+                                    if idx != 1
+                                        || path.path.segments.last().unwrap().ident != "Context"
+                                    {
+                                        return Err(Error::new_spanned(
+                                            arg,
+                                            "The Context must be the second argument.",
+                                        )
+                                        .into());
+                                    } else {
+                                        create_ctx = false;
+                                    }
+                                }
+                            }
+                            _ => {
+                                return Err(Error::new_spanned(arg, "Invalid argument type.").into())
+                            }
+                        }
+                    }
+                }
+
+                if create_ctx {
+                    let arg =
+                        syn::parse2::<FnArg>(quote! { _: &#crate_name::Context<'_> }).unwrap();
+                    method.sig.inputs.insert(1, arg);
+                }
+
+                let (id_ident, id_ty) = match args.as_slice() {
+                    [(ident, ty)] => (&ident.ident, ty),
+                    _ => {
+                        return Err(Error::new_spanned(
+                            &method.sig,
+                            "Node loader must have exactly one id argument.",
+                        )
+                        .into())
+                    }
+                };
+
+                let node_type = ty.value_type();
+                create_node_types.push(
+                    quote! { <#node_type as #crate_name::Type>::create_type_info(registry); },
+                );
+
+                let field_ident = &method.sig.ident;
+                if let OutputType::Value(inner_ty) = &ty {
+                    let block = &method.block;
+                    let new_block = quote!({
+                        {
+                            let value:#inner_ty = async move #block.await;
+                            Ok(value)
+                        }
+                    });
+                    method.block = syn::parse2::<Block>(new_block).expect("invalid block");
+                    method.sig.output =
+                        syn::parse2::<ReturnType>(quote! { -> #crate_name::Result<#inner_ty> })
+                            .expect("invalid result type");
+                }
+
+                find_nodes.push(quote! {
+                    #(#cfg_attrs)*
+                    if id.type_name() == <#node_type as #crate_name::Type>::type_name() {
+                        let #id_ident: #id_ty = match id.parse_id() {
+                            Ok(#id_ident) => #id_ident,
+                            Err(_) => return Ok(None),
+                        };
+                        let node = self.#field_ident(ctx, #id_ident).await.map_err(|err| ctx.convert_error(err, ctx.item.pos))?;
+                        let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+                        return #crate_name::OutputValueType::resolve(&node, &ctx_obj, ctx.item).await.map(::std::option::Option::Some);
+                    }
+                });
             } else if !method_args.skip {
                 if method.sig.asyncness.is_none() {
                     return Err(Error::new_spanned(&method, "Must be asynchronous").into());
@@ -232,6 +346,7 @@ pub fn generate(
                     Some(provides) => quote! { Some(#provides) },
                     None => quote! { None },
                 };
+                let visible = generate_visible(&method_args.visible);
                 let ty = match &method.sig.output {
                     ReturnType::Type(_, ty) => OutputType::parse(ty)?,
                     ReturnType::Default => {
@@ -322,6 +437,7 @@ pub fn generate(
                         default,
                         default_with,
                         validator,
+                        deprecation,
                         ..
                     },
                 ) in args
@@ -333,6 +449,10 @@ pub fn generate(
                         .as_ref()
                         .map(|s| quote! {Some(#s)})
                         .unwrap_or_else(|| quote! {None});
+                    let deprecation = deprecation
+                        .as_ref()
+                        .map(|s| quote! {Some(#s)})
+                        .unwrap_or_else(|| quote! {None});
                     let default = generate_default(&default, &default_with)?;
                     let schema_default = default
                         .as_ref()
@@ -341,6 +461,10 @@ pub fn generate(
                         })
                         .unwrap_or_else(|| quote! {None});
 
+                    let (validator, async_validator) = match &validator {
+                        Some(meta) => split_async_validator(meta)?,
+                        None => (None, None),
+                    };
                     let validator = match &validator {
                         Some(meta) => {
                             let stream = generate_validator(&crate_name, meta)?;
@@ -355,6 +479,7 @@ pub fn generate(
                             description: #desc,
                             ty: <#ty as #crate_name::Type>::create_type_info(registry),
                             default_value: #schema_default,
+                            deprecation: #deprecation,
                             validator: #validator,
                         });
                     });
@@ -367,13 +492,40 @@ pub fn generate(
                         None => quote! { None },
                     };
                     let param_getter_name = get_param_getter_ident(&ident.ident.to_string());
+                    let get_param = match &async_validator {
+                        Some(validator) => {
+                            quote! { ctx.param_value_async(#name, #default, &#validator).await? }
+                        }
+                        None => quote! { #param_getter_name()? },
+                    };
                     get_params.push(quote! {
                         let #param_getter_name = || -> #crate_name::ServerResult<#ty> { ctx.param_value(#name, #default) };
-                        let #ident: #ty = #param_getter_name()?;
+                        let #ident: #ty = #get_param;
                     });
                 }
 
-                let schema_ty = ty.value_type();
+                if method_args.global_id {
+                    if field_name != "id" {
+                        return Err(Error::new_spanned(
+                            &method.sig.ident,
+                            "Only the `id` field can be a global id.",
+                        )
+                        .into());
+                    }
+                    add_node_type = Some(quote! { registry.add_node_type(#gql_typename); });
+                }
+
+                let schema_ty = match &ty {
+                    _ if method_args.global_id => quote! { #crate_name::GlobalId },
+                    OutputType::Value(_) => {
+                        let value_ty = ty.value_type();
+                        quote! { #value_ty }
+                    }
+                    OutputType::Result(_, _) => {
+                        let result_ty = ty.result_type();
+                        quote! { <#result_ty as #crate_name::ResolverResult>::Output }
+                    }
+                };
 
                 schema_fields.push(quote! {
                     #(#cfg_attrs)*
@@ -391,6 +543,7 @@ pub fn generate(
                         external: #external,
                         provides: #provides,
                         requires: #requires,
+                        visible: #visible,
                     });
                 });
 
@@ -412,7 +565,7 @@ pub fn generate(
                 let resolve_obj = quote! {
                     {
                         let res = self.#field_ident(ctx, #(#use_params),*).await;
-                        res.map_err(|err| err.into_server_error().at(ctx.item.pos))?
+                        #crate_name::ResolverError::into_output(res, ctx)?
                     }
                 };
 
@@ -424,7 +577,7 @@ pub fn generate(
                 let guard = guard.map(|guard| {
                     quote! {
                         #guard.check(ctx).await
-                            .map_err(|err| err.into_server_error().at(ctx.item.pos))?;
+                            .map_err(|err| ctx.convert_error(err, ctx.item.pos))?;
                     }
                 });
 
@@ -436,10 +589,18 @@ pub fn generate(
                 let post_guard = post_guard.map(|guard| {
                     quote! {
                         #guard.check(ctx, &res).await
-                            .map_err(|err| err.into_server_error().at(ctx.item.pos))?;
+                            .map_err(|err| ctx.convert_error(err, ctx.item.pos))?;
                     }
                 });
 
+                let global_id = if method_args.global_id {
+                    Some(quote! {
+                        let res = #crate_name::GlobalId::new(<Self as #crate_name::Type>::type_name(), res);
+                    })
+                } else {
+                    None
+                };
+
                 resolvers.push(quote! {
                     #(#cfg_attrs)*
                     if ctx.item.node.name.node == #field_name {
@@ -448,6 +609,7 @@ pub fn generate(
                         let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
                         let res = #resolve_obj;
                         #post_guard
+                        #global_id
                         return #crate_name::OutputValueType::resolve(&res, &ctx_obj, ctx.item).await.map(::std::option::Option::Some);
                     }
                 });
@@ -468,6 +630,19 @@ pub fn generate(
         }
     };
 
+    let check_guard = generate_type_guard(&crate_name, &object_args.guard)?.map(|guard| {
+        quote! {
+            async fn check_guard(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::ServerResult<()> {
+                #guard
+            }
+        }
+    });
+    let resolve_guard = check_guard.as_ref().map(|_| {
+        quote! {
+            #crate_name::resolver_utils::ContainerType::check_guard(self, &ctx.with_parent_field(_field)).await?;
+        }
+    });
+
     find_entities.sort_by(|(a, _), (b, _)| b.cmp(a));
     let find_entities_iter = find_entities.iter().map(|(_, code)| code);
 
@@ -492,9 +667,12 @@ pub fn generate(
                     cache_control: #cache_control,
                     extends: #extends,
                     keys: None,
+                    visible: #visible,
                 });
                 #(#create_entity_types)*
                 #(#add_keys)*
+                #(#create_node_types)*
+                #add_node_type
                 ty
             }
         }
@@ -524,12 +702,20 @@ pub fn generate(
                 #(#find_entities_iter)*
                 Ok(None)
             }
+
+            async fn find_node(&self, ctx: &#crate_name::Context<'_>, id: &#crate_name::GlobalId) -> #crate_name::ServerResult<::std::option::Option<#crate_name::serde_json::Value>> {
+                #(#find_nodes)*
+                Ok(None)
+            }
+
+            #check_guard
         }
 
         #[allow(clippy::all, clippy::pedantic)]
         #[#crate_name::async_trait::async_trait]
         impl #generics #crate_name::OutputValueType for #self_ty #where_clause {
             async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::ServerResult<#crate_name::serde_json::Value> {
+                #resolve_guard
                 #crate_name::resolver_utils::resolve_container(ctx, self).await
             }
         }
@@ -50,6 +50,16 @@ impl<'a> OutputType<'a> {
         ty
     }
 
+    pub fn result_type(&self) -> Type {
+        let tokens = match self {
+            OutputType::Value(ty) => quote! {#ty},
+            OutputType::Result(ty, _) => quote! {#ty},
+        };
+        let mut ty = syn::parse2::<syn::Type>(tokens).unwrap();
+        Self::remove_lifecycle(&mut ty);
+        ty
+    }
+
     fn remove_lifecycle(ty: &mut Type) {
         match ty {
             Type::Reference(r) => {
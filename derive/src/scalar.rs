@@ -24,6 +24,11 @@ pub fn generate(
     let desc = get_rustdoc(&item_impl.attrs)?
         .map(|s| quote! { Some(#s) })
         .unwrap_or_else(|| quote! {None});
+    let specified_by_url = scalar_args
+        .specified_by_url
+        .as_ref()
+        .map(|url| quote! { Some(#url) })
+        .unwrap_or_else(|| quote! {None});
     let self_ty = &item_impl.self_ty;
     let generic = &item_impl.generics;
     let where_clause = &item_impl.generics.where_clause;
@@ -42,6 +47,7 @@ pub fn generate(
                     name: #gql_typename.to_string(),
                     description: #desc,
                     is_valid: |value| <#self_ty as #crate_name::ScalarType>::is_valid(value),
+                    specified_by_url: #specified_by_url,
                 })
             }
         }
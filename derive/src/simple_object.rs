@@ -1,6 +1,7 @@
 use crate::args;
 use crate::utils::{
-    generate_guards, generate_post_guards, get_crate_name, get_rustdoc, GeneratorResult,
+    generate_guards, generate_post_guards, generate_type_guard, generate_visible, get_crate_name,
+    get_rustdoc, GeneratorResult,
 };
 use darling::ast::Data;
 use inflector::Inflector;
@@ -23,6 +24,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
     let desc = get_rustdoc(&object_args.attrs)?
         .map(|s| quote! { Some(#s) })
         .unwrap_or_else(|| quote! {None});
+    let visible = generate_visible(&object_args.visible);
 
     let s = match &object_args.data {
         Data::Struct(e) => e,
@@ -37,6 +39,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
     let mut getters = Vec::new();
     let mut resolvers = Vec::new();
     let mut schema_fields = Vec::new();
+    let mut add_node_type = None;
 
     for field in &s.fields {
         if field.skip {
@@ -68,9 +71,31 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
             Some(provides) => quote! { Some(#provides) },
             None => quote! { None },
         };
+        let field_visible = generate_visible(&field.visible);
         let vis = &field.vis;
         let ty = &field.ty;
 
+        if field.global_id {
+            if field_name != "id" {
+                return Err(
+                    Error::new_spanned(&ident, "Only the `id` field can be a global id.").into(),
+                );
+            }
+            add_node_type = Some(quote! { registry.add_node_type(#gql_typename); });
+        }
+        let schema_ty = if field.global_id {
+            quote! { #crate_name::GlobalId }
+        } else {
+            quote! { #ty }
+        };
+        let global_id = if field.global_id {
+            Some(quote! {
+                let res = #crate_name::GlobalId::new(<Self as #crate_name::Type>::type_name(), res);
+            })
+        } else {
+            None
+        };
+
         let cache_control = {
             let public = field.cache_control.is_public();
             let max_age = field.cache_control.max_age;
@@ -87,12 +112,13 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                 name: #field_name.to_string(),
                 description: #field_desc,
                 args: Default::default(),
-                ty: <#ty as #crate_name::Type>::create_type_info(registry),
+                ty: <#schema_ty as #crate_name::Type>::create_type_info(registry),
                 deprecation: #field_deprecation,
                 cache_control: #cache_control,
                 external: #external,
                 provides: #provides,
                 requires: #requires,
+                visible: #field_visible,
             });
         });
 
@@ -100,13 +126,13 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
             Some(meta) => generate_guards(&crate_name, &meta)?,
             None => None,
         };
-        let guard = guard.map(|guard| quote! { #guard.check(ctx).await.map_err(|err| err.into_server_error().at(ctx.item.pos))?; });
+        let guard = guard.map(|guard| quote! { #guard.check(ctx).await.map_err(|err| ctx.convert_error(err, ctx.item.pos))?; });
 
         let post_guard = match &field.post_guard {
             Some(meta) => generate_post_guards(&crate_name, &meta)?,
             None => None,
         };
-        let post_guard = post_guard.map(|guard| quote! { #guard.check(ctx, &res).await.map_err(|err| err.into_server_error().at(ctx.item.pos))?; });
+        let post_guard = post_guard.map(|guard| quote! { #guard.check(ctx, &res).await.map_err(|err| ctx.convert_error(err, ctx.item.pos))?; });
 
         getters.push(if !field.owned {
             quote! {
@@ -129,9 +155,10 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         resolvers.push(quote! {
             if ctx.item.node.name.node == #field_name {
                 #guard
-                let res = self.#ident(ctx).await.map_err(|err| err.into_server_error().at(ctx.item.pos))?;
+                let res = self.#ident(ctx).await.map_err(|err| ctx.convert_error(err, ctx.item.pos))?;
                 let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
                 #post_guard
+                #global_id
                 return #crate_name::OutputValueType::resolve(&res, &ctx_obj, ctx.item).await.map(::std::option::Option::Some);
             }
         });
@@ -148,6 +175,19 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         }
     };
 
+    let check_guard = generate_type_guard(&crate_name, &object_args.guard)?.map(|guard| {
+        quote! {
+            async fn check_guard(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::ServerResult<()> {
+                #guard
+            }
+        }
+    });
+    let resolve_guard = check_guard.as_ref().map(|_| {
+        quote! {
+            #crate_name::resolver_utils::ContainerType::check_guard(self, &ctx.with_parent_field(_field)).await?;
+        }
+    });
+
     let expanded = quote! {
         #[allow(clippy::all, clippy::pedantic)]
         impl #generics #ident #where_clause {
@@ -161,7 +201,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
             }
 
             fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
-                registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::Object {
+                let ty = registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::Object {
                     name: #gql_typename.to_string(),
                     description: #desc,
                     fields: {
@@ -172,7 +212,10 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                     cache_control: #cache_control,
                     extends: #extends,
                     keys: None,
-                })
+                    visible: #visible,
+                });
+                #add_node_type
+                ty
             }
         }
 
@@ -184,6 +227,8 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
                 #(#resolvers)*
                 Ok(None)
             }
+
+            #check_guard
         }
 
         #[allow(clippy::all, clippy::pedantic)]
@@ -191,6 +236,7 @@ pub fn generate(object_args: &args::SimpleObject) -> GeneratorResult<TokenStream
         impl #generics #crate_name::OutputValueType for #ident #generics #where_clause {
 
             async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::ServerResult<#crate_name::serde_json::Value> {
+                #resolve_guard
                 #crate_name::resolver_utils::resolve_container(ctx, self).await
             }
         }
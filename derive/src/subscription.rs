@@ -4,7 +4,7 @@ use crate::output_type::OutputType;
 use crate::utils::{
     generate_default, generate_guards, generate_validator, get_cfg_attrs, get_crate_name,
     get_param_getter_ident, get_rustdoc, parse_graphql_attrs, remove_graphql_attrs,
-    GeneratorResult,
+    split_async_validator, GeneratorResult,
 };
 use inflector::Inflector;
 use proc_macro::TokenStream;
@@ -155,6 +155,7 @@ pub fn generate(
                     default,
                     default_with,
                     validator,
+                    deprecation,
                 },
             ) in args
             {
@@ -165,8 +166,16 @@ pub fn generate(
                     .as_ref()
                     .map(|s| quote! {Some(#s)})
                     .unwrap_or_else(|| quote! {None});
+                let deprecation = deprecation
+                    .as_ref()
+                    .map(|s| quote! {Some(#s)})
+                    .unwrap_or_else(|| quote! {None});
                 let default = generate_default(&default, &default_with)?;
 
+                let (validator, async_validator) = match &validator {
+                    Some(meta) => split_async_validator(meta)?,
+                    None => (None, None),
+                };
                 let validator = match &validator {
                     Some(meta) => {
                         let stream = generate_validator(&crate_name, meta)?;
@@ -188,6 +197,7 @@ pub fn generate(
                         description: #desc,
                         ty: <#ty as #crate_name::Type>::create_type_info(registry),
                         default_value: #schema_default,
+                        deprecation: #deprecation,
                         validator: #validator,
                     });
                 });
@@ -199,9 +209,15 @@ pub fn generate(
                     None => quote! { None },
                 };
                 let param_getter_name = get_param_getter_ident(&ident.ident.to_string());
+                let get_param = match &async_validator {
+                    Some(validator) => {
+                        quote! { ctx.param_value_async(#name, #default, &#validator).await? }
+                    }
+                    None => quote! { ctx.param_value(#name, #default)? },
+                };
                 get_params.push(quote! {
                     let #param_getter_name = || -> #crate_name::ServerResult<#ty> { ctx.param_value(#name, #default) };
-                    let #ident: #ty = ctx.param_value(#name, #default)?;
+                    let #ident: #ty = #get_param;
                 });
             }
 
@@ -242,6 +258,7 @@ pub fn generate(
                     external: false,
                     requires: None,
                     provides: None,
+                    visible: None,
                 });
             });
 
@@ -249,7 +266,7 @@ pub fn generate(
                 self.#ident(ctx, #(#use_params),*)
                     .await
                     .map_err(|err| {
-                        err.into_server_error().at(ctx.item.pos)
+                        ctx.convert_error(err, ctx.item.pos)
                     })?
             };
 
@@ -258,7 +275,7 @@ pub fn generate(
                 None => None,
             };
             let guard = guard.map(|guard| quote! {
-                #guard.check(ctx).await.map_err(|err| err.into_server_error().at(ctx.item.pos))?;
+                #guard.check(ctx).await.map_err(|err| ctx.convert_error(err, ctx.item.pos))?;
             });
             if field.post_guard.is_some() {
                 return Err(Error::new_spanned(
@@ -303,6 +320,7 @@ pub fn generate(
                             let ctx_extension = #crate_name::extensions::ExtensionContext {
                                 schema_data: &schema_env.data,
                                 query_data: &query_env.ctx_data,
+                                error_mask: query_env.error_mask(&schema_env),
                             };
 
                             #crate_name::extensions::Extension::execution_start(&mut *query_env.extensions.lock(), &ctx_extension);
@@ -378,6 +396,7 @@ pub fn generate(
                     cache_control: ::std::default::Default::default(),
                     extends: false,
                     keys: None,
+                    visible: None,
                 })
             }
         }
@@ -1,10 +1,12 @@
 use crate::args;
-use crate::utils::{get_crate_name, get_rustdoc, GeneratorResult};
+use crate::utils::{
+    generate_type_guard, generate_visible, get_crate_name, get_rustdoc, GeneratorResult,
+};
 use darling::ast::{Data, Style};
 use proc_macro::TokenStream;
 use quote::quote;
 use std::collections::HashSet;
-use syn::{Error, Type};
+use syn::{parse_quote, Error, Type};
 
 pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
     let crate_name = get_crate_name(union_args.internal);
@@ -24,11 +26,13 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
     let desc = get_rustdoc(&union_args.attrs)?
         .map(|s| quote! { Some(#s) })
         .unwrap_or_else(|| quote! {None});
+    let visible = generate_visible(&union_args.visible);
 
     let mut registry_types = Vec::new();
     let mut possible_types = Vec::new();
     let mut get_introspection_typename = Vec::new();
     let mut collect_all_fields = Vec::new();
+    let mut check_guards = Vec::new();
 
     for variant in s {
         let enum_name = &variant.ident;
@@ -119,11 +123,25 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
             collect_all_fields.push(quote! {
                 #ident::#enum_name(obj) => obj.collect_all_fields(ctx, fields)
             });
+
+            check_guards.push(quote! {
+                #ident::#enum_name(obj) => obj.check_guard(ctx).await
+            });
         } else {
             return Err(Error::new_spanned(ty, "Invalid type").into());
         }
     }
 
+    let mut resolver_generics = generics.clone();
+    resolver_generics
+        .params
+        .push(parse_quote! { __T: #crate_name::ObjectType + Send + Sync });
+    let (resolver_impl_generics, _, resolver_where_clause) = resolver_generics.split_for_impl();
+
+    let type_guard = generate_type_guard(&crate_name, &union_args.guard)?.map(|guard| {
+        quote! { #guard?; }
+    });
+
     let expanded = quote! {
         #(#type_into_impls)*
 
@@ -150,7 +168,8 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
                             let mut possible_types = #crate_name::indexmap::IndexSet::new();
                             #(#possible_types)*
                             possible_types
-                        }
+                        },
+                        visible: #visible,
                     }
                 })
             }
@@ -169,17 +188,37 @@ pub fn generate(union_args: &args::Union) -> GeneratorResult<TokenStream> {
                     #(#collect_all_fields),*
                 }
             }
+
+            async fn check_guard(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::ServerResult<()> {
+                #type_guard
+                match self {
+                    #(#check_guards),*
+                }
+            }
         }
 
         #[allow(clippy::all, clippy::pedantic)]
         #[#crate_name::async_trait::async_trait]
         impl #generics #crate_name::OutputValueType for #ident #generics {
-            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::ServerResult<#crate_name::serde_json::Value> {
+            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::ServerResult<#crate_name::serde_json::Value> {
+                #crate_name::resolver_utils::ContainerType::check_guard(self, &ctx.with_parent_field(field)).await?;
                 #crate_name::resolver_utils::resolve_container(ctx, self).await
             }
         }
 
         impl #generics #crate_name::UnionType for #ident #generics {}
+
+        #[allow(clippy::all, clippy::pedantic)]
+        impl #resolver_impl_generics #crate_name::ResolverError<__T> for #ident #generics #resolver_where_clause {
+            type Output = #crate_name::types::ResultUnion<__T, Self>;
+
+            fn into_output(res: ::std::result::Result<__T, Self>, _ctx: &#crate_name::Context<'_>) -> #crate_name::ServerResult<Self::Output> {
+                match res {
+                    Ok(obj) => Ok(#crate_name::types::ResultUnion::Ok(obj)),
+                    Err(err) => Ok(#crate_name::types::ResultUnion::Err(err)),
+                }
+            }
+        }
     };
     Ok(expanded.into())
 }
@@ -66,6 +66,23 @@ fn generate_nested_validator(
                         None => Some(item),
                     })
                     .unwrap())
+            } else if !ls.nested.is_empty()
+                && ls
+                    .nested
+                    .iter()
+                    .all(|item| !matches!(item, NestedMeta::Meta(Meta::NameValue(_))))
+            {
+                // Tuple validators, such as `Regex("[a-z]+")` or `ListItems(Email)`.
+                let ty = &ls.path;
+                for item in &ls.nested {
+                    match item {
+                        NestedMeta::Lit(lit) => params.push(quote! { (#lit).into() }),
+                        NestedMeta::Meta(_) => {
+                            params.push(generate_nested_validator(crate_name, item)?)
+                        }
+                    }
+                }
+                Ok(quote! { #ty(#(#params),*) })
             } else {
                 let ty = &ls.path;
                 for item in &ls.nested {
@@ -117,104 +134,115 @@ pub fn generate_validator(crate_name: &TokenStream, args: &Meta) -> GeneratorRes
     }
 }
 
-pub fn generate_guards(
-    crate_name: &TokenStream,
-    args: &Meta,
-) -> GeneratorResult<Option<TokenStream>> {
+/// Splits `validator(..., async = "...")` into the input value validator and the expression of
+/// the async validator.
+pub fn split_async_validator(args: &Meta) -> GeneratorResult<(Option<Meta>, Option<Expr>)> {
     match args {
         Meta::List(args) => {
-            let mut guards = None;
-            for item in &args.nested {
-                if let NestedMeta::Meta(Meta::List(ls)) = item {
-                    let ty = &ls.path;
-                    let mut params = Vec::new();
-                    for attr in &ls.nested {
-                        if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
-                            let name = &nv.path;
-                            if let Lit::Str(value) = &nv.lit {
-                                let value_str = value.value();
-                                if value_str.starts_with('@') {
-                                    let getter_name = get_param_getter_ident(&value_str[1..]);
-                                    params.push(quote! { #name: #getter_name()? });
-                                } else {
-                                    let expr = syn::parse_str::<Expr>(&value_str)?;
-                                    params.push(quote! { #name: (#expr).into() });
-                                }
-                            } else {
-                                return Err(Error::new_spanned(
-                                    &nv.lit,
-                                    "Value must be string literal",
-                                )
-                                .into());
-                            }
+            let mut args = args.clone();
+            let mut async_validator = None;
+            for nested_meta in std::mem::take(&mut args.nested) {
+                match &nested_meta {
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("async") => {
+                        if let Lit::Str(value) = &nv.lit {
+                            async_validator = Some(syn::parse_str::<Expr>(&value.value())?);
                         } else {
-                            return Err(
-                                Error::new_spanned(attr, "Invalid property for guard").into()
-                            );
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Value must be string literal",
+                            )
+                            .into());
                         }
                     }
-                    let guard = quote! { #ty { #(#params),* } };
-                    if guards.is_none() {
-                        guards = Some(guard);
+                    _ => args.nested.push(nested_meta),
+                }
+            }
+            if args.nested.is_empty() && async_validator.is_some() {
+                Ok((None, async_validator))
+            } else {
+                Ok((Some(Meta::List(args)), async_validator))
+            }
+        }
+        _ => Ok((Some(args.clone()), None)),
+    }
+}
+
+fn generate_guard(nested_meta: &NestedMeta, ext: &TokenStream) -> GeneratorResult<TokenStream> {
+    match nested_meta {
+        NestedMeta::Meta(Meta::List(ls))
+            if ls.path.is_ident("and")
+                || ls.path.is_ident("all_of")
+                || ls.path.is_ident("or")
+                || ls.path.is_ident("any_of") =>
+        {
+            let method = if ls.path.is_ident("and") || ls.path.is_ident("all_of") {
+                quote! { and }
+            } else {
+                quote! { or }
+            };
+            let mut guards = Vec::new();
+            for nested_meta in &ls.nested {
+                guards.push(generate_guard(nested_meta, ext)?);
+            }
+            guards
+                .into_iter()
+                .fold(None, |acc, item| match acc {
+                    Some(prev) => Some(quote! { #ext::#method(#prev, #item) }),
+                    None => Some(item),
+                })
+                .ok_or_else(|| Error::new_spanned(ls, "At least one guard must be defined").into())
+        }
+        NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("not") => {
+            if ls.nested.len() != 1 {
+                return Err(Error::new_spanned(ls, "Only one guard can be negated").into());
+            }
+            let guard = generate_guard(&ls.nested[0], ext)?;
+            Ok(quote! { #ext::not(#guard) })
+        }
+        NestedMeta::Meta(Meta::List(ls)) => {
+            let ty = &ls.path;
+            let mut params = Vec::new();
+            for attr in &ls.nested {
+                if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
+                    let name = &nv.path;
+                    if let Lit::Str(value) = &nv.lit {
+                        let value_str = value.value();
+                        let arg_name = value_str
+                            .strip_prefix('@')
+                            .or_else(|| value_str.strip_prefix("arg:"));
+                        if let Some(arg_name) = arg_name {
+                            let getter_name = get_param_getter_ident(arg_name);
+                            params.push(quote! { #name: #getter_name()? });
+                        } else {
+                            let expr = syn::parse_str::<Expr>(&value_str)?;
+                            params.push(quote! { #name: (#expr).into() });
+                        }
                     } else {
-                        guards =
-                            Some(quote! { #crate_name::guard::GuardExt::and(#guard, #guards) });
+                        return Err(
+                            Error::new_spanned(&nv.lit, "Value must be string literal").into()
+                        );
                     }
                 } else {
-                    return Err(Error::new_spanned(item, "Invalid guard").into());
+                    return Err(Error::new_spanned(attr, "Invalid property for guard").into());
                 }
             }
-            Ok(guards)
+            Ok(quote! { #ty { #(#params),* } })
         }
-        _ => Err(Error::new_spanned(args, "Invalid guards").into()),
+        NestedMeta::Meta(Meta::Path(ty)) => Ok(quote! { #ty {} }),
+        _ => Err(Error::new_spanned(nested_meta, "Invalid guard").into()),
     }
 }
 
-pub fn generate_post_guards(
-    crate_name: &TokenStream,
-    args: &Meta,
-) -> GeneratorResult<Option<TokenStream>> {
+fn generate_guards_with(args: &Meta, ext: TokenStream) -> GeneratorResult<Option<TokenStream>> {
     match args {
         Meta::List(args) => {
             let mut guards = None;
             for item in &args.nested {
-                if let NestedMeta::Meta(Meta::List(ls)) = item {
-                    let ty = &ls.path;
-                    let mut params = Vec::new();
-                    for attr in &ls.nested {
-                        if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
-                            let name = &nv.path;
-                            if let Lit::Str(value) = &nv.lit {
-                                let value_str = value.value();
-                                if value_str.starts_with('@') {
-                                    let getter_name = get_param_getter_ident(&value_str[1..]);
-                                    params.push(quote! { #name: #getter_name()? });
-                                } else {
-                                    let expr = syn::parse_str::<Expr>(&value_str)?;
-                                    params.push(quote! { #name: (#expr).into() });
-                                }
-                            } else {
-                                return Err(Error::new_spanned(
-                                    &nv.lit,
-                                    "Value must be string literal",
-                                )
-                                .into());
-                            }
-                        } else {
-                            return Err(
-                                Error::new_spanned(attr, "Invalid property for guard").into()
-                            );
-                        }
-                    }
-                    let guard = quote! { #ty { #(#params),* } };
-                    if guards.is_none() {
-                        guards = Some(guard);
-                    } else {
-                        guards =
-                            Some(quote! { #crate_name::guard::PostGuardExt::and(#guard, #guards) });
-                    }
+                let guard = generate_guard(item, &ext)?;
+                if guards.is_none() {
+                    guards = Some(guard);
                 } else {
-                    return Err(Error::new_spanned(item, "Invalid guard").into());
+                    guards = Some(quote! { #ext::and(#guard, #guards) });
                 }
             }
             Ok(guards)
@@ -223,6 +251,73 @@ pub fn generate_post_guards(
     }
 }
 
+pub fn generate_guards(
+    crate_name: &TokenStream,
+    args: &Meta,
+) -> GeneratorResult<Option<TokenStream>> {
+    generate_guards_with(args, quote! { #crate_name::guard::GuardExt })
+}
+
+pub fn generate_post_guards(
+    crate_name: &TokenStream,
+    args: &Meta,
+) -> GeneratorResult<Option<TokenStream>> {
+    generate_guards_with(args, quote! { #crate_name::guard::PostGuardExt })
+}
+
+/// Type-level guards are not checked in a resolver, so they cannot use its arguments.
+fn check_type_guard_params(meta: &Meta) -> GeneratorResult<()> {
+    match meta {
+        Meta::List(ls) => {
+            for nested_meta in &ls.nested {
+                if let NestedMeta::Meta(meta) = nested_meta {
+                    check_type_guard_params(meta)?;
+                }
+            }
+            Ok(())
+        }
+        Meta::NameValue(nv) => match &nv.lit {
+            Lit::Str(value)
+                if value.value().starts_with('@') || value.value().starts_with("arg:") =>
+            {
+                Err(Error::new_spanned(
+                    &nv.lit,
+                    "Field arguments are not available in a type-level guard",
+                )
+                .into())
+            }
+            _ => Ok(()),
+        },
+        Meta::Path(_) => Ok(()),
+    }
+}
+
+pub fn generate_type_guard(
+    crate_name: &TokenStream,
+    args: &Option<Meta>,
+) -> GeneratorResult<Option<TokenStream>> {
+    let guard = match args {
+        Some(meta) => {
+            check_type_guard_params(meta)?;
+            generate_guards(crate_name, meta)?
+        }
+        None => None,
+    };
+    Ok(guard.map(|guard| {
+        quote! {
+            #crate_name::guard::Guard::check(&#guard, ctx).await
+                .map_err(|err| ctx.convert_error(err, ctx.item.pos))
+        }
+    }))
+}
+
+pub fn generate_visible(visible: &Option<syn::Path>) -> TokenStream {
+    match visible {
+        Some(visible) => quote! { Some(#visible) },
+        None => quote! { None },
+    }
+}
+
 pub fn get_rustdoc(attrs: &[Attribute]) -> GeneratorResult<Option<String>> {
     let mut full_docs = String::new();
     for attr in attrs {
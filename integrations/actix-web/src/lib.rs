@@ -6,10 +6,11 @@ mod subscription;
 pub use subscription::WSSubscription;
 
 use actix_web::dev::{Payload, PayloadStream};
-use actix_web::http::StatusCode;
+use actix_web::error::InternalError;
+use actix_web::http::{Method, StatusCode};
 use actix_web::{http, web, Error, FromRequest, HttpRequest, HttpResponse, Responder, Result};
-use async_graphql::http::MultipartOptions;
-use async_graphql::ParseRequestError;
+use async_graphql::http::{MultipartOptions, ResponseHead};
+use async_graphql::{ParseRequestError, ServerError};
 use futures::channel::mpsc;
 use futures::future::{self, FutureExt, Ready};
 use futures::io::ErrorKind;
@@ -30,24 +31,24 @@ impl Request {
     }
 }
 
-type RequestMapper =
-    fn(<<BatchRequest as FromRequest>::Future as Future>::Output) -> Result<Request>;
-
 impl FromRequest for Request {
     type Error = Error;
-    type Future = future::Map<<BatchRequest as FromRequest>::Future, RequestMapper>;
+    type Future = Pin<Box<dyn Future<Output = Result<Request>>>>;
     type Config = MultipartOptions;
 
     fn from_request(req: &HttpRequest, payload: &mut Payload<PayloadStream>) -> Self::Future {
-        BatchRequest::from_request(req, payload).map(|res| {
-            res.and_then(|batch| {
-                batch
-                    .0
-                    .into_single()
-                    .map_err(actix_web::error::ErrorBadRequest)
+        let accept = accept(req);
+        BatchRequest::from_request(req, payload)
+            .map(move |res| {
+                res.and_then(|batch| {
+                    batch
+                        .0
+                        .into_single()
+                        .map_err(|err| error(accept.as_deref(), err))
+                })
+                .map(Self)
             })
-            .map(Self)
-        })
+            .boxed_local()
     }
 }
 
@@ -71,6 +72,14 @@ impl FromRequest for BatchRequest {
 
     fn from_request(req: &HttpRequest, payload: &mut Payload<PayloadStream>) -> Self::Future {
         let config = req.app_data::<Self::Config>().cloned().unwrap_or_default();
+        let accept = accept(req);
+
+        if req.method() == Method::GET {
+            let res = async_graphql::http::parse_query_string(req.query_string())
+                .map(|request| BatchRequest(request.into()))
+                .map_err(|err| error(accept.as_deref(), err));
+            return Box::pin(future::ready(res));
+        }
 
         let content_type = req
             .headers()
@@ -98,18 +107,37 @@ impl FromRequest for BatchRequest {
                         .into_async_read(),
                     config,
                 )
-                .map_err(|err| match err {
-                    ParseRequestError::PayloadTooLarge => {
-                        actix_web::error::ErrorPayloadTooLarge(err)
-                    }
-                    _ => actix_web::error::ErrorBadRequest(err),
-                })
+                .map_err(|err| error(accept.as_deref(), err))
                 .await?,
             ))
         })
     }
 }
 
+fn accept(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get(http::header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
+fn status(head: ResponseHead) -> StatusCode {
+    StatusCode::from_u16(head.status).unwrap_or(StatusCode::BAD_REQUEST)
+}
+
+/// Convert an error to a GraphQL response with the error.
+fn error(accept: Option<&str>, err: ParseRequestError) -> Error {
+    let head = ResponseHead::for_error(accept, &err);
+    let body = serde_json::to_string(&async_graphql::Response::from_errors(vec![
+        ServerError::new(err.to_string()),
+    ]))
+    .unwrap();
+    let response = HttpResponse::build(status(head))
+        .content_type(head.content_type)
+        .body(body);
+    InternalError::from_response(err, response).into()
+}
+
 /// Responder for a GraphQL response.
 ///
 /// This contains a batch response, but since regular responses are a type of batch response it
@@ -132,9 +160,10 @@ impl Responder for Response {
     type Error = Error;
     type Future = Ready<Result<HttpResponse>>;
 
-    fn respond_to(self, _req: &HttpRequest) -> Self::Future {
-        let mut res = HttpResponse::build(StatusCode::OK);
-        res.content_type("application/json");
+    fn respond_to(self, req: &HttpRequest) -> Self::Future {
+        let head = ResponseHead::for_response(accept(req).as_deref(), &self.0);
+        let mut res = HttpResponse::build(status(head));
+        res.content_type(head.content_type);
         if self.0.is_ok() {
             if let Some(cache_control) = self.0.cache_control().value() {
                 res.header("cache-control", cache_control);
@@ -2,24 +2,32 @@ use actix::{
     Actor, ActorContext, ActorFuture, ActorStream, AsyncContext, ContextFutureSpawner,
     StreamHandler, WrapFuture, WrapStream,
 };
+use actix_http::error::PayloadError;
 use actix_http::ws;
-use actix_web_actors::ws::{Message, ProtocolError, WebsocketContext};
-use async_graphql::http::WebSocket;
+use actix_web::web::Bytes;
+use actix_web::{HttpRequest, HttpResponse};
+use actix_web_actors::ws::{CloseReason, Message, ProtocolError, WebsocketContext};
+use async_graphql::http::{WebSocket, WebSocketOptions, WebSocketProtocols, WsMessage};
 use async_graphql::{Data, ObjectType, Result, Schema, SubscriptionType};
 use futures::channel::mpsc;
-use futures::SinkExt;
+use futures::future::BoxFuture;
+use futures::{Future, SinkExt, Stream};
 use std::time::{Duration, Instant};
 
 const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
 const CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
 
+type InitializerFuture = BoxFuture<'static, Result<Data>>;
+
 /// Actor for subscription via websocket
 pub struct WSSubscription<Query, Mutation, Subscription> {
     schema: Option<Schema<Query, Mutation, Subscription>>,
     last_heartbeat: Instant,
     messages: Option<mpsc::UnboundedSender<Vec<u8>>>,
-    initializer: Option<Box<dyn FnOnce(serde_json::Value) -> Result<Data> + Send + Sync>>,
+    initializer: Option<Box<dyn FnOnce(serde_json::Value) -> InitializerFuture + Send + Sync>>,
     continuation: Vec<u8>,
+    protocol: WebSocketProtocols,
+    options: WebSocketOptions,
 }
 
 impl<Query, Mutation, Subscription> WSSubscription<Query, Mutation, Subscription>
@@ -29,6 +37,8 @@ where
     Subscription: SubscriptionType + Send + Sync + 'static,
 {
     /// Create an actor for subscription connection via websocket.
+    ///
+    /// It uses the subscriptions-transport-ws protocol, unless another one is set with `protocol`.
     pub fn new(schema: Schema<Query, Mutation, Subscription>) -> Self {
         Self {
             schema: Some(schema),
@@ -36,16 +46,77 @@ where
             messages: None,
             initializer: None,
             continuation: Vec::new(),
+            protocol: WebSocketProtocols::SubscriptionsTransportWS,
+            options: WebSocketOptions::default(),
         }
     }
 
-    /// Set a context data initialization function.
-    pub fn initializer<F>(self, f: F) -> Self
+    /// Set the GraphQL over WebSocket protocol.
+    pub fn protocol(self, protocol: WebSocketProtocols) -> Self {
+        Self { protocol, ..self }
+    }
+
+    /// Set the options of the connection, such as the keep-alive interval.
+    pub fn options(self, options: WebSocketOptions) -> Self {
+        Self { options, ..self }
+    }
+
+    /// Start the actor with the protocol negotiated from the `Sec-WebSocket-Protocol` header of
+    /// the request.
+    ///
+    /// ```no_run
+    /// use actix_web::{web, HttpRequest, HttpResponse, Result};
+    /// use async_graphql::*;
+    /// use async_graphql_actix_web::WSSubscription;
+    ///
+    /// struct Query;
+    ///
+    /// #[Object]
+    /// impl Query {
+    ///     async fn value(&self) -> i32 {
+    ///         10
+    ///     }
+    /// }
+    ///
+    /// type MySchema = Schema<Query, EmptyMutation, EmptySubscription>;
+    ///
+    /// async fn index_ws(
+    ///     schema: web::Data<MySchema>,
+    ///     req: HttpRequest,
+    ///     payload: web::Payload,
+    /// ) -> Result<HttpResponse> {
+    ///     WSSubscription::new(MySchema::clone(&*schema)).start(&req, payload)
+    /// }
+    /// ```
+    pub fn start<T>(self, request: &HttpRequest, stream: T) -> actix_web::Result<HttpResponse>
     where
-        F: FnOnce(serde_json::Value) -> Result<Data> + Send + Sync + 'static,
+        T: Stream<Item = std::result::Result<Bytes, PayloadError>> + 'static,
+    {
+        let protocol = WebSocketProtocols::negotiate(
+            request
+                .headers()
+                .get("sec-websocket-protocol")
+                .and_then(|value| value.to_str().ok()),
+        )
+        .ok_or_else(|| actix_web::error::ErrorBadRequest("Unsupported Sec-WebSocket-Protocol"))?;
+        actix_web_actors::ws::start_with_protocols(
+            self.protocol(protocol),
+            &[protocol.sec_websocket_protocol()],
+            request,
+            stream,
+        )
+    }
+
+    /// Set an async context data initialization function.
+    pub fn initializer<F, R>(self, f: F) -> Self
+    where
+        F: FnOnce(serde_json::Value) -> R + Send + Sync + 'static,
+        R: Future<Output = Result<Data>> + Send + 'static,
     {
         Self {
-            initializer: Some(Box::new(f)),
+            initializer: Some(Box::new(move |value| -> InitializerFuture {
+                Box::pin(f(value))
+            })),
             ..self
         }
     }
@@ -73,13 +144,26 @@ where
 
         let (tx, rx) = mpsc::unbounded();
 
-        WebSocket::with_data(self.schema.take().unwrap(), rx, self.initializer.take())
-            .into_actor(self)
-            .map(|response, _act, ctx| {
-                ctx.text(response);
-            })
-            .finish()
-            .spawn(ctx);
+        WebSocket::with_data(
+            self.schema.take().unwrap(),
+            rx,
+            self.initializer.take(),
+            self.protocol,
+        )
+        .options(self.options.clone())
+        .into_actor(self)
+        .map(|response, _act, ctx| match response {
+            WsMessage::Text(text) => ctx.text(text),
+            WsMessage::Close(code, reason) => {
+                ctx.close(Some(CloseReason {
+                    code: code.into(),
+                    description: Some(reason),
+                }));
+                ctx.stop();
+            }
+        })
+        .finish()
+        .spawn(ctx);
 
         self.messages = Some(tx);
     }
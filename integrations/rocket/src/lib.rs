@@ -3,8 +3,8 @@
 #![warn(missing_docs)]
 #![forbid(unsafe_code)]
 
-use async_graphql::http::MultipartOptions;
-use async_graphql::{ObjectType, Schema, SubscriptionType, Variables};
+use async_graphql::http::{MultipartOptions, ResponseHead};
+use async_graphql::{ObjectType, Schema, SubscriptionType};
 use log::{error, info};
 use rocket::{
     data::{self, FromData},
@@ -42,7 +42,7 @@ use yansi::Paint;
 ///     }
 /// }
 ///
-/// #[rocket::post("/?<query..>")]
+/// #[rocket::get("/?<query..>")]
 /// async fn graphql_query(schema: State<'_, ExampleSchema>, query: Request) -> Result<Response, Status> {
 ///     query.execute(&schema)
 ///         .await
@@ -129,10 +129,13 @@ impl GraphQL {
 /// Implements `FromQuery` and `FromData`, so that it can be used as parameter in a
 /// Rocket route.
 ///
+/// The query parameters are parsed with `async_graphql::http::parse_query_string`, which refuses
+/// mutations.
+///
 /// # Examples
 ///
 /// ```rust,no_run,ignore
-/// #[rocket::post("/?<query..>")]
+/// #[rocket::get("/?<query..>")]
 /// async fn graphql_query(schema: State<'_, ExampleSchema>, query: Request) -> Result<Response, Status> {
 ///     query.execute(&schema)
 ///         .await
@@ -149,23 +152,19 @@ pub struct Request(pub async_graphql::Request);
 impl Request {
     /// Mimics `async_graphql::Schema.execute()`.
     /// Executes the query, always return a complete result.
+    ///
+    /// The errors are logged, and the status of the response is chosen by `Response`.
     pub async fn execute<Q, M, S>(self, schema: &Schema<Q, M, S>) -> Result<Response, Status>
     where
         Q: ObjectType + Send + Sync + 'static,
         M: ObjectType + Send + Sync + 'static,
         S: SubscriptionType + Send + Sync + 'static,
     {
-        schema
-            .execute(self.0)
-            .await
-            .into_result()
-            .map(Response)
-            .map_err(|es| {
-                for e in es {
-                    error!("{}", e);
-                }
-                Status::BadRequest
-            })
+        let resp = schema.execute(self.0).await;
+        for e in &resp.errors {
+            error!("{}", e);
+        }
+        Ok(Response(resp))
     }
 }
 
@@ -173,61 +172,13 @@ impl<'q> FromQuery<'q> for Request {
     type Error = String;
 
     fn from_query(query_items: request::Query) -> Result<Self, Self::Error> {
-        let mut query = None;
-        let mut operation_name = None;
-        let mut variables = None;
-
-        for query_item in query_items {
-            let (key, value) = query_item.key_value();
-            match key.as_str() {
-                "query" => {
-                    if query.is_some() {
-                        return Err(r#"Multiple parameters named "query" found. Only one parameter by that name is allowed."#.to_string());
-                    } else {
-                        query = value.url_decode().map_err(|e| e.to_string())?.into();
-                    }
-                }
-                "operation_name" => {
-                    if operation_name.is_some() {
-                        return Err(r#"Multiple parameters named "operation_name" found. Only one parameter by that name is allowed."#.to_string());
-                    } else {
-                        operation_name = value.url_decode().map_err(|e| e.to_string())?.into();
-                    }
-                }
-                "variables" => {
-                    if variables.is_some() {
-                        return Err(r#"Multiple parameters named "variables" found. Only one parameter by that name is allowed."#.to_string());
-                    } else {
-                        let decoded = value.url_decode().map_err(|e| e.to_string())?;
-                        let json_value = serde_json::from_str::<serde_json::Value>(&decoded)
-                            .map_err(|e| e.to_string())?;
-                        variables = Variables::from_json(json_value).into();
-                    }
-                }
-                _ => {
-                    return Err(format!(
-                        r#"Extra parameter named "{}" found. Extra parameters are not allowed."#,
-                        key
-                    ));
-                }
-            }
-        }
-
-        if let Some(query_source) = query {
-            let mut request = async_graphql::Request::new(query_source);
-
-            if let Some(variables) = variables {
-                request = request.variables(variables);
-            }
-
-            if let Some(operation_name) = operation_name {
-                request = request.operation_name(operation_name);
-            }
-
-            Ok(Request(request))
-        } else {
-            Err(r#"Parameter "query" missing from request."#.to_string())
-        }
+        let query = query_items
+            .map(|query_item| query_item.raw.as_str())
+            .collect::<Vec<_>>()
+            .join("&");
+        async_graphql::http::parse_query_string(&query)
+            .map(Request)
+            .map_err(|e| e.to_string())
     }
 }
 
@@ -258,7 +209,13 @@ impl FromData for Request {
 
         match request {
             Ok(request) => data::Outcome::Success(Request(request)),
-            Err(e) => data::Outcome::Failure((Status::BadRequest, format!("{}", e))),
+            Err(e) => {
+                let head = ResponseHead::for_error(req.headers().get_one("Accept"), &e);
+                data::Outcome::Failure((
+                    Status::from_code(head.status).unwrap_or(Status::BadRequest),
+                    format!("{}", e),
+                ))
+            }
         }
     }
 }
@@ -266,17 +223,22 @@ impl FromData for Request {
 /// Wrapper around `async-graphql::Response` for implementing the trait
 /// `rocket::response::responder::Responder`, so that `Response` can directly be returned
 /// from a Rocket Route function.
+///
+/// The status and content type of the response are chosen from the `Accept` header of the
+/// request, see `async_graphql::http::ResponseHead`.
 pub struct Response(pub async_graphql::Response);
 
 impl<'r> Responder<'r, 'static> for Response {
-    fn respond_to(self, _: &'r RocketRequest<'_>) -> response::Result<'static> {
+    fn respond_to(self, req: &'r RocketRequest<'_>) -> response::Result<'static> {
         let body = serde_json::to_string(&self.0).unwrap();
 
-        RocketResponse::build()
-            .header(ContentType::new("application", "json"))
-            .status(Status::Ok)
+        let mut builder = RocketResponse::build();
+        builder.cache_control(&self.0);
+        let head = ResponseHead::for_response(req.headers().get_one("Accept"), &self.0.into());
+        builder
+            .header(ContentType::parse_flexible(head.content_type).unwrap_or(ContentType::JSON))
+            .status(Status::from_code(head.status).unwrap_or(Status::Ok))
             .sized_body(body.len(), Cursor::new(body))
-            .cache_control(&self.0)
             .ok()
     }
 }
@@ -8,9 +8,10 @@
 #![allow(clippy::needless_doctest_main)]
 #![forbid(unsafe_code)]
 
-use async_graphql::http::MultipartOptions;
-use async_graphql::{ObjectType, ParseRequestError, Schema, SubscriptionType};
+use async_graphql::http::{MultipartOptions, ResponseHead};
+use async_graphql::{ObjectType, ParseRequestError, Schema, ServerError, SubscriptionType};
 use async_trait::async_trait;
+use std::convert::TryFrom;
 use tide::{
     http::{
         headers::{self, HeaderValue},
@@ -63,7 +64,7 @@ impl<Query, Mutation, Subscription> Clone for Endpoint<Query, Mutation, Subscrip
     fn clone(&self) -> Self {
         Self {
             schema: self.schema.clone(),
-            opts: self.opts,
+            opts: self.opts.clone(),
             batch: self.batch,
         }
     }
@@ -79,17 +80,20 @@ where
     TideState: Clone + Send + Sync + 'static,
 {
     async fn call(&self, request: Request<TideState>) -> tide::Result {
-        respond(
-            self.schema
-                .execute_batch(if self.batch {
-                    receive_batch_request_opts(request, self.opts).await
+        let accept = accept(&request);
+        let batch_request = receive_batch(request, self.opts.clone())
+            .await
+            .and_then(|request| {
+                if self.batch {
+                    Ok(request)
                 } else {
-                    receive_request_opts(request, self.opts)
-                        .await
-                        .map(Into::into)
-                }?)
-                .await,
-        )
+                    request.into_single().map(Into::into)
+                }
+            });
+        match batch_request {
+            Ok(request) => respond_to(accept.as_deref(), self.schema.execute_batch(request).await),
+            Err(err) => respond_error(accept.as_deref(), &err),
+        }
     }
 }
 
@@ -105,10 +109,11 @@ pub async fn receive_request_opts<State: Clone + Send + Sync + 'static>(
     request: Request<State>,
     opts: MultipartOptions,
 ) -> tide::Result<async_graphql::Request> {
-    receive_batch_request_opts(request, opts)
-        .await?
-        .into_single()
-        .map_err(|e| tide::Error::new(StatusCode::BadRequest, e))
+    let accept = accept(&request);
+    receive_batch(request, opts)
+        .await
+        .and_then(async_graphql::BatchRequest::into_single)
+        .map_err(|e| error(accept.as_deref(), e))
 }
 
 /// Convert a Tide request to a GraphQL batch request.
@@ -120,11 +125,22 @@ pub async fn receive_batch_request<State: Clone + Send + Sync + 'static>(
 
 /// Convert a Tide request to a GraphQL batch request with options on how to receive multipart.
 pub async fn receive_batch_request_opts<State: Clone + Send + Sync + 'static>(
-    mut request: Request<State>,
+    request: Request<State>,
     opts: MultipartOptions,
 ) -> tide::Result<async_graphql::BatchRequest> {
+    let accept = accept(&request);
+    receive_batch(request, opts)
+        .await
+        .map_err(|e| error(accept.as_deref(), e))
+}
+
+async fn receive_batch<State: Clone + Send + Sync + 'static>(
+    mut request: Request<State>,
+    opts: MultipartOptions,
+) -> Result<async_graphql::BatchRequest, ParseRequestError> {
     if request.method() == Method::Get {
-        request.query::<async_graphql::Request>().map(Into::into)
+        async_graphql::http::parse_query_string(request.url().query().unwrap_or_default())
+            .map(Into::into)
     } else {
         let body = request.take_body();
         let content_type = request
@@ -132,30 +148,59 @@ pub async fn receive_batch_request_opts<State: Clone + Send + Sync + 'static>(
             .and_then(|values| values.get(0))
             .map(HeaderValue::as_str);
 
-        async_graphql::http::receive_batch_body(content_type, body, opts)
-            .await
-            .map_err(|e| {
-                tide::Error::new(
-                    match &e {
-                        ParseRequestError::PayloadTooLarge => StatusCode::PayloadTooLarge,
-                        _ => StatusCode::BadRequest,
-                    },
-                    e,
-                )
-            })
+        async_graphql::http::receive_batch_body(content_type, body, opts).await
     }
 }
 
+fn accept<State>(request: &Request<State>) -> Option<String> {
+    request
+        .header(headers::ACCEPT)
+        .and_then(|values| values.get(0))
+        .map(|value| value.as_str().to_string())
+}
+
+fn status(head: ResponseHead) -> StatusCode {
+    StatusCode::try_from(head.status).unwrap_or(StatusCode::BadRequest)
+}
+
+fn error(accept: Option<&str>, e: ParseRequestError) -> tide::Error {
+    tide::Error::new(status(ResponseHead::for_error(accept, &e)), e)
+}
+
+fn respond_error(accept: Option<&str>, e: &ParseRequestError) -> tide::Result {
+    let head = ResponseHead::for_error(accept, e);
+    let gql = async_graphql::Response::from_errors(vec![ServerError::new(e.to_string())]);
+
+    let mut response = Response::new(status(head));
+    response.set_body(Body::from_json(&gql)?);
+    response.insert_header(headers::CONTENT_TYPE, head.content_type);
+    Ok(response)
+}
+
 /// Convert a GraphQL response to a Tide response.
+///
+/// The response has the `application/json` content type, use [`respond_to`](fn.respond_to.html)
+/// to choose it from the `Accept` header of the request.
 pub fn respond(gql: impl Into<async_graphql::BatchResponse>) -> tide::Result {
+    respond_to(None, gql)
+}
+
+/// Convert a GraphQL response to a Tide response, with the status and content type chosen from
+/// the `Accept` header of the request.
+pub fn respond_to(
+    accept: Option<&str>,
+    gql: impl Into<async_graphql::BatchResponse>,
+) -> tide::Result {
     let gql = gql.into();
+    let head = ResponseHead::for_response(accept, &gql);
 
-    let mut response = Response::new(StatusCode::Ok);
+    let mut response = Response::new(status(head));
     if gql.is_ok() {
         if let Some(cache_control) = gql.cache_control().value() {
             response.insert_header(headers::CACHE_CONTROL, cache_control);
         }
     }
     response.set_body(Body::from_json(&gql)?);
+    response.insert_header(headers::CONTENT_TYPE, head.content_type);
     Ok(response)
 }
@@ -68,6 +68,40 @@ fn quickstart() -> Result<()> {
 
             assert_eq!(string, json!({"data": {"add": 30}}).to_string());
 
+            //
+            let resp = reqwest::Client::builder()
+                .no_proxy()
+                .build()
+                .unwrap()
+                .post(format!("http://{}", listen_addr).as_str())
+                .body(r#"{"query":"{ add(a: 10) }"}"#)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(reqwest::header::ACCEPT, "application/graphql-response+json")
+                .send()
+                .await?;
+
+            assert_eq!(resp.status(), reqwest::StatusCode::BAD_REQUEST);
+            assert_eq!(
+                resp.headers()[reqwest::header::CONTENT_TYPE],
+                "application/graphql-response+json"
+            );
+
+            //
+            let resp = reqwest::Client::builder()
+                .no_proxy()
+                .build()
+                .unwrap()
+                .get(format!("http://{}", listen_addr).as_str())
+                .query(&[("query", "mutation { add }")])
+                .send()
+                .await?;
+
+            assert_eq!(resp.status(), reqwest::StatusCode::METHOD_NOT_ALLOWED);
+            assert_eq!(
+                resp.headers()[reqwest::header::CONTENT_TYPE],
+                "application/json"
+            );
+
             Ok(())
         });
 
@@ -1,10 +1,11 @@
 use crate::BadRequest;
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{MultipartOptions, ResponseHead};
 use async_graphql::{ObjectType, Schema, SubscriptionType};
 use futures::TryStreamExt;
 use std::io;
 use std::io::ErrorKind;
 use std::sync::Arc;
+use warp::http::Method;
 use warp::reply::Response as WarpResponse;
 use warp::{Buf, Filter, Rejection, Reply};
 
@@ -46,34 +47,55 @@ where
 {
     let opts = Arc::new(opts);
     warp::any()
+        .and(warp::method())
+        .and(warp::query::raw().or(warp::any().map(String::new)).unify())
         .and(warp::header::optional::<String>("content-type"))
+        .and(warp::header::optional::<String>("accept"))
         .and(warp::body::stream())
         .and(warp::any().map(move || opts.clone()))
         .and(warp::any().map(move || schema.clone()))
         .and_then(
-            |content_type, body, opts: Arc<MultipartOptions>, schema| async move {
-                let request = async_graphql::http::receive_batch_body(
-                    content_type,
-                    futures::TryStreamExt::map_err(body, |err| {
-                        io::Error::new(ErrorKind::Other, err)
-                    })
-                    .map_ok(|mut buf| Buf::to_bytes(&mut buf))
-                    .into_async_read(),
-                    MultipartOptions::clone(&opts),
-                )
-                .await
-                .map_err(|err| warp::reject::custom(BadRequest(err.into())))?;
+            |method,
+             query: String,
+             content_type,
+             accept: Option<String>,
+             body,
+             opts: Arc<MultipartOptions>,
+             schema| async move {
+                let request = if method == Method::GET {
+                    async_graphql::http::parse_query_string(&query).map(Into::into)
+                } else {
+                    async_graphql::http::receive_batch_body(
+                        content_type,
+                        futures::TryStreamExt::map_err(body, |err| {
+                            io::Error::new(ErrorKind::Other, err)
+                        })
+                        .map_ok(|mut buf| Buf::to_bytes(&mut buf))
+                        .into_async_read(),
+                        MultipartOptions::clone(&opts),
+                    )
+                    .await
+                }
+                .map_err(|err| warp::reject::custom(BadRequest::new(accept.as_deref(), err)))?;
                 Ok::<_, Rejection>((schema, request))
             },
         )
 }
 
 /// Reply for `async_graphql::BatchRequest`.
-pub struct BatchResponse(async_graphql::BatchResponse);
+pub struct BatchResponse(async_graphql::BatchResponse, Option<String>);
+
+impl BatchResponse {
+    /// Set the `Accept` header of the request, which chooses the status and content type of the
+    /// response, see `async_graphql::http::ResponseHead`.
+    pub fn with_accept(self, accept: Option<String>) -> Self {
+        BatchResponse(self.0, accept)
+    }
+}
 
 impl From<async_graphql::BatchResponse> for BatchResponse {
     fn from(resp: async_graphql::BatchResponse) -> Self {
-        BatchResponse(resp)
+        BatchResponse(resp, None)
     }
 }
 
@@ -89,13 +111,12 @@ fn add_cache_control(http_resp: &mut WarpResponse, resp: &async_graphql::BatchRe
 
 impl Reply for BatchResponse {
     fn into_response(self) -> WarpResponse {
-        let mut resp = warp::reply::with_header(
-            warp::reply::json(&self.0),
-            "content-type",
-            "application/json",
-        )
-        .into_response();
+        let mut resp = warp::reply::json(&self.0).into_response();
         add_cache_control(&mut resp, &self.0);
+        crate::set_response_head(
+            &mut resp,
+            ResponseHead::for_response(self.1.as_deref(), &self.0),
+        );
         resp
     }
 }
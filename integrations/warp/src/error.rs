@@ -1,9 +1,35 @@
+use async_graphql::http::ResponseHead;
+use async_graphql::{ParseRequestError, ServerError};
 use warp::reject::Reject;
+use warp::reply::Response as WarpResponse;
+use warp::Reply;
 
 /// Bad request error
 ///
 /// It's a wrapper of `async_graphql::ParseRequestError`.
-pub struct BadRequest(pub anyhow::Error);
+pub struct BadRequest(pub anyhow::Error, ResponseHead);
+
+impl BadRequest {
+    pub(crate) fn new(accept: Option<&str>, err: ParseRequestError) -> Self {
+        let head = ResponseHead::for_error(accept, &err);
+        Self(err.into(), head)
+    }
+
+    /// The status and content type of the response, chosen from the error and the `Accept` header
+    /// of the request.
+    pub fn response_head(&self) -> ResponseHead {
+        self.1
+    }
+
+    /// Create a GraphQL response with the error, which can be returned when the rejection is
+    /// recovered.
+    pub fn reply(&self) -> WarpResponse {
+        let gql = async_graphql::Response::from_errors(vec![ServerError::new(self.0.to_string())]);
+        let mut resp = warp::reply::json(&gql).into_response();
+        crate::set_response_head(&mut resp, self.1);
+        resp
+    }
+}
 
 impl std::fmt::Debug for BadRequest {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -12,4 +12,20 @@ mod subscription;
 pub use batch_request::{graphql_batch, graphql_batch_opts, BatchResponse};
 pub use error::BadRequest;
 pub use request::{graphql, graphql_opts, Response};
-pub use subscription::{graphql_subscription, graphql_subscription_with_data};
+pub use subscription::{
+    graphql_subscription, graphql_subscription_upgrade, graphql_subscription_upgrade_with_data,
+    graphql_subscription_upgrade_with_options, graphql_subscription_with_data,
+    graphql_subscription_with_options,
+};
+
+use async_graphql::http::ResponseHead;
+use warp::http::StatusCode;
+use warp::reply::Response as WarpResponse;
+
+fn set_response_head(resp: &mut WarpResponse, head: ResponseHead) {
+    *resp.status_mut() = StatusCode::from_u16(head.status).unwrap_or(StatusCode::OK);
+    resp.headers_mut().insert(
+        "content-type",
+        warp::http::HeaderValue::from_static(head.content_type),
+    );
+}
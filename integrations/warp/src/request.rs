@@ -1,5 +1,5 @@
 use crate::BadRequest;
-use async_graphql::http::MultipartOptions;
+use async_graphql::http::{MultipartOptions, ResponseHead};
 use async_graphql::{ObjectType, Schema, SubscriptionType};
 use futures::TryStreamExt;
 use std::io;
@@ -38,10 +38,14 @@ use warp::{Buf, Filter, Rejection, Reply};
 /// #[tokio::main]
 /// async fn main() {
 ///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
-///     let filter = async_graphql_warp::graphql(schema).
-///             and_then(|(schema, request): (MySchema, async_graphql::Request)| async move {
-///         Ok::<_, Infallible>(async_graphql_warp::Response::from(schema.execute(request).await))
-///     });
+///     let filter = async_graphql_warp::graphql(schema)
+///         .and(warp::header::optional::<String>("accept"))
+///         .and_then(|(schema, request): (MySchema, async_graphql::Request), accept| async move {
+///             Ok::<_, Infallible>(
+///                 async_graphql_warp::Response::from(schema.execute(request).await)
+///                     .with_accept(accept),
+///             )
+///         });
 ///     warp::serve(filter).run(([0, 0, 0, 0], 8000)).await;
 /// }
 /// ```
@@ -83,6 +87,7 @@ where
         .and(warp::method())
         .and(warp::query::raw().or(warp::any().map(String::new)).unify())
         .and(warp::header::optional::<String>("content-type"))
+        .and(warp::header::optional::<String>("accept"))
         .and(warp::body::stream())
         .and(warp::any().map(move || opts.clone()))
         .and(warp::any().map(move || schema.clone()))
@@ -90,23 +95,28 @@ where
             |method,
              query: String,
              content_type,
+             accept: Option<String>,
              body,
              opts: Arc<MultipartOptions>,
              schema| async move {
                 if method == Method::GET {
-                    let request: async_graphql::Request = serde_urlencoded::from_str(&query)
-                        .map_err(|err| warp::reject::custom(BadRequest(err.into())))?;
+                    let request =
+                        async_graphql::http::parse_query_string(&query).map_err(|err| {
+                            warp::reject::custom(BadRequest::new(accept.as_deref(), err))
+                        })?;
                     Ok::<_, Rejection>((schema, request))
                 } else {
                     let request = async_graphql::http::receive_body(
                         content_type,
-                        futures::TryStreamExt::map_err(body, |err| io::Error::new(ErrorKind::Other, err))
-                            .map_ok(|mut buf| Buf::to_bytes(&mut buf))
-                            .into_async_read(),
+                        futures::TryStreamExt::map_err(body, |err| {
+                            io::Error::new(ErrorKind::Other, err)
+                        })
+                        .map_ok(|mut buf| Buf::to_bytes(&mut buf))
+                        .into_async_read(),
                         MultipartOptions::clone(&opts),
                     )
-                        .await
-                        .map_err(|err| warp::reject::custom(BadRequest(err.into())))?;
+                    .await
+                    .map_err(|err| warp::reject::custom(BadRequest::new(accept.as_deref(), err)))?;
                     Ok::<_, Rejection>((schema, request))
                 }
             },
@@ -114,11 +124,19 @@ where
 }
 
 /// Reply for `async_graphql::Request`.
-pub struct Response(async_graphql::Response);
+pub struct Response(async_graphql::Response, Option<String>);
+
+impl Response {
+    /// Set the `Accept` header of the request, which chooses the status and content type of the
+    /// response, see `async_graphql::http::ResponseHead`.
+    pub fn with_accept(self, accept: Option<String>) -> Self {
+        Response(self.0, accept)
+    }
+}
 
 impl From<async_graphql::Response> for Response {
     fn from(resp: async_graphql::Response) -> Self {
-        Response(resp)
+        Response(resp, None)
     }
 }
 
@@ -134,13 +152,10 @@ fn add_cache_control(http_resp: &mut WarpResponse, resp: &async_graphql::Respons
 
 impl Reply for Response {
     fn into_response(self) -> WarpResponse {
-        let mut resp = warp::reply::with_header(
-            warp::reply::json(&self.0),
-            "content-type",
-            "application/json",
-        )
-        .into_response();
+        let mut resp = warp::reply::json(&self.0).into_response();
         add_cache_control(&mut resp, &self.0);
+        let head = ResponseHead::for_response(self.1.as_deref(), &self.0.into());
+        crate::set_response_head(&mut resp, head);
         resp
     }
 }
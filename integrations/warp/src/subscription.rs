@@ -1,5 +1,6 @@
+use async_graphql::http::{WebSocketOptions, WebSocketProtocols, WsMessage};
 use async_graphql::{Data, ObjectType, Result, Schema, SubscriptionType};
-use futures::{future, StreamExt};
+use futures::{future, Future, StreamExt};
 use warp::filters::ws;
 use warp::{Filter, Rejection, Reply};
 
@@ -44,13 +45,19 @@ where
     Mutation: ObjectType + Sync + Send + 'static,
     Subscription: SubscriptionType + Send + Sync + 'static,
 {
-    graphql_subscription_with_data::<_, _, _, fn(serde_json::Value) -> Result<Data>>(schema, None)
+    graphql_subscription_with_data::<_, _, _, fn(serde_json::Value) -> future::Ready<Result<Data>>, _>(
+        schema, None,
+    )
 }
 
 /// GraphQL subscription filter
 ///
-/// Specifies that a function converts the init payload to data.
-pub fn graphql_subscription_with_data<Query, Mutation, Subscription, F>(
+/// Specifies that an async function converts the init payload to data.
+///
+/// The protocol is negotiated with the `Sec-WebSocket-Protocol` header of the client, see
+/// `async_graphql::http::WebSocketProtocols::negotiate`. Use `graphql_subscription_upgrade_with_data`
+/// in your own filter to choose the protocol yourself.
+pub fn graphql_subscription_with_data<Query, Mutation, Subscription, F, R>(
     schema: Schema<Query, Mutation, Subscription>,
     initializer: Option<F>,
 ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone
@@ -58,33 +65,142 @@ where
     Query: ObjectType + Sync + Send + 'static,
     Mutation: ObjectType + Sync + Send + 'static,
     Subscription: SubscriptionType + Send + Sync + 'static,
-    F: FnOnce(serde_json::Value) -> Result<Data> + Send + Sync + Clone + 'static,
+    F: FnOnce(serde_json::Value) -> R + Send + Sync + Clone + 'static,
+    R: Future<Output = Result<Data>> + Send + 'static,
+{
+    graphql_subscription_with_options(schema, initializer, WebSocketOptions::default())
+}
+
+/// GraphQL subscription filter
+///
+/// Specifies that an async function converts the init payload to data, and the options of the
+/// connections, such as the keep-alive interval.
+pub fn graphql_subscription_with_options<Query, Mutation, Subscription, F, R>(
+    schema: Schema<Query, Mutation, Subscription>,
+    initializer: Option<F>,
+    options: WebSocketOptions,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone
+where
+    Query: ObjectType + Sync + Send + 'static,
+    Mutation: ObjectType + Sync + Send + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+    F: FnOnce(serde_json::Value) -> R + Send + Sync + Clone + 'static,
+    R: Future<Output = Result<Data>> + Send + 'static,
 {
     warp::any()
         .and(warp::ws())
+        .and(
+            warp::header::optional::<String>("sec-websocket-protocol").and_then(
+                |protocols: Option<String>| async move {
+                    WebSocketProtocols::negotiate(protocols.as_deref()).ok_or_else(warp::reject)
+                },
+            ),
+        )
         .and(warp::any().map(move || schema.clone()))
         .and(warp::any().map(move || initializer.clone()))
         .map(
-            |ws: ws::Ws, schema: Schema<Query, Mutation, Subscription>, initializer: Option<F>| {
-                ws.on_upgrade(move |websocket| {
-                    let (ws_sender, ws_receiver) = websocket.split();
-
-                    async move {
-                        let _ = async_graphql::http::WebSocket::with_data(
-                            schema,
-                            ws_receiver
-                                .take_while(|msg| future::ready(msg.is_ok()))
-                                .map(Result::unwrap)
-                                .map(ws::Message::into_bytes),
-                            initializer,
-                        )
-                        .map(ws::Message::text)
-                        .map(Ok)
-                        .forward(ws_sender)
-                        .await;
-                    }
-                })
+            move |ws: ws::Ws,
+                  protocol: WebSocketProtocols,
+                  schema: Schema<Query, Mutation, Subscription>,
+                  initializer: Option<F>| {
+                let options = options.clone();
+                let reply = ws.on_upgrade(move |websocket| {
+                    graphql_subscription_upgrade_with_options(
+                        websocket,
+                        protocol,
+                        schema,
+                        initializer,
+                        options,
+                    )
+                });
+                warp::reply::with_header(
+                    reply,
+                    "Sec-WebSocket-Protocol",
+                    protocol.sec_websocket_protocol(),
+                )
             },
         )
-        .map(|reply| warp::reply::with_header(reply, "Sec-WebSocket-Protocol", "graphql-ws"))
+}
+
+/// Serve a GraphQL subscription over an upgraded websocket with the protocol `protocol`.
+pub async fn graphql_subscription_upgrade<Query, Mutation, Subscription>(
+    websocket: ws::WebSocket,
+    protocol: WebSocketProtocols,
+    schema: Schema<Query, Mutation, Subscription>,
+) where
+    Query: ObjectType + Sync + Send + 'static,
+    Mutation: ObjectType + Sync + Send + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+{
+    graphql_subscription_upgrade_with_data::<
+        _,
+        _,
+        _,
+        fn(serde_json::Value) -> future::Ready<Result<Data>>,
+        _,
+    >(websocket, protocol, schema, None)
+    .await
+}
+
+/// Serve a GraphQL subscription over an upgraded websocket with the protocol `protocol`.
+///
+/// Specifies that an async function converts the init payload to data.
+pub async fn graphql_subscription_upgrade_with_data<Query, Mutation, Subscription, F, R>(
+    websocket: ws::WebSocket,
+    protocol: WebSocketProtocols,
+    schema: Schema<Query, Mutation, Subscription>,
+    initializer: Option<F>,
+) where
+    Query: ObjectType + Sync + Send + 'static,
+    Mutation: ObjectType + Sync + Send + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+    F: FnOnce(serde_json::Value) -> R + Send + Sync + 'static,
+    R: Future<Output = Result<Data>> + Send + 'static,
+{
+    graphql_subscription_upgrade_with_options(
+        websocket,
+        protocol,
+        schema,
+        initializer,
+        WebSocketOptions::default(),
+    )
+    .await
+}
+
+/// Serve a GraphQL subscription over an upgraded websocket with the protocol `protocol`.
+///
+/// Specifies that an async function converts the init payload to data, and the options of the
+/// connection.
+pub async fn graphql_subscription_upgrade_with_options<Query, Mutation, Subscription, F, R>(
+    websocket: ws::WebSocket,
+    protocol: WebSocketProtocols,
+    schema: Schema<Query, Mutation, Subscription>,
+    initializer: Option<F>,
+    options: WebSocketOptions,
+) where
+    Query: ObjectType + Sync + Send + 'static,
+    Mutation: ObjectType + Sync + Send + 'static,
+    Subscription: SubscriptionType + Send + Sync + 'static,
+    F: FnOnce(serde_json::Value) -> R + Send + Sync + 'static,
+    R: Future<Output = Result<Data>> + Send + 'static,
+{
+    let (ws_sender, ws_receiver) = websocket.split();
+
+    let _ = async_graphql::http::WebSocket::with_data(
+        schema,
+        ws_receiver
+            .take_while(|msg| future::ready(msg.is_ok()))
+            .map(Result::unwrap)
+            .map(ws::Message::into_bytes),
+        initializer,
+        protocol,
+    )
+    .options(options)
+    .map(|msg| match msg {
+        WsMessage::Text(text) => ws::Message::text(text),
+        WsMessage::Close(code, reason) => ws::Message::close_with(code, reason),
+    })
+    .map(Ok)
+    .forward(ws_sender)
+    .await;
 }
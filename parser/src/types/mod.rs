@@ -111,7 +111,8 @@ impl Display for BaseType {
 /// A resolved GraphQL value, for example `1` or `"Hello World!"`.
 ///
 /// It can be serialized and deserialized. Enums will be converted to strings. Attempting to
-/// serialize `Upload` will fail, and `Enum` and `Upload` cannot be deserialized.
+/// serialize `Upload` or `StoredUpload` will fail, and `Enum`, `Upload` and `StoredUpload` cannot
+/// be deserialized.
 ///
 /// [Reference](https://spec.graphql.org/June2018/#Value).
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -135,6 +136,9 @@ pub enum ConstValue {
     /// An uploaded file.
     #[serde(serialize_with = "fail_serialize_upload", skip_deserializing)]
     Upload(UploadValue),
+    /// An uploaded file that is not stored in a file.
+    #[serde(serialize_with = "fail_serialize_stored_upload", skip_deserializing)]
+    StoredUpload(StoredUploadValue),
 }
 
 impl ConstValue {
@@ -156,6 +160,7 @@ impl ConstValue {
                     .collect(),
             ),
             Self::Upload(upload) => Value::Upload(upload),
+            Self::StoredUpload(upload) => Value::StoredUpload(upload),
         }
     }
 
@@ -191,7 +196,7 @@ impl Display for ConstValue {
             Self::String(val) => write_quoted(val, f),
             Self::Boolean(true) => f.write_str("true"),
             Self::Boolean(false) => f.write_str("false"),
-            Self::Null | Self::Upload(_) => f.write_str("null"),
+            Self::Null | Self::Upload(_) | Self::StoredUpload(_) => f.write_str("null"),
             Self::Enum(name) => f.write_str(name),
             Self::List(items) => write_list(items, f),
             Self::Object(map) => write_object(map, f),
@@ -216,8 +221,8 @@ impl TryFrom<ConstValue> for serde_json::Value {
 /// [`ConstValue`](enum.ConstValue.html) with variables.
 ///
 /// It can be serialized and deserialized. Enums will be converted to strings. Attempting to
-/// serialize `Upload` or `Variable` will fail, and `Enum`, `Upload` and `Variable` cannot be
-/// deserialized.
+/// serialize `Upload`, `StoredUpload` or `Variable` will fail, and `Enum`, `Upload`,
+/// `StoredUpload` and `Variable` cannot be deserialized.
 ///
 /// [Reference](https://spec.graphql.org/June2018/#Value).
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -244,6 +249,9 @@ pub enum Value {
     /// An uploaded file.
     #[serde(serialize_with = "fail_serialize_upload", skip_deserializing)]
     Upload(UploadValue),
+    /// An uploaded file that is not stored in a file.
+    #[serde(serialize_with = "fail_serialize_stored_upload", skip_deserializing)]
+    StoredUpload(StoredUploadValue),
 }
 
 impl Value {
@@ -278,6 +286,7 @@ impl Value {
                     .collect::<Result<_, _>>()?,
             ),
             Self::Upload(upload) => ConstValue::Upload(upload),
+            Self::StoredUpload(upload) => ConstValue::StoredUpload(upload),
         })
     }
 
@@ -322,7 +331,7 @@ impl Display for Value {
             Self::String(val) => write_quoted(val, f),
             Self::Boolean(true) => f.write_str("true"),
             Self::Boolean(false) => f.write_str("false"),
-            Self::Null | Self::Upload(_) => f.write_str("null"),
+            Self::Null | Self::Upload(_) | Self::StoredUpload(_) => f.write_str("null"),
             Self::Enum(name) => f.write_str(name),
             Self::List(items) => write_list(items, f),
             Self::Object(map) => write_object(map, f),
@@ -355,6 +364,12 @@ fn fail_serialize_variable<S: Serializer>(_: &str, _: S) -> Result<S::Ok, S::Err
 fn fail_serialize_upload<S: Serializer>(_: &UploadValue, _: S) -> Result<S::Ok, S::Error> {
     Err(S::Error::custom("cannot serialize uploaded file"))
 }
+fn fail_serialize_stored_upload<S: Serializer>(
+    _: &StoredUploadValue,
+    _: S,
+) -> Result<S::Ok, S::Error> {
+    Err(S::Error::custom("cannot serialize uploaded file"))
+}
 
 fn write_quoted(s: &str, f: &mut Formatter<'_>) -> fmt::Result {
     f.write_char('"')?;
@@ -435,6 +450,19 @@ impl PartialEq for UploadValue {
 }
 impl Eq for UploadValue {}
 
+/// A file upload value whose content is not stored in a file.
+///
+/// The content is kept by the request, this refers to it by its index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredUploadValue {
+    /// The index of the content in the uploads of the request.
+    pub index: usize,
+    /// The name of the file, `None` if the file is streamed and it is only known once it is read.
+    pub filename: Option<String>,
+    /// The content type of the file.
+    pub content_type: Option<String>,
+}
+
 /// A const GraphQL directive, such as `@deprecated(reason: "Use the other field)`. This differs
 /// from [`Directive`](struct.Directive.html) in that it uses [`ConstValue`](enum.ConstValue.html)
 /// instead of [`Value`](enum.Value.html).
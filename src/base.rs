@@ -1,8 +1,8 @@
 use crate::parser::types::Field;
 use crate::registry::Registry;
 use crate::{
-    registry, ContainerType, ContextSelectionSet, InputValueResult, Positioned, Result,
-    ServerResult, Value,
+    registry, ContainerType, Context, ContextSelectionSet, Error, InputValueResult, Positioned,
+    Result, ServerResult, Value,
 };
 use std::borrow::Cow;
 
@@ -94,11 +94,43 @@ impl<T: OutputValueType + Sync> OutputValueType for Result<T> {
     ) -> ServerResult<serde_json::Value> {
         match self {
             Ok(value) => Ok(value.resolve(ctx, field).await?),
-            Err(err) => Err(err.clone().into_server_error().at(field.pos)),
+            Err(err) => Err(ctx.convert_error(err.clone(), field.pos)),
         }
     }
 }
 
+/// An error that can be returned by a resolver.
+///
+/// It is implemented for [`Error`](struct.Error.html) and for the types that derive `GraphQLError`,
+/// which fail the field, and for the types that derive `Union`, which make the field resolve to a
+/// [`ResultUnion`](types/enum.ResultUnion.html) instead.
+pub trait ResolverError<T>: Sized {
+    /// The value of the field.
+    type Output: OutputValueType + Send + Sync;
+
+    /// Converts the result of a resolver into the value of the field.
+    fn into_output(res: std::result::Result<T, Self>, ctx: &Context<'_>)
+        -> ServerResult<Self::Output>;
+}
+
+impl<T: OutputValueType + Send + Sync> ResolverError<T> for Error {
+    type Output = T;
+
+    fn into_output(res: Result<T>, ctx: &Context<'_>) -> ServerResult<T> {
+        res.map_err(|err| ctx.convert_error(err, ctx.item.pos))
+    }
+}
+
+/// The value of a field whose resolver returns this type.
+#[doc(hidden)]
+pub trait ResolverResult {
+    type Output: OutputValueType + Send + Sync;
+}
+
+impl<T, E: ResolverError<T>> ResolverResult for std::result::Result<T, E> {
+    type Output = E::Output;
+}
+
 /// A GraphQL object.
 pub trait ObjectType: ContainerType {}
 
@@ -1,17 +1,20 @@
+use crate::error::ErrorMask;
 use crate::extensions::Extensions;
 use crate::parser::types::{
     Directive, Field, FragmentDefinition, Name, OperationDefinition, SelectionSet,
     Value as InputValue,
 };
 use crate::schema::SchemaEnv;
+use crate::validators::AsyncInputValueValidator;
 use crate::{
-    Error, InputValueType, Lookahead, Pos, Positioned, Result, ServerError, ServerResult, Value,
+    Error, ExtendError, InputValueType, IntrospectionMode, Lookahead, Pos, Positioned, Result,
+    ServerError, ServerResult, Value,
 };
 use fnv::FnvHashMap;
 use serde::ser::{SerializeSeq, Serializer};
 use serde::{Deserialize, Serialize};
 use std::any::{Any, TypeId};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryFrom;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::ops::Deref;
@@ -104,6 +107,13 @@ impl Data {
     pub fn insert<D: Any + Send + Sync>(&mut self, data: D) {
         self.0.insert(TypeId::of::<D>(), Box::new(data));
     }
+
+    /// Gets the data of the specified type or `None` if it does not exist.
+    pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&D> {
+        self.0
+            .get(&TypeId::of::<D>())
+            .and_then(|d| d.downcast_ref::<D>())
+    }
 }
 
 impl Debug for Data {
@@ -112,6 +122,56 @@ impl Debug for Data {
     }
 }
 
+/// Context for the functions that decide whether a field, enum value or type is visible.
+///
+/// It gives access to the data of the request and of the schema, but not to the query being
+/// executed, so that the same functions can be used when exporting the SDL of the schema.
+#[derive(Clone, Copy)]
+pub struct VisibleContext<'a> {
+    schema_data: &'a Data,
+    query_data: &'a Data,
+}
+
+impl<'a> VisibleContext<'a> {
+    pub(crate) fn new(schema_data: &'a Data, query_data: &'a Data) -> Self {
+        Self {
+            schema_data,
+            query_data,
+        }
+    }
+
+    /// Gets the global data defined in the request or `Schema`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Error` if the specified type data does not exist.
+    pub fn data<D: Any + Send + Sync>(&self) -> Result<&'a D> {
+        self.data_opt::<D>().ok_or_else(|| {
+            Error::new(format!(
+                "Data `{}` does not exist.",
+                std::any::type_name::<D>()
+            ))
+        })
+    }
+
+    /// Gets the global data defined in the request or `Schema`.
+    ///
+    /// # Panics
+    ///
+    /// It will panic if the specified data type does not exist.
+    pub fn data_unchecked<D: Any + Send + Sync>(&self) -> &'a D {
+        self.data_opt::<D>()
+            .unwrap_or_else(|| panic!("Data `{}` does not exist.", std::any::type_name::<D>()))
+    }
+
+    /// Gets the global data defined in the request or `Schema` or `None` if the specified type data does not exist.
+    pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&'a D> {
+        self.query_data
+            .data_opt::<D>()
+            .or_else(|| self.schema_data.data_opt::<D>())
+    }
+}
+
 /// Context for `SelectionSet`
 pub type ContextSelectionSet<'a> = ContextBase<'a, &'a Positioned<SelectionSet>>;
 
@@ -249,6 +309,10 @@ pub struct QueryEnvInner {
     pub operation: Positioned<OperationDefinition>,
     pub fragments: HashMap<Name, Positioned<FragmentDefinition>>,
     pub ctx_data: Arc<Data>,
+    pub introspection_mode: IntrospectionMode,
+    pub masked_errors: spin::Mutex<HashSet<String>>,
+    #[cfg(feature = "multipart")]
+    pub uploads: Vec<crate::UploadContent>,
 }
 
 #[doc(hidden)]
@@ -264,6 +328,14 @@ impl Deref for QueryEnv {
 }
 
 impl QueryEnv {
+    #[doc(hidden)]
+    pub fn error_mask<'a>(&'a self, schema_env: &'a SchemaEnv) -> Option<ErrorMask<'a>> {
+        schema_env.mask_errors.as_ref().map(|policy| ErrorMask {
+            policy,
+            error_ids: &self.masked_errors,
+        })
+    }
+
     #[doc(hidden)]
     pub fn new(inner: QueryEnvInner) -> QueryEnv {
         QueryEnv(Arc::new(inner))
@@ -290,6 +362,10 @@ impl QueryEnv {
 }
 
 impl<'a, T> ContextBase<'a, T> {
+    pub(crate) fn visible_context(&self) -> VisibleContext<'a> {
+        VisibleContext::new(&self.schema_env.data, &self.query_env.ctx_data)
+    }
+
     #[doc(hidden)]
     pub fn get_child_resolve_id(&self) -> ResolveId {
         let id = self
@@ -371,6 +447,22 @@ impl<'a, T> ContextBase<'a, T> {
             .and_then(|d| d.downcast_ref::<D>())
     }
 
+    /// Convert an error returned by a resolver into a server error.
+    ///
+    /// If the schema has an error mask policy, an error id is added to the internal errors, which
+    /// are masked by the schema before the response is sent.
+    #[doc(hidden)]
+    pub fn convert_error(&self, err: impl Into<Error>, pos: Pos) -> ServerError {
+        let mut err = err.into();
+        if let Some(policy) = &self.schema_env.mask_errors {
+            if let Some(id) = policy.masked_error_id(&err) {
+                err = err.extend(serde_json::json!({ "errorId": id }));
+                self.query_env.masked_errors.lock().insert(id);
+            }
+        }
+        err.into_server_error().at(pos)
+    }
+
     fn var_value(&self, name: &str, pos: Pos) -> ServerResult<Value> {
         self.query_env
             .operation
@@ -448,6 +540,18 @@ impl<'a> ContextBase<'a, &'a Positioned<SelectionSet>> {
             query_env: self.query_env,
         }
     }
+
+    #[doc(hidden)]
+    pub fn with_parent_field(&self, field: &'a Positioned<Field>) -> Context<'a> {
+        ContextBase {
+            path_node: self.path_node.clone(),
+            item: field,
+            resolve_id: self.resolve_id,
+            inc_resolve_id: self.inc_resolve_id,
+            schema_env: self.schema_env,
+            query_env: self.query_env,
+        }
+    }
 }
 
 impl<'a> ContextBase<'a, &'a Positioned<Field>> {
@@ -457,17 +561,50 @@ impl<'a> ContextBase<'a, &'a Positioned<Field>> {
         name: &str,
         default: Option<fn() -> T>,
     ) -> ServerResult<T> {
+        self.param_value_with_pos(name, default)
+            .map(|(_, value)| value)
+    }
+
+    #[doc(hidden)]
+    pub async fn param_value_async<T, V>(
+        &self,
+        name: &str,
+        default: Option<fn() -> T>,
+        validator: &V,
+    ) -> ServerResult<T>
+    where
+        T: InputValueType + Send + Sync,
+        V: AsyncInputValueValidator<T>,
+    {
+        let (pos, value) = self.param_value_with_pos(name, default)?;
+        validator.is_valid(self, &value).await.map_err(|reason| {
+            ServerError::new(format!(
+                "Invalid value for argument \"{}\", {}",
+                name, reason
+            ))
+            .at(pos)
+        })?;
+        Ok(value)
+    }
+
+    fn param_value_with_pos<T: InputValueType>(
+        &self,
+        name: &str,
+        default: Option<fn() -> T>,
+    ) -> ServerResult<(Pos, T)> {
         let value = self.item.node.get_argument(name).cloned();
         if value.is_none() {
             if let Some(default) = default {
-                return Ok(default());
+                return Ok((Pos::default(), default()));
             }
         }
         let (pos, value) = match value {
             Some(value) => (value.pos, Some(self.resolve_input_value(value)?)),
             None => (Pos::default(), None),
         };
-        InputValueType::parse(value).map_err(|e| e.into_server_error().at(pos))
+        InputValueType::parse(value)
+            .map(|value| (pos, value))
+            .map_err(|e| e.into_server_error().at(pos))
     }
 
     /// Creates a uniform interface to inspect the forthcoming selections.
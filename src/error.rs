@@ -1,5 +1,6 @@
 use crate::{parser, InputValueType, Pos, Value};
 use serde::Serialize;
+use std::collections::HashSet;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::marker::PhantomData;
 use thiserror::Error;
@@ -147,6 +148,11 @@ impl<T: InputValueType, E: Display> From<E> for InputValueError<T> {
 pub type InputValueResult<T> = Result<T, InputValueError<T>>;
 
 /// An error with a message and optional extensions.
+///
+/// Errors created with `Error::new` are considered user-facing, while errors converted with `From`
+/// (for example with the `?` operator on a `ParseIntError`, or `"message".into()`) are considered
+/// internal and can be masked with
+/// [`SchemaBuilder::mask_errors`](struct.SchemaBuilder.html#method.mask_errors).
 #[derive(Debug, Clone, Serialize)]
 pub struct Error {
     /// The error message.
@@ -154,6 +160,8 @@ pub struct Error {
     /// Extensions to the error.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extensions: Option<serde_json::Map<String, serde_json::Value>>,
+    #[serde(skip)]
+    pub(crate) internal: bool,
 }
 
 impl Error {
@@ -162,9 +170,15 @@ impl Error {
         Self {
             message: message.into(),
             extensions: None,
+            internal: false,
         }
     }
 
+    /// Returns `true` if the error was converted with `From` rather than created with `Error::new`.
+    pub fn is_internal(&self) -> bool {
+        self.internal
+    }
+
     /// Convert the error to a server error.
     #[must_use]
     pub fn into_server_error(self) -> ServerError {
@@ -182,10 +196,175 @@ impl<T: Display> From<T> for Error {
         Self {
             message: e.to_string(),
             extensions: None,
+            internal: true,
         }
     }
 }
 
+type AllowErrorFn = Box<dyn Fn(&Error) -> bool + Send + Sync>;
+
+/// A policy for masking the messages of internal errors.
+///
+/// The message of a masked error is replaced with a generic text, and a generated error id is
+/// added to its extensions as `errorId`. The original error, with the same error id, is still
+/// passed to `Extension::error` so that it can be logged.
+///
+/// Only internal errors are masked, see [`Error::is_internal`](struct.Error.html#method.is_internal).
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> Result<i32> {
+///         Ok("abc".parse::<i32>()?)
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///         .mask_errors(ErrorMaskPolicy::new().message("Something went wrong"))
+///         .finish();
+///     let errors = schema.execute("{ value }").await.errors;
+///     assert_eq!(errors[0].message, "Something went wrong");
+///     assert!(errors[0].extensions.as_ref().unwrap().contains_key("errorId"));
+/// });
+/// ```
+pub struct ErrorMaskPolicy {
+    message: String,
+    allow: Option<AllowErrorFn>,
+    error_id: Box<dyn Fn() -> String + Send + Sync>,
+}
+
+impl Default for ErrorMaskPolicy {
+    fn default() -> Self {
+        Self {
+            message: "Internal server error".to_string(),
+            allow: None,
+            error_id: Box::new(default_error_id),
+        }
+    }
+}
+
+impl ErrorMaskPolicy {
+    /// Create a policy that masks all internal errors with the message `Internal server error`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the message of the masked errors.
+    pub fn message(self, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            ..self
+        }
+    }
+
+    /// Set a whitelist, internal errors for which `f` returns `true` are not masked.
+    pub fn allow(self, f: impl Fn(&Error) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            allow: Some(Box::new(f)),
+            ..self
+        }
+    }
+
+    /// Set the function used to generate error ids.
+    pub fn error_id(self, f: impl Fn() -> String + Send + Sync + 'static) -> Self {
+        Self {
+            error_id: Box::new(f),
+            ..self
+        }
+    }
+
+    /// Returns a new error id if the error should be masked.
+    pub(crate) fn masked_error_id(&self, err: &Error) -> Option<String> {
+        if !err.internal {
+            return None;
+        }
+        if let Some(allow) = &self.allow {
+            if allow(err) {
+                return None;
+            }
+        }
+        Some((self.error_id)())
+    }
+
+    /// Returns the masked version of an error, which keeps its position and error id.
+    pub(crate) fn mask(&self, err: &ServerError) -> ServerError {
+        ServerError {
+            message: self.message.clone(),
+            locations: err.locations.clone(),
+            path: err.path.clone(),
+            extensions: err.extensions.as_ref().map(|extensions| {
+                extensions
+                    .iter()
+                    .filter(|(key, _)| *key == "errorId")
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect()
+            }),
+        }
+    }
+}
+
+/// The masked errors of a query, which are the errors that were given an error id by the
+/// `ErrorMaskPolicy` of the schema.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct ErrorMask<'a> {
+    pub(crate) policy: &'a ErrorMaskPolicy,
+    pub(crate) error_ids: &'a spin::Mutex<HashSet<String>>,
+}
+
+impl<'a> ErrorMask<'a> {
+    /// Returns the masked version of the error if it is masked.
+    pub(crate) fn mask(&self, err: &ServerError) -> Option<ServerError> {
+        if self.error_ids.lock().contains(error_id(err)?) {
+            Some(self.policy.mask(err))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the masked version of the error if it is masked, and forgets its error id.
+    ///
+    /// This is called once the error is in a response, so that the error ids of a long-lived
+    /// subscription don't pile up.
+    pub(crate) fn take(&self, err: &ServerError) -> Option<ServerError> {
+        if self.error_ids.lock().remove(error_id(err)?) {
+            Some(self.policy.mask(err))
+        } else {
+            None
+        }
+    }
+}
+
+fn error_id(err: &ServerError) -> Option<&str> {
+    err.extensions
+        .as_ref()
+        .and_then(|extensions| extensions.get("errorId"))
+        .and_then(|id| id.as_str())
+}
+
+fn default_error_id() -> String {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_micros() as u64)
+        .unwrap_or_default();
+    format!(
+        "{:012x}-{:06x}",
+        time,
+        COUNTER.fetch_add(1, Ordering::Relaxed) & 0xff_ffff
+    )
+}
+
 /// An alias for `Result<T, Error>`.
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -282,6 +461,10 @@ pub enum ParseRequestError {
     /// The request is a batch request, but the server does not support batch requests.
     #[error("Batch requests are not supported")]
     UnsupportedBatch,
+
+    /// The request is a mutation, which cannot be sent with the `GET` method.
+    #[error("Mutations are not allowed in GET requests")]
+    MutationNotAllowed,
 }
 
 #[cfg(feature = "multipart")]
@@ -291,6 +474,14 @@ impl From<multer::Error> for ParseRequestError {
             multer::Error::FieldSizeExceeded { .. } | multer::Error::StreamSizeExceeded { .. } => {
                 ParseRequestError::PayloadTooLarge
             }
+            multer::Error::StreamReadFailed(ref inner)
+                if matches!(
+                    inner.downcast_ref::<multer::Error>(),
+                    Some(multer::Error::StreamSizeExceeded { .. })
+                ) =>
+            {
+                ParseRequestError::PayloadTooLarge
+            }
             _ => ParseRequestError::InvalidMultipart(err),
         }
     }
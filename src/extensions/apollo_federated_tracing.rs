@@ -0,0 +1,411 @@
+use crate::extensions::{Extension, ExtensionContext, ExtensionFactory, ResolveInfo};
+use crate::{PathSegment, Pos, QueryPathSegment, ServerError, Variables};
+use std::collections::BTreeMap;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Apollo federated tracing extension
+///
+/// Builds a federated trace (`ftv1`) of the query and returns it, base64 encoded, in the
+/// `ftv1` field of the response extensions. This is the format that the Apollo gateway expects
+/// from subgraphs when it sends the `apollo-federation-include-trace: ftv1` header.
+///
+/// A trace is only generated for requests that contain the
+/// [`IncludeFederatedTrace`](struct.IncludeFederatedTrace.html) data, so an integration should
+/// insert it when the gateway asks for one.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::{ApolloFederatedTracing, IncludeFederatedTrace};
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///         .extension(ApolloFederatedTracing)
+///         .finish();
+///
+///     let resp = schema.execute(Request::new("{ value }").data(IncludeFederatedTrace)).await;
+///     assert!(resp.extensions.unwrap()["ftv1"].is_string());
+/// });
+/// ```
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "apollo_federated_tracing")))]
+pub struct ApolloFederatedTracing;
+
+impl ApolloFederatedTracing {
+    /// The HTTP header the Apollo gateway uses to ask a subgraph for a trace.
+    pub const HEADER: &'static str = "apollo-federation-include-trace";
+}
+
+/// Request data that enables the [`ApolloFederatedTracing`](struct.ApolloFederatedTracing.html)
+/// extension for a request.
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "apollo_federated_tracing")))]
+pub struct IncludeFederatedTrace;
+
+impl ExtensionFactory for ApolloFederatedTracing {
+    fn create(&self) -> Box<dyn Extension> {
+        Box::new(ApolloFederatedTracingExtension {
+            enabled: false,
+            start_time: SystemTime::now(),
+            end_time: SystemTime::now(),
+            start_instant: Instant::now(),
+            pending_resolves: Default::default(),
+            errors: Default::default(),
+            root: Default::default(),
+        })
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum PathKey {
+    Name(String),
+    Index(usize),
+}
+
+struct PendingResolve {
+    path: Vec<PathKey>,
+    parent_type: String,
+    return_type: String,
+    start_time: u64,
+}
+
+#[derive(Default)]
+struct TraceNode {
+    key: Option<PathKey>,
+    ty: String,
+    parent_type: String,
+    start_time: u64,
+    end_time: u64,
+    errors: Vec<TraceError>,
+    children: Vec<TraceNode>,
+}
+
+impl TraceNode {
+    fn node_at(&mut self, path: &[PathKey]) -> &mut TraceNode {
+        match path.split_first() {
+            Some((key, rest)) => {
+                let idx = match self
+                    .children
+                    .iter()
+                    .position(|child| child.key.as_ref() == Some(key))
+                {
+                    Some(idx) => idx,
+                    None => {
+                        self.children.push(TraceNode {
+                            key: Some(key.clone()),
+                            ..TraceNode::default()
+                        });
+                        self.children.len() - 1
+                    }
+                };
+                self.children[idx].node_at(rest)
+            }
+            None => self,
+        }
+    }
+
+    fn encode(&self, w: &mut ProtoWriter) {
+        match &self.key {
+            Some(PathKey::Name(name)) => w.string(1, name),
+            Some(PathKey::Index(idx)) => {
+                w.key(2, WIRE_VARINT);
+                w.varint(*idx as u64);
+            }
+            None => {}
+        }
+        w.string(3, &self.ty);
+        w.uint64(8, self.start_time);
+        w.uint64(9, self.end_time);
+        for error in &self.errors {
+            w.message(11, |w| error.encode(w));
+        }
+        for child in &self.children {
+            w.message(12, |w| child.encode(w));
+        }
+        w.string(13, &self.parent_type);
+    }
+}
+
+struct TraceError {
+    error: ServerError,
+    time: u64,
+}
+
+impl TraceError {
+    fn encode(&self, w: &mut ProtoWriter) {
+        w.string(1, &self.error.message);
+        for Pos { line, column } in &self.error.locations {
+            w.message(2, |w| {
+                w.uint64(1, *line as u64);
+                w.uint64(2, *column as u64);
+            });
+        }
+        w.uint64(3, self.time);
+        w.string(4, &serde_json::to_string(&self.error).unwrap_or_default());
+    }
+}
+
+struct ApolloFederatedTracingExtension {
+    enabled: bool,
+    start_time: SystemTime,
+    end_time: SystemTime,
+    start_instant: Instant,
+    pending_resolves: BTreeMap<usize, PendingResolve>,
+    errors: Vec<TraceError>,
+    root: TraceNode,
+}
+
+impl ApolloFederatedTracingExtension {
+    fn elapsed(&self) -> u64 {
+        self.start_instant.elapsed().as_nanos() as u64
+    }
+
+    fn finish_resolve(&mut self, pending_resolve: PendingResolve, end_time: u64) {
+        let node = self.root.node_at(&pending_resolve.path);
+        if let Some(PathKey::Name(_)) = &node.key {
+            node.ty = pending_resolve.return_type;
+            node.parent_type = pending_resolve.parent_type;
+            node.start_time = pending_resolve.start_time;
+            node.end_time = end_time;
+        }
+    }
+}
+
+impl Extension for ApolloFederatedTracingExtension {
+    fn name(&self) -> Option<&'static str> {
+        Some("ftv1")
+    }
+
+    fn parse_start(
+        &mut self,
+        ctx: &ExtensionContext<'_>,
+        _query_source: &str,
+        _variables: &Variables,
+    ) {
+        self.enabled = ctx.data_opt::<IncludeFederatedTrace>().is_some();
+        self.start_time = SystemTime::now();
+        self.start_instant = Instant::now();
+    }
+
+    fn execution_end(&mut self, _ctx: &ExtensionContext<'_>) {
+        self.end_time = SystemTime::now();
+    }
+
+    fn resolve_start(&mut self, _ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
+        if !self.enabled {
+            return;
+        }
+
+        let mut path = Vec::new();
+        info.path_node.for_each(|segment| {
+            path.push(match segment {
+                QueryPathSegment::Index(idx) => PathKey::Index(*idx),
+                QueryPathSegment::Name(name) => PathKey::Name(name.to_string()),
+            })
+        });
+        let start_time = self.elapsed();
+        self.pending_resolves.insert(
+            info.resolve_id.current,
+            PendingResolve {
+                path,
+                parent_type: info.parent_type.to_string(),
+                return_type: info.return_type.to_string(),
+                start_time,
+            },
+        );
+    }
+
+    fn resolve_end(&mut self, _ctx: &ExtensionContext<'_>, info: &ResolveInfo<'_>) {
+        if let Some(pending_resolve) = self.pending_resolves.remove(&info.resolve_id.current) {
+            let end_time = self.elapsed();
+            self.finish_resolve(pending_resolve, end_time);
+        }
+    }
+
+    fn error(&mut self, _ctx: &ExtensionContext<'_>, err: &ServerError) {
+        if !self.enabled {
+            return;
+        }
+
+        // An error is reported again by every resolver it propagates through, each time with a
+        // longer path, so only the most complete one is kept.
+        let time = self.elapsed();
+        let existing = self.errors.iter_mut().find(|existing| {
+            existing.error.message == err.message
+                && existing.error.locations == err.locations
+                && err.path.ends_with(&existing.error.path)
+        });
+        match existing {
+            Some(existing) => existing.error = err.clone(),
+            None => self.errors.push(TraceError {
+                error: err.clone(),
+                time,
+            }),
+        }
+    }
+
+    fn result(&mut self, ctx: &ExtensionContext<'_>) -> Option<serde_json::Value> {
+        if !self.enabled {
+            return None;
+        }
+
+        // Resolvers that failed never call `resolve_end`.
+        let end_time = self.elapsed();
+        for (_, pending_resolve) in std::mem::take(&mut self.pending_resolves) {
+            self.finish_resolve(pending_resolve, end_time);
+        }
+        for mut error in std::mem::take(&mut self.errors) {
+            // The trace is sent to the client too, so it must not expose the masked errors.
+            error.error = ctx.masked_error(&error.error);
+            let path = error
+                .error
+                .path
+                .iter()
+                .map(|segment| match segment {
+                    PathSegment::Field(name) => PathKey::Name(name.clone()),
+                    PathSegment::Index(idx) => PathKey::Index(*idx),
+                })
+                .collect::<Vec<_>>();
+            self.root.node_at(&path).errors.push(error);
+        }
+
+        let mut w = ProtoWriter::default();
+        w.message(3, |w| encode_timestamp(w, self.end_time));
+        w.message(4, |w| encode_timestamp(w, self.start_time));
+        w.uint64(
+            11,
+            self.end_time
+                .duration_since(self.start_time)
+                .map(|duration| duration.as_nanos() as u64)
+                .unwrap_or_default(),
+        );
+        w.message(14, |w| self.root.encode(w));
+        Some(base64::encode(&w.0).into())
+    }
+}
+
+fn encode_timestamp(w: &mut ProtoWriter, time: SystemTime) {
+    let duration = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    w.uint64(1, duration.as_secs());
+    w.uint64(2, duration.subsec_nanos() as u64);
+}
+
+const WIRE_VARINT: u8 = 0;
+const WIRE_LENGTH_DELIMITED: u8 = 2;
+
+/// A minimal protobuf encoder for the `Trace` message of Apollo's `reports.proto`.
+#[derive(Default)]
+struct ProtoWriter(Vec<u8>);
+
+impl ProtoWriter {
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    fn key(&mut self, field: u32, wire_type: u8) {
+        self.varint(((field << 3) | wire_type as u32) as u64);
+    }
+
+    fn uint64(&mut self, field: u32, value: u64) {
+        if value != 0 {
+            self.key(field, WIRE_VARINT);
+            self.varint(value);
+        }
+    }
+
+    fn string(&mut self, field: u32, value: &str) {
+        if !value.is_empty() {
+            self.key(field, WIRE_LENGTH_DELIMITED);
+            self.varint(value.len() as u64);
+            self.0.extend_from_slice(value.as_bytes());
+        }
+    }
+
+    fn message(&mut self, field: u32, f: impl FnOnce(&mut ProtoWriter)) {
+        let mut inner = ProtoWriter::default();
+        f(&mut inner);
+        self.key(field, WIRE_LENGTH_DELIMITED);
+        self.varint(inner.0.len() as u64);
+        self.0.extend(inner.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    struct Query;
+
+    #[Object(internal)]
+    impl Query {
+        async fn values(&self) -> Vec<i32> {
+            vec![1, 2]
+        }
+
+        async fn fail(&self) -> Result<i32> {
+            Err("oops".into())
+        }
+    }
+
+    #[async_std::test]
+    async fn test_disabled_by_default() {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(ApolloFederatedTracing)
+            .finish();
+        let resp = schema.execute("{ values }").await;
+        assert!(resp.extensions.is_none());
+    }
+
+    #[async_std::test]
+    async fn test_trace() {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(ApolloFederatedTracing)
+            .finish();
+
+        let resp = schema
+            .execute(Request::new("{ values }").data(IncludeFederatedTrace))
+            .await;
+        let trace = base64::decode(resp.extensions.unwrap()["ftv1"].as_str().unwrap()).unwrap();
+        let contains = |needle: &[u8]| trace.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(b"\x0a\x06values"));
+        assert!(contains(b"\x1a\x07[Int!]!"));
+        assert!(contains(b"\x6a\x05Query"));
+
+        let resp = schema
+            .execute(Request::new("{ fail }").data(IncludeFederatedTrace))
+            .await;
+        let trace = base64::decode(resp.extensions.unwrap()["ftv1"].as_str().unwrap()).unwrap();
+        let contains = |needle: &[u8]| trace.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(b"\x0a\x04fail"));
+        assert!(contains(b"\x0a\x04oops"));
+    }
+
+    #[async_std::test]
+    async fn test_masked_errors() {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(ApolloFederatedTracing)
+            .mask_errors(ErrorMaskPolicy::new())
+            .finish();
+
+        let resp = schema
+            .execute(Request::new("{ fail }").data(IncludeFederatedTrace))
+            .await;
+        let trace = base64::decode(resp.extensions.unwrap()["ftv1"].as_str().unwrap()).unwrap();
+        let contains = |needle: &[u8]| trace.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(b"\x0a\x15Internal server error"));
+        assert!(!contains(b"oops"));
+    }
+}
@@ -1,5 +1,7 @@
 //! Extensions for schema
 
+#[cfg(feature = "apollo_federated_tracing")]
+mod apollo_federated_tracing;
 #[cfg(feature = "apollo_persisted_queries")]
 pub mod apollo_persisted_queries;
 #[cfg(feature = "apollo_tracing")]
@@ -10,8 +12,11 @@ mod logger;
 mod tracing;
 
 use crate::context::{QueryPathNode, ResolveId};
+use crate::error::ErrorMask;
 use crate::{Data, Request, Result, ServerError, ServerResult, Variables};
 
+#[cfg(feature = "apollo_federated_tracing")]
+pub use self::apollo_federated_tracing::{ApolloFederatedTracing, IncludeFederatedTrace};
 #[cfg(feature = "apollo_tracing")]
 pub use self::apollo_tracing::ApolloTracing;
 #[cfg(feature = "log")]
@@ -35,6 +40,9 @@ pub struct ExtensionContext<'a> {
 
     #[doc(hidden)]
     pub query_data: &'a Data,
+
+    #[doc(hidden)]
+    pub error_mask: Option<ErrorMask<'a>>,
 }
 
 impl<'a> ExtensionContext<'a> {
@@ -71,6 +79,16 @@ impl<'a> ExtensionContext<'a> {
             .or_else(|| self.schema_data.get(&TypeId::of::<D>()))
             .and_then(|d| d.downcast_ref::<D>())
     }
+
+    /// Returns the error as it is sent to the client.
+    ///
+    /// `Extension::error` gets the errors before they are masked by the error mask policy of the
+    /// schema, so an extension that outputs errors must use this to not expose the internal ones.
+    pub fn masked_error(&self, err: &ServerError) -> ServerError {
+        self.error_mask
+            .and_then(|error_mask| error_mask.mask(err))
+            .unwrap_or_else(|| err.clone())
+    }
 }
 
 /// Parameters for `Extension::resolve_field_start`
@@ -1,6 +1,6 @@
 //! Field guards
 
-use crate::{Context, Result};
+use crate::{Context, Error, Result};
 use serde::export::PhantomData;
 
 /// Field guard
@@ -20,6 +20,16 @@ pub trait GuardExt: Guard + Sized {
     fn and<R: Guard>(self, other: R) -> And<Self, R> {
         And(self, other)
     }
+
+    /// Allow access if either of the two guards allows it.
+    fn or<R: Guard>(self, other: R) -> Or<Self, R> {
+        Or(self, other)
+    }
+
+    /// Allow access only if this guard denies it.
+    fn not(self) -> Not<Self> {
+        Not(self)
+    }
 }
 
 impl<T: Guard> GuardExt for T {}
@@ -35,6 +45,76 @@ impl<A: Guard + Send + Sync, B: Guard + Send + Sync> Guard for And<A, B> {
     }
 }
 
+/// Guard for [`GuardExt::or`](trait.GuardExt.html#method.or).
+pub struct Or<A: Guard, B: Guard>(A, B);
+
+#[async_trait::async_trait]
+impl<A: Guard + Send + Sync, B: Guard + Send + Sync> Guard for Or<A, B> {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        match self.0.check(ctx).await {
+            Ok(()) => Ok(()),
+            Err(err) => self.1.check(ctx).await.map_err(|_| err),
+        }
+    }
+}
+
+/// Guard for [`GuardExt::not`](trait.GuardExt.html#method.not).
+pub struct Not<A: Guard>(A);
+
+#[async_trait::async_trait]
+impl<A: Guard + Send + Sync> Guard for Not<A> {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        match self.0.check(ctx).await {
+            Ok(()) => Err(Error::new("Forbidden")),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+/// Allow access if any of the guards allows it.
+///
+/// If all of them deny access, the error of the first one is returned.
+pub fn any_of(guards: Vec<Box<dyn Guard + Send + Sync>>) -> AnyOf {
+    AnyOf(guards)
+}
+
+/// Allow access only if all of the guards allow it.
+pub fn all_of(guards: Vec<Box<dyn Guard + Send + Sync>>) -> AllOf {
+    AllOf(guards)
+}
+
+/// Guard for [`any_of`](fn.any_of.html).
+pub struct AnyOf(Vec<Box<dyn Guard + Send + Sync>>);
+
+#[async_trait::async_trait]
+impl Guard for AnyOf {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        let mut first_err = None;
+        for guard in &self.0 {
+            match guard.check(ctx).await {
+                Ok(()) => return Ok(()),
+                Err(err) => {
+                    first_err.get_or_insert(err);
+                }
+            }
+        }
+        Err(first_err.unwrap_or_else(|| Error::new("Forbidden")))
+    }
+}
+
+/// Guard for [`all_of`](fn.all_of.html).
+pub struct AllOf(Vec<Box<dyn Guard + Send + Sync>>);
+
+#[async_trait::async_trait]
+impl Guard for AllOf {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        for guard in &self.0 {
+            guard.check(ctx).await?;
+        }
+        Ok(())
+    }
+}
+
 /// Field post guard
 ///
 /// This is a post-condition for a field that is resolved if `Ok(()` is returned, otherwise an error is returned.
@@ -52,6 +132,16 @@ pub trait PostGuardExt<T: Send + Sync>: PostGuard<T> + Sized {
     fn and<R: PostGuard<T>>(self, other: R) -> PostAnd<T, Self, R> {
         PostAnd(self, other, PhantomData)
     }
+
+    /// Allow the result through if either of the two guards allows it.
+    fn or<R: PostGuard<T>>(self, other: R) -> PostOr<T, Self, R> {
+        PostOr(self, other, PhantomData)
+    }
+
+    /// Allow the result through only if this guard denies it.
+    fn not(self) -> PostNot<T, Self> {
+        PostNot(self, PhantomData)
+    }
 }
 
 impl<T: PostGuard<R>, R: Send + Sync> PostGuardExt<R> for T {}
@@ -68,3 +158,79 @@ impl<T: Send + Sync, A: PostGuard<T> + Send + Sync, B: PostGuard<T> + Send + Syn
         self.1.check(ctx, result).await
     }
 }
+
+/// PostGuard for [`PostGuardExt<T>::or`](trait.PostGuardExt.html#method.or).
+pub struct PostOr<T: Send + Sync, A: PostGuard<T>, B: PostGuard<T>>(A, B, PhantomData<T>);
+
+#[async_trait::async_trait]
+impl<T: Send + Sync, A: PostGuard<T> + Send + Sync, B: PostGuard<T> + Send + Sync> PostGuard<T>
+    for PostOr<T, A, B>
+{
+    async fn check(&self, ctx: &Context<'_>, result: &T) -> Result<()> {
+        match self.0.check(ctx, result).await {
+            Ok(()) => Ok(()),
+            Err(err) => self.1.check(ctx, result).await.map_err(|_| err),
+        }
+    }
+}
+
+/// PostGuard for [`PostGuardExt<T>::not`](trait.PostGuardExt.html#method.not).
+pub struct PostNot<T: Send + Sync, A: PostGuard<T>>(A, PhantomData<T>);
+
+#[async_trait::async_trait]
+impl<T: Send + Sync, A: PostGuard<T> + Send + Sync> PostGuard<T> for PostNot<T, A> {
+    async fn check(&self, ctx: &Context<'_>, result: &T) -> Result<()> {
+        match self.0.check(ctx, result).await {
+            Ok(()) => Err(Error::new("Forbidden")),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+/// Allow the result through if any of the post guards allows it.
+///
+/// If all of them deny it, the error of the first one is returned.
+pub fn post_any_of<T: Send + Sync>(
+    guards: Vec<Box<dyn PostGuard<T> + Send + Sync>>,
+) -> PostAnyOf<T> {
+    PostAnyOf(guards)
+}
+
+/// Allow the result through only if all of the post guards allow it.
+pub fn post_all_of<T: Send + Sync>(
+    guards: Vec<Box<dyn PostGuard<T> + Send + Sync>>,
+) -> PostAllOf<T> {
+    PostAllOf(guards)
+}
+
+/// PostGuard for [`post_any_of`](fn.post_any_of.html).
+pub struct PostAnyOf<T: Send + Sync>(Vec<Box<dyn PostGuard<T> + Send + Sync>>);
+
+#[async_trait::async_trait]
+impl<T: Send + Sync> PostGuard<T> for PostAnyOf<T> {
+    async fn check(&self, ctx: &Context<'_>, result: &T) -> Result<()> {
+        let mut first_err = None;
+        for guard in &self.0 {
+            match guard.check(ctx, result).await {
+                Ok(()) => return Ok(()),
+                Err(err) => {
+                    first_err.get_or_insert(err);
+                }
+            }
+        }
+        Err(first_err.unwrap_or_else(|| Error::new("Forbidden")))
+    }
+}
+
+/// PostGuard for [`post_all_of`](fn.post_all_of.html).
+pub struct PostAllOf<T: Send + Sync>(Vec<Box<dyn PostGuard<T> + Send + Sync>>);
+
+#[async_trait::async_trait]
+impl<T: Send + Sync> PostGuard<T> for PostAllOf<T> {
+    async fn check(&self, ctx: &Context<'_>, result: &T) -> Result<()> {
+        for guard in &self.0 {
+            guard.check(ctx, result).await?;
+        }
+        Ok(())
+    }
+}
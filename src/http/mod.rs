@@ -4,13 +4,22 @@ mod graphiql_source;
 #[cfg(feature = "multipart")]
 mod multipart;
 mod playground_source;
+mod query_string;
+mod response;
 mod websocket;
 
 pub use graphiql_source::graphiql_source;
 #[cfg(feature = "multipart")]
-pub use multipart::MultipartOptions;
+pub use multipart::{MemoryStorage, MultipartOptions, TempFileStorage, UploadStorage};
 pub use playground_source::{playground_source, GraphQLPlaygroundConfig};
-pub use websocket::WebSocket;
+pub use query_string::parse_query_string;
+pub use response::{
+    response_content_type, ResponseHead, GRAPHQL_RESPONSE_CONTENT_TYPE, JSON_CONTENT_TYPE,
+};
+pub use websocket::{
+    WebSocket, WebSocketHooks, WebSocketOptions, WebSocketProtocols, WsMessage,
+    ALL_WEBSOCKET_PROTOCOLS,
+};
 
 use crate::{BatchRequest, ParseRequestError, Request};
 use futures::io::AsyncRead;
@@ -32,18 +41,47 @@ pub async fn receive_body(
 #[cfg(feature = "multipart")]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
 /// Receive a GraphQL request from a content type and body.
+///
+/// The body is a multipart request, a GraphQL query with the `application/graphql` content type,
+/// or JSON otherwise.
 pub async fn receive_batch_body(
     content_type: Option<impl AsRef<str>>,
     body: impl AsyncRead + Send + 'static,
     opts: MultipartOptions,
 ) -> Result<BatchRequest, ParseRequestError> {
+    let content_type = content_type.as_ref().map(AsRef::as_ref);
     if let Some(Ok(boundary)) = content_type.map(multer::parse_boundary) {
         multipart::receive_batch_multipart(body, boundary, opts).await
+    } else if matches!(content_type, Some(content_type) if is_graphql_content_type(content_type)) {
+        receive_graphql(body).await.map(Into::into)
     } else {
         receive_batch_json(body).await
     }
 }
 
+#[cfg(feature = "multipart")]
+fn is_graphql_content_type(content_type: &str) -> bool {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .eq_ignore_ascii_case("application/graphql")
+}
+
+/// Receive a GraphQL request from a body that is a GraphQL query, which is sent with the
+/// `application/graphql` content type.
+pub async fn receive_graphql(
+    body: impl AsyncRead + Send + 'static,
+) -> Result<Request, ParseRequestError> {
+    let mut query = String::new();
+    futures::pin_mut!(body);
+    body.read_to_string(&mut query)
+        .await
+        .map_err(ParseRequestError::Io)?;
+    Ok(Request::new(query))
+}
+
 /// Receive a GraphQL request from a body as JSON.
 pub async fn receive_json(
     body: impl AsyncRead + Send + 'static,
@@ -1,16 +1,82 @@
-use crate::{BatchRequest, ParseRequestError};
-use bytes::Bytes;
+use crate::{BatchRequest, ParseRequestError, Request, UploadContent, UploadStream};
+use bytes::{Bytes, BytesMut};
 use futures::io::AsyncRead;
-use futures::stream::Stream;
-use multer::{Constraints, Multipart, SizeLimit};
+use futures::lock::Mutex;
+use futures::stream::{BoxStream, Stream, StreamExt, TryStreamExt};
+use futures::FutureExt;
+use multer::{Constraints, Field, Multipart, SizeLimit};
 use pin_project_lite::pin_project;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::{self, Seek, SeekFrom, Write};
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
+/// Storage for the files of a multipart request
+///
+/// The default storage is `TempFileStorage`. Implement this trait to store the files elsewhere,
+/// for example to send them straight to an object storage and return the key of the object with
+/// `UploadContent::custom`.
+///
+/// The files that are not stored in files are read with the methods of `Upload` that take the
+/// context.
+#[async_trait::async_trait]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
+pub trait UploadStorage: Send + Sync + 'static {
+    /// Store the content of an uploaded file.
+    async fn store(
+        &self,
+        filename: &str,
+        content_type: Option<&str>,
+        content: BoxStream<'static, io::Result<Bytes>>,
+    ) -> io::Result<UploadContent>;
+}
+
+/// Stores the uploaded files in temporary files.
+#[derive(Default)]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
+pub struct TempFileStorage;
+
+#[async_trait::async_trait]
+impl UploadStorage for TempFileStorage {
+    async fn store(
+        &self,
+        _filename: &str,
+        _content_type: Option<&str>,
+        mut content: BoxStream<'static, io::Result<Bytes>>,
+    ) -> io::Result<UploadContent> {
+        let mut file = tempfile::tempfile()?;
+        while let Some(chunk) = content.try_next().await? {
+            file.write_all(&chunk)?;
+        }
+        file.seek(SeekFrom::Start(0))?;
+        Ok(UploadContent::file(file))
+    }
+}
+
+/// Stores the uploaded files in memory.
+#[derive(Default)]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
+pub struct MemoryStorage;
+
+#[async_trait::async_trait]
+impl UploadStorage for MemoryStorage {
+    async fn store(
+        &self,
+        _filename: &str,
+        _content_type: Option<&str>,
+        mut content: BoxStream<'static, io::Result<Bytes>>,
+    ) -> io::Result<UploadContent> {
+        let mut data = BytesMut::new();
+        while let Some(chunk) = content.try_next().await? {
+            data.extend_from_slice(&chunk);
+        }
+        Ok(UploadContent::memory(data.freeze()))
+    }
+}
+
 /// Options for `receive_multipart`.
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone)]
 #[non_exhaustive]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
 pub struct MultipartOptions {
@@ -18,6 +84,14 @@ pub struct MultipartOptions {
     pub max_file_size: Option<usize>,
     /// The maximum number of files.
     pub max_num_files: Option<usize>,
+    /// The maximum size of the whole request, the maximum file size times the maximum number of
+    /// files by default.
+    pub max_request_size: Option<usize>,
+    /// The storage of the files, `TempFileStorage` by default.
+    pub storage: Option<Arc<dyn UploadStorage>>,
+    /// Read the files from the request body only when they are opened with
+    /// `StreamedUpload::open`.
+    pub stream_uploads: bool,
 }
 
 impl MultipartOptions {
@@ -36,6 +110,43 @@ impl MultipartOptions {
             ..self
         }
     }
+
+    /// Set maximum size of the whole request.
+    pub fn max_request_size(self, size: usize) -> Self {
+        MultipartOptions {
+            max_request_size: Some(size),
+            ..self
+        }
+    }
+
+    /// Set the storage of the files.
+    pub fn storage(self, storage: impl UploadStorage) -> Self {
+        MultipartOptions {
+            storage: Some(Arc::new(storage)),
+            ..self
+        }
+    }
+
+    /// Read the files lazily from the request body, instead of storing them before the request is
+    /// executed.
+    ///
+    /// The files must then be received as `StreamedUpload` and opened with `StreamedUpload::open`,
+    /// and their filename is only known once they are opened. They are sent in the order of the
+    /// `map` part, so a file that is opened before the ones that precede it in the request body
+    /// makes those be stored with the storage first. A stream must be consumed or dropped before
+    /// the next file is read from the body.
+    pub fn stream_uploads(self, enable: bool) -> Self {
+        MultipartOptions {
+            stream_uploads: enable,
+            ..self
+        }
+    }
+}
+
+struct StoredFile {
+    filename: String,
+    content_type: Option<String>,
+    content: UploadContent,
 }
 
 pub(super) async fn receive_batch_multipart(
@@ -48,10 +159,16 @@ pub(super) async fn receive_batch_multipart(
         boundary,
         Constraints::new().size_limit({
             let mut limit = SizeLimit::new();
-            if let (Some(max_file_size), Some(max_num_files)) =
-                (opts.max_file_size, opts.max_file_size)
-            {
-                limit = limit.whole_stream((max_file_size * max_num_files) as u64);
+            let max_request_size =
+                opts.max_request_size
+                    .or_else(|| match (opts.max_file_size, opts.max_num_files) {
+                        (Some(max_file_size), Some(max_num_files)) => {
+                            max_file_size.checked_mul(max_num_files)
+                        }
+                        _ => None,
+                    });
+            if let Some(max_request_size) = max_request_size {
+                limit = limit.whole_stream(max_request_size as u64);
             }
             if let Some(max_file_size) = opts.max_file_size {
                 limit = limit.per_field(max_file_size as u64);
@@ -59,12 +176,21 @@ pub(super) async fn receive_batch_multipart(
             limit
         }),
     );
+    let storage = opts.storage.unwrap_or_else(|| Arc::new(TempFileStorage));
 
     let mut request = None;
     let mut map = None;
-    let mut files = Vec::new();
+    let mut files = HashMap::new();
 
-    while let Some(mut field) = multipart.next_field().await? {
+    loop {
+        if opts.stream_uploads && request.is_some() && map.is_some() {
+            break;
+        }
+
+        let field = match multipart.next_field().await? {
+            Some(field) => field,
+            None => break,
+        };
         match field.name() {
             Some("operations") => {
                 let request_str = field.text().await?;
@@ -83,13 +209,10 @@ pub(super) async fn receive_batch_multipart(
             _ => {
                 if let Some(name) = field.name().map(ToString::to_string) {
                     if let Some(filename) = field.file_name().map(ToString::to_string) {
-                        let content_type = field.content_type().map(|mime| mime.to_string());
-                        let mut file = tempfile::tempfile().map_err(ParseRequestError::Io)?;
-                        while let Some(chunk) = field.chunk().await.unwrap() {
-                            file.write(&chunk).map_err(ParseRequestError::Io)?;
-                        }
-                        file.seek(SeekFrom::Start(0))?;
-                        files.push((name, filename, content_type, file));
+                        let file = store_field(&*storage, filename, field)
+                            .await
+                            .map_err(from_io_error)?;
+                        files.insert(name, file);
                     }
                 }
             }
@@ -97,34 +220,48 @@ pub(super) async fn receive_batch_multipart(
     }
 
     let mut request: BatchRequest = request.ok_or(ParseRequestError::MissingOperatorsPart)?;
-    let map = map.as_mut().ok_or(ParseRequestError::MissingMapPart)?;
-
-    for (name, filename, content_type, file) in files {
-        if let Some(var_paths) = map.remove(&name) {
-            for var_path in var_paths {
-                match &mut request {
-                    BatchRequest::Single(request) => {
-                        request.set_upload(
-                            &var_path,
-                            filename.clone(),
-                            content_type.clone(),
-                            file.try_clone().unwrap(),
-                        );
-                    }
-                    BatchRequest::Batch(requests) => {
-                        let mut s = var_path.splitn(2, '.');
-                        let idx = s.next().and_then(|idx| idx.parse::<usize>().ok());
-                        let path = s.next();
-
-                        if let (Some(idx), Some(path)) = (idx, path) {
-                            if let Some(request) = requests.get_mut(idx) {
-                                request.set_upload(
-                                    path,
-                                    filename.clone(),
-                                    content_type.clone(),
-                                    file.try_clone().unwrap(),
-                                );
-                            }
+    let map = map.ok_or(ParseRequestError::MissingMapPart)?;
+
+    let lazy_uploads = if opts.stream_uploads {
+        Some(Arc::new(LazyUploads {
+            storage,
+            state: Mutex::new(LazyState {
+                multipart,
+                pending: map
+                    .keys()
+                    .filter(|name| !files.contains_key(*name))
+                    .cloned()
+                    .collect(),
+                stored: HashMap::new(),
+            }),
+        }))
+    } else {
+        None
+    };
+
+    for (name, var_paths) in map {
+        // The filename of a streamed file is only known once it is opened.
+        let (filename, content_type, content) = match (files.remove(&name), &lazy_uploads) {
+            (Some(file), _) => (Some(file.filename), file.content_type, file.content),
+            (None, Some(lazy_uploads)) => {
+                (None, None, LazyUploads::content(lazy_uploads.clone(), name))
+            }
+            (None, None) => return Err(ParseRequestError::MissingFiles),
+        };
+
+        for var_path in var_paths {
+            match &mut request {
+                BatchRequest::Single(request) => {
+                    set_upload(request, &var_path, &filename, &content_type, &content)?;
+                }
+                BatchRequest::Batch(requests) => {
+                    let mut s = var_path.splitn(2, '.');
+                    let idx = s.next().and_then(|idx| idx.parse::<usize>().ok());
+                    let path = s.next();
+
+                    if let (Some(idx), Some(path)) = (idx, path) {
+                        if let Some(request) = requests.get_mut(idx) {
+                            set_upload(request, path, &filename, &content_type, &content)?;
                         }
                     }
                 }
@@ -132,11 +269,133 @@ pub(super) async fn receive_batch_multipart(
         }
     }
 
-    if !map.is_empty() {
-        return Err(ParseRequestError::MissingFiles);
+    Ok(request)
+}
+
+/// Set a variable to an upload, the files that are stored in files are set as upload values.
+fn set_upload(
+    request: &mut Request,
+    var_path: &str,
+    filename: &Option<String>,
+    content_type: &Option<String>,
+    content: &UploadContent,
+) -> Result<(), ParseRequestError> {
+    match (filename, content.try_clone_file()) {
+        (Some(filename), Some(file)) => request.set_upload(
+            var_path,
+            filename.clone(),
+            content_type.clone(),
+            file.map_err(ParseRequestError::Io)?,
+        ),
+        _ => request.set_upload_content(
+            var_path,
+            filename.clone(),
+            content_type.clone(),
+            content.clone(),
+        ),
     }
+    Ok(())
+}
 
-    Ok(request)
+async fn store_field(
+    storage: &dyn UploadStorage,
+    filename: String,
+    field: Field,
+) -> io::Result<StoredFile> {
+    let content_type = field.content_type().map(|mime| mime.to_string());
+    let content = storage
+        .store(
+            &filename,
+            content_type.as_deref(),
+            field.map_err(into_io_error).boxed(),
+        )
+        .await?;
+    Ok(StoredFile {
+        filename,
+        content_type,
+        content,
+    })
+}
+
+/// The files of a multipart request that are read after the request is received.
+struct LazyUploads {
+    storage: Arc<dyn UploadStorage>,
+    state: Mutex<LazyState>,
+}
+
+struct LazyState {
+    multipart: Multipart,
+    /// The files that have not been reached in the request body.
+    pending: HashSet<String>,
+    /// The files that were reached before they were opened.
+    stored: HashMap<String, StoredFile>,
+}
+
+impl LazyUploads {
+    fn content(self: Arc<Self>, name: String) -> UploadContent {
+        UploadContent::lazy(move || {
+            let lazy_uploads = self.clone();
+            let name = name.clone();
+            async move { lazy_uploads.open(&name).await }.boxed()
+        })
+    }
+
+    async fn open(&self, name: &str) -> io::Result<UploadStream> {
+        let mut state = self.state.lock().await;
+
+        if let Some(file) = state.stored.remove(name) {
+            return file.content.open(file.filename, file.content_type).await;
+        }
+        if !state.pending.contains(name) {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "The upload has already been opened.",
+            ));
+        }
+
+        while let Some(field) = state.multipart.next_field().await.map_err(into_io_error)? {
+            let (field_name, filename) = match (field.name(), field.file_name()) {
+                (Some(field_name), Some(filename)) if state.pending.contains(field_name) => {
+                    (field_name.to_string(), filename.to_string())
+                }
+                _ => continue,
+            };
+            state.pending.remove(&field_name);
+
+            if field_name == name {
+                return Ok(UploadStream::new(
+                    filename,
+                    field.content_type().map(|mime| mime.to_string()),
+                    field.map_err(into_io_error).boxed(),
+                ));
+            }
+            let file = store_field(&*self.storage, filename, field).await?;
+            state.stored.insert(field_name, file);
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "The upload is missing from the request.",
+        ))
+    }
+}
+
+fn into_io_error(err: multer::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err)
+}
+
+fn from_io_error(err: io::Error) -> ParseRequestError {
+    if !matches!(err.get_ref(), Some(inner) if inner.is::<multer::Error>()) {
+        return ParseRequestError::Io(err);
+    }
+    let kind = err.kind();
+    match err
+        .into_inner()
+        .map(|inner| inner.downcast::<multer::Error>())
+    {
+        Some(Ok(err)) => (*err).into(),
+        _ => ParseRequestError::Io(kind.into()),
+    }
 }
 
 pin_project! {
@@ -0,0 +1,62 @@
+use crate::parser::parse_query;
+use crate::parser::types::{DocumentOperations, OperationType};
+use crate::{ParseRequestError, Request};
+
+/// Receive a GraphQL request from the query string of a `GET` request.
+///
+/// The query string has the `query` and `operationName` parameters, and the `variables` and
+/// `extensions` parameters encoded as JSON. Mutations are refused with
+/// `ParseRequestError::MutationNotAllowed`, since a `GET` request must not have side effects.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::http::parse_query_string;
+///
+/// let request = parse_query_string(
+///     "query=query(%24id%3A%20Int!)%20%7B%20user(id%3A%20%24id)%20%7B%20name%20%7D%20%7D&variables=%7B%22id%22%3A%201%7D",
+/// ).unwrap();
+/// assert_eq!(request.query, "query($id: Int!) { user(id: $id) { name } }");
+///
+/// assert!(parse_query_string("query=mutation%20%7B%20logout%20%7D").is_err());
+/// ```
+pub fn parse_query_string(input: &str) -> Result<Request, ParseRequestError> {
+    let mut request = serde_json::Map::new();
+    for (name, value) in form_urlencoded::parse(input.as_bytes()) {
+        let value = match name.as_ref() {
+            "query" | "operationName" => serde_json::Value::String(value.into_owned()),
+            "variables" | "extensions" if !value.is_empty() => {
+                serde_json::from_str(&value).map_err(ParseRequestError::InvalidRequest)?
+            }
+            _ => continue,
+        };
+        request.insert(name.into_owned(), value);
+    }
+    let request: Request = serde_json::from_value(serde_json::Value::Object(request))
+        .map_err(ParseRequestError::InvalidRequest)?;
+
+    if is_mutation(&request) {
+        return Err(ParseRequestError::MutationNotAllowed);
+    }
+    Ok(request)
+}
+
+/// Returns `true` if the operation of the request is a mutation. Invalid queries are left to the
+/// execution to report.
+fn is_mutation(request: &Request) -> bool {
+    let document = match parse_query(&request.query) {
+        Ok(document) => document,
+        Err(_) => return false,
+    };
+    let operation = match (&document.operations, &request.operation_name) {
+        (DocumentOperations::Single(operation), None) => Some(operation),
+        (DocumentOperations::Multiple(operations), Some(operation_name)) => {
+            operations.get(operation_name.as_str())
+        }
+        (DocumentOperations::Multiple(operations), None) if operations.len() == 1 => {
+            operations.values().next()
+        }
+        _ => None,
+    };
+    matches!(operation, Some(operation) if operation.node.ty == OperationType::Mutation)
+}
@@ -0,0 +1,120 @@
+use crate::{BatchResponse, ParseRequestError};
+
+/// The `application/json` content type.
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+
+/// The `application/graphql-response+json` content type of the GraphQL over HTTP specification.
+pub const GRAPHQL_RESPONSE_CONTENT_TYPE: &str = "application/graphql-response+json";
+
+/// The HTTP status code and content type of a GraphQL response
+///
+/// They follow the [GraphQL over HTTP specification](https://graphql.github.io/graphql-over-http/draft/):
+/// the content type is negotiated from the `Accept` header of the request, and with
+/// `application/graphql-response+json` a response that failed before its execution, for
+/// example because the query is invalid, has the status `400 Bad Request`. With
+/// `application/json`, such responses have the status `200 OK` for legacy clients.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::http::ResponseHead;
+///
+/// let response = BatchResponse::Single(Response::from_errors(vec![ServerError::new("Invalid query")]));
+/// assert_eq!(
+///     ResponseHead::for_response(Some("application/graphql-response+json"), &response),
+///     ResponseHead { status: 400, content_type: "application/graphql-response+json" },
+/// );
+/// assert_eq!(
+///     ResponseHead::for_response(None, &response),
+///     ResponseHead { status: 200, content_type: "application/json" },
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ResponseHead {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The value of the `Content-Type` header.
+    pub content_type: &'static str,
+}
+
+impl ResponseHead {
+    /// The status and content type of an executed response.
+    pub fn for_response(accept: Option<&str>, response: &BatchResponse) -> Self {
+        let content_type = response_content_type(accept);
+        let is_request_error = match response {
+            BatchResponse::Single(response) => {
+                response.data.is_null()
+                    && !response.errors.is_empty()
+                    && response.errors.iter().all(|err| err.path.is_empty())
+            }
+            BatchResponse::Batch(_) => false,
+        };
+        let status = if is_request_error && content_type == GRAPHQL_RESPONSE_CONTENT_TYPE {
+            400
+        } else {
+            200
+        };
+        Self {
+            status,
+            content_type,
+        }
+    }
+
+    /// The status and content type of a request that could not be received.
+    pub fn for_error(accept: Option<&str>, err: &ParseRequestError) -> Self {
+        let status = match err {
+            ParseRequestError::PayloadTooLarge => 413,
+            ParseRequestError::MutationNotAllowed => 405,
+            _ => 400,
+        };
+        Self {
+            status,
+            content_type: response_content_type(accept),
+        }
+    }
+}
+
+/// Returns the content type of the response for the `Accept` header of the request.
+///
+/// `application/graphql-response+json` is chosen if the client accepts it at least as much as
+/// `application/json`, otherwise `application/json` is used, which is also the content type for
+/// wildcards and clients that do not send the header.
+pub fn response_content_type(accept: Option<&str>) -> &'static str {
+    let mut graphql_response_quality = 0.0;
+    let mut json_quality = 0.0;
+
+    for media_range in accept.unwrap_or_default().split(',') {
+        let mut params = media_range.split(';');
+        let media_type = params
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let quality = params
+            .filter_map(|param| {
+                let mut param = param.splitn(2, '=');
+                match (param.next(), param.next()) {
+                    (Some(name), Some(value)) if name.trim() == "q" => value.trim().parse().ok(),
+                    _ => None,
+                }
+            })
+            .next()
+            .unwrap_or(1.0_f32);
+
+        let target = match media_type.as_str() {
+            GRAPHQL_RESPONSE_CONTENT_TYPE => &mut graphql_response_quality,
+            JSON_CONTENT_TYPE | "application/*" | "*/*" => &mut json_quality,
+            _ => continue,
+        };
+        if quality > *target {
+            *target = quality;
+        }
+    }
+
+    if graphql_response_quality > 0.0 && graphql_response_quality >= json_quality {
+        GRAPHQL_RESPONSE_CONTENT_TYPE
+    } else {
+        JSON_CONTENT_TYPE
+    }
+}
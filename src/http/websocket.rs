@@ -1,161 +1,523 @@
 //! WebSocket transport for subscription
 
-use crate::{Data, Error, ObjectType, Request, Response, Result, Schema, SubscriptionType};
-use futures::Stream;
+use crate::{
+    Data, Error, ObjectType, Request, Response, Result, Schema, ServerError, SubscriptionType,
+};
+use futures::future::{self, BoxFuture, Ready};
+use futures::{Future, Stream, TryFutureExt};
+use futures_timer::Delay;
 use pin_project_lite::pin_project;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::pin::Pin;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// The `Sec-WebSocket-Protocol` values of all the supported protocols, in the order of preference.
+pub const ALL_WEBSOCKET_PROTOCOLS: [&str; 2] = ["graphql-transport-ws", "graphql-ws"];
+
+/// A GraphQL over WebSocket protocol.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum WebSocketProtocols {
+    /// [subscriptions-transport-ws protocol](https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md).
+    SubscriptionsTransportWS,
+    /// [graphql-ws protocol](https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md).
+    GraphQLWS,
+}
+
+impl WebSocketProtocols {
+    /// Returns the `Sec-WebSocket-Protocol` value of the protocol.
+    pub fn sec_websocket_protocol(&self) -> &'static str {
+        match self {
+            WebSocketProtocols::SubscriptionsTransportWS => "graphql-ws",
+            WebSocketProtocols::GraphQLWS => "graphql-transport-ws",
+        }
+    }
+
+    /// Chooses a protocol from the `Sec-WebSocket-Protocol` header of the client.
+    ///
+    /// The first protocol of the client that is supported is chosen. Clients that do not send the
+    /// header get the subscriptions-transport-ws protocol, and `None` is returned if none of the
+    /// protocols of the client is supported.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::http::WebSocketProtocols;
+    ///
+    /// assert_eq!(
+    ///     WebSocketProtocols::negotiate(Some("graphql-transport-ws, graphql-ws")),
+    ///     Some(WebSocketProtocols::GraphQLWS),
+    /// );
+    /// assert_eq!(
+    ///     WebSocketProtocols::negotiate(None),
+    ///     Some(WebSocketProtocols::SubscriptionsTransportWS),
+    /// );
+    /// assert_eq!(WebSocketProtocols::negotiate(Some("mqtt")), None);
+    /// ```
+    pub fn negotiate(sec_websocket_protocol: Option<&str>) -> Option<Self> {
+        match sec_websocket_protocol {
+            Some(protocols) => protocols
+                .split(',')
+                .find_map(|protocol| protocol.trim().parse().ok()),
+            None => Some(WebSocketProtocols::SubscriptionsTransportWS),
+        }
+    }
+}
+
+impl FromStr for WebSocketProtocols {
+    type Err = Error;
+
+    fn from_str(protocol: &str) -> Result<Self, Self::Err> {
+        match protocol {
+            "graphql-ws" => Ok(WebSocketProtocols::SubscriptionsTransportWS),
+            "graphql-transport-ws" => Ok(WebSocketProtocols::GraphQLWS),
+            _ => Err(Error::new(format!(
+                "Unsupported Sec-WebSocket-Protocol: {}",
+                protocol
+            ))),
+        }
+    }
+}
+
+/// A message to send to the client.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum WsMessage {
+    /// A text message.
+    Text(String),
+
+    /// Close the connection with a status code and a reason.
+    Close(u16, String),
+}
+
+/// Hooks into the lifecycle of a websocket connection.
+///
+/// All of them are called with the data of the connection, which is returned by the data
+/// initialization function. They are called synchronously while the connection is polled or
+/// dropped, so spawn a task for any asynchronous work.
+#[allow(unused_variables)]
+pub trait WebSocketHooks: Send + Sync + 'static {
+    /// Called when the connection is initialized, before `connection_ack` is sent.
+    fn on_connect(&self, data: &Data) {}
+
+    /// Called when the client starts the operation `id`.
+    fn on_operation_start(&self, data: &Data, id: &str, request: &Request) {}
+
+    /// Called when the operation `id` is complete, failed or stopped by the client, including
+    /// when the connection goes away.
+    fn on_operation_complete(&self, data: &Data, id: &str) {}
+
+    /// Called when an initialized connection is closed or dropped.
+    fn on_disconnect(&self, data: &Data) {}
+}
+
+/// Options for `WebSocket`.
+#[derive(Default, Clone)]
+#[non_exhaustive]
+pub struct WebSocketOptions {
+    /// The interval between keep-alive messages, none are sent by default.
+    ///
+    /// These are `ka` messages in the subscriptions-transport-ws protocol and `ping` messages in
+    /// the graphql-ws protocol.
+    pub keepalive_interval: Option<Duration>,
+    /// The time the client has to send `connection_init` before the connection is closed,
+    /// unlimited by default.
+    pub connection_init_timeout: Option<Duration>,
+    /// The maximum number of active operations of a connection, unlimited by default.
+    pub max_operations: Option<usize>,
+    /// The hooks into the lifecycle of the connection.
+    pub hooks: Option<Arc<dyn WebSocketHooks>>,
+}
+
+impl WebSocketOptions {
+    /// Set the interval between keep-alive messages.
+    pub fn keepalive_interval(self, interval: Duration) -> Self {
+        WebSocketOptions {
+            keepalive_interval: Some(interval),
+            ..self
+        }
+    }
+
+    /// Set the time the client has to send `connection_init`.
+    pub fn connection_init_timeout(self, timeout: Duration) -> Self {
+        WebSocketOptions {
+            connection_init_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Set the maximum number of active operations of a connection.
+    pub fn max_operations(self, n: usize) -> Self {
+        WebSocketOptions {
+            max_operations: Some(n),
+            ..self
+        }
+    }
+
+    /// Set the hooks into the lifecycle of the connection.
+    pub fn hooks(self, hooks: impl WebSocketHooks) -> Self {
+        WebSocketOptions {
+            hooks: Some(Arc::new(hooks)),
+            ..self
+        }
+    }
+}
+
+type OperationStream = Pin<Box<dyn Stream<Item = Result<Response, Vec<ServerError>>> + Send>>;
+
+/// The state of a connection, which calls the `WebSocketHooks` when it goes away.
+#[derive(Default)]
+struct Connection {
+    data: Arc<Data>,
+    streams: HashMap<String, OperationStream>,
+    hooks: Option<Arc<dyn WebSocketHooks>>,
+    initialized: bool,
+}
+
+impl Connection {
+    fn initialize(&mut self, data: Option<Data>) {
+        if let Some(data) = data {
+            self.data = Arc::new(data);
+        }
+        if !self.initialized {
+            self.initialized = true;
+            if let Some(hooks) = &self.hooks {
+                hooks.on_connect(&self.data);
+            }
+        }
+    }
+
+    fn start_operation<Query, Mutation, Subscription>(
+        &mut self,
+        schema: &Schema<Query, Mutation, Subscription>,
+        id: String,
+        request: Request,
+    ) where
+        Query: ObjectType + Send + Sync + 'static,
+        Mutation: ObjectType + Send + Sync + 'static,
+        Subscription: SubscriptionType + Send + Sync + 'static,
+    {
+        self.complete_operation(&id);
+        if let Some(hooks) = &self.hooks {
+            hooks.on_operation_start(&self.data, &id, &request);
+        }
+        let stream = schema.execute_stream_with_ctx_data(request, Arc::clone(&self.data));
+        self.streams.insert(id, Box::pin(stream));
+    }
+
+    fn complete_operation(&mut self, id: &str) -> bool {
+        if self.streams.remove(id).is_none() {
+            return false;
+        }
+        if let Some(hooks) = &self.hooks {
+            hooks.on_operation_complete(&self.data, id);
+        }
+        true
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        if let Some(hooks) = &self.hooks {
+            for id in self.streams.keys() {
+                hooks.on_operation_complete(&self.data, id);
+            }
+            if self.initialized {
+                hooks.on_disconnect(&self.data);
+            }
+        }
+    }
+}
 
 pin_project! {
     /// A GraphQL connection over websocket.
     ///
-    /// [Reference](https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md).
+    /// It speaks one of the `WebSocketProtocols`, which is usually negotiated with
+    /// `WebSocketProtocols::negotiate` when the connection is upgraded.
     pub struct WebSocket<S, F, Query, Mutation, Subscription> {
         data_initializer: Option<F>,
-        data: Arc<Data>,
+        init_future: Option<BoxFuture<'static, Result<Option<Data>>>>,
+        connection: Connection,
         schema: Schema<Query, Mutation, Subscription>,
-        streams: HashMap<String, Pin<Box<dyn Stream<Item = Response> + Send>>>,
+        protocol: WebSocketProtocols,
+        options: WebSocketOptions,
+        closed: bool,
+        init_timer: Option<Delay>,
+        keepalive_timer: Option<Delay>,
         #[pin]
         stream: S,
     }
 }
 
 impl<S, Query, Mutation, Subscription>
-    WebSocket<S, fn(serde_json::Value) -> Result<Data>, Query, Mutation, Subscription>
+    WebSocket<S, fn(serde_json::Value) -> Ready<Result<Data>>, Query, Mutation, Subscription>
 {
     /// Create a new websocket.
     #[must_use]
-    pub fn new(schema: Schema<Query, Mutation, Subscription>, stream: S) -> Self {
-        Self {
-            data_initializer: None,
-            data: Arc::default(),
-            schema,
-            streams: HashMap::new(),
-            stream,
-        }
+    pub fn new(
+        schema: Schema<Query, Mutation, Subscription>,
+        stream: S,
+        protocol: WebSocketProtocols,
+    ) -> Self {
+        Self::with_data(schema, stream, None, protocol)
     }
 }
 
 impl<S, F, Query, Mutation, Subscription> WebSocket<S, F, Query, Mutation, Subscription> {
     /// Create a new websocket with a data initialization function.
     ///
-    /// This function, if present, will be called with the data sent by the client in the
-    /// [`GQL_CONNECTION_INIT` message](https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md#gql_connection_init).
-    /// From that point on the returned data will be accessible to all requests.
+    /// This async function, if present, will be called with the payload sent by the client in the
+    /// [`GQL_CONNECTION_INIT` message](https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md#gql_connection_init),
+    /// or `null` if there is none. The connection is acknowledged once it returns, and from that
+    /// point on the returned data will be accessible to all requests. If it fails, the connection
+    /// is closed.
     #[must_use]
-    pub fn with_data(
+    pub fn with_data<R>(
         schema: Schema<Query, Mutation, Subscription>,
         stream: S,
         data_initializer: Option<F>,
-    ) -> Self {
+        protocol: WebSocketProtocols,
+    ) -> Self
+    where
+        F: FnOnce(serde_json::Value) -> R,
+        R: Future<Output = Result<Data>> + Send + 'static,
+    {
         Self {
             data_initializer,
-            data: Arc::default(),
+            init_future: None,
+            connection: Connection::default(),
             schema,
-            streams: HashMap::new(),
+            protocol,
+            options: WebSocketOptions::default(),
+            closed: false,
+            init_timer: None,
+            keepalive_timer: None,
             stream,
         }
     }
+
+    /// Set the options of the connection.
+    ///
+    /// The `connection_init` timeout starts when this is called.
+    #[must_use]
+    pub fn options(mut self, options: WebSocketOptions) -> Self {
+        self.connection.hooks = options.hooks.clone();
+        self.init_timer = options.connection_init_timeout.map(Delay::new);
+        self.options = options;
+        self
+    }
 }
 
-impl<S, F, Query, Mutation, Subscription> Stream for WebSocket<S, F, Query, Mutation, Subscription>
+impl<S, F, R, Query, Mutation, Subscription> Stream
+    for WebSocket<S, F, Query, Mutation, Subscription>
 where
     S: Stream,
     S::Item: AsRef<[u8]>,
-    F: FnOnce(serde_json::Value) -> Result<Data>,
+    F: FnOnce(serde_json::Value) -> R,
+    R: Future<Output = Result<Data>> + Send + 'static,
     Query: ObjectType + Send + Sync + 'static,
     Mutation: ObjectType + Send + Sync + 'static,
     Subscription: SubscriptionType + Send + Sync + 'static,
 {
-    type Item = String;
+    type Item = WsMessage;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        let this = self.project();
+        let mut this = self.project();
+
+        if *this.closed {
+            return Poll::Ready(None);
+        }
+        let protocol = *this.protocol;
+
+        if let Some(init_timer) = this.init_timer {
+            if Pin::new(init_timer).poll(cx).is_ready() {
+                return close(
+                    this.closed,
+                    4408,
+                    "Connection initialisation timeout".to_string(),
+                );
+            }
+        }
 
-        match this.stream.poll_next(cx) {
-            Poll::Ready(message) => {
-                let message = match message {
-                    Some(message) => message,
-                    None => return Poll::Ready(None),
+        loop {
+            // No more messages are read until the data initialization function returns.
+            if let Some(init_future) = this.init_future {
+                let data = match init_future.as_mut().poll(cx) {
+                    Poll::Ready(res) => res,
+                    Poll::Pending => return Poll::Pending,
                 };
+                *this.init_future = None;
+                match data {
+                    Ok(data) => {
+                        this.connection.initialize(data);
+                        *this.init_timer = None;
+                        *this.keepalive_timer = this.options.keepalive_interval.map(Delay::new);
+                        return Poll::Ready(Some(ServerMessage::ConnectionAck.into_ws_message()));
+                    }
+                    Err(e) => match protocol {
+                        WebSocketProtocols::SubscriptionsTransportWS => {
+                            *this.closed = true;
+                            return Poll::Ready(Some(
+                                ServerMessage::ConnectionError { payload: e }.into_ws_message(),
+                            ));
+                        }
+                        WebSocketProtocols::GraphQLWS => {
+                            return close(this.closed, 4403, e.message);
+                        }
+                    },
+                }
+            }
+
+            let message = match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(message)) => message,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => break,
+            };
 
-                let message: ClientMessage = match serde_json::from_slice(message.as_ref()) {
-                    Ok(message) => message,
-                    Err(e) => {
+            let message = serde_json::from_slice::<ClientMessage>(message.as_ref())
+                .map_err(|e| e.to_string())
+                .and_then(|message| {
+                    if message.is_supported_by(protocol) {
+                        Ok(message)
+                    } else {
+                        Err("Unsupported message type".to_string())
+                    }
+                });
+            let message = match message {
+                Ok(message) => message,
+                Err(e) => match protocol {
+                    WebSocketProtocols::SubscriptionsTransportWS => {
                         return Poll::Ready(Some(
-                            serde_json::to_string(&ServerMessage::ConnectionError {
-                                payload: Error::new(e.to_string()),
-                            })
-                            .unwrap(),
-                        ))
+                            ServerMessage::ConnectionError {
+                                payload: Error::new(e),
+                            }
+                            .into_ws_message(),
+                        ));
                     }
-                };
+                    WebSocketProtocols::GraphQLWS => return close(this.closed, 4400, e),
+                },
+            };
 
-                match message {
-                    ClientMessage::ConnectionInit { payload } => {
-                        if let Some(payload) = payload {
-                            if let Some(data_initializer) = this.data_initializer.take() {
-                                *this.data = Arc::new(match data_initializer(payload) {
-                                    Ok(data) => data,
-                                    Err(e) => {
-                                        return Poll::Ready(Some(
-                                            serde_json::to_string(
-                                                &ServerMessage::ConnectionError { payload: e },
-                                            )
-                                            .unwrap(),
-                                        ))
-                                    }
-                                });
+            match message {
+                ClientMessage::ConnectionInit { payload } => {
+                    if this.connection.initialized && protocol == WebSocketProtocols::GraphQLWS {
+                        return close(
+                            this.closed,
+                            4429,
+                            "Too many initialisation requests".to_string(),
+                        );
+                    }
+                    *this.init_future = Some(match this.data_initializer.take() {
+                        Some(data_initializer) => {
+                            Box::pin(data_initializer(payload.unwrap_or_default()).map_ok(Some))
+                        }
+                        None => Box::pin(future::ready(Ok(None))),
+                    });
+                }
+                ClientMessage::Start {
+                    id,
+                    payload: request,
+                }
+                | ClientMessage::Subscribe {
+                    id,
+                    payload: request,
+                } => {
+                    if !this.connection.initialized {
+                        match protocol {
+                            WebSocketProtocols::SubscriptionsTransportWS => {
+                                return Poll::Ready(Some(error_message(
+                                    &id,
+                                    "The connection has not been initialized",
+                                )));
+                            }
+                            WebSocketProtocols::GraphQLWS => {
+                                return close(this.closed, 4401, "Unauthorized".to_string());
                             }
                         }
-                        return Poll::Ready(Some(
-                            serde_json::to_string(&ServerMessage::ConnectionAck).unwrap(),
-                        ));
                     }
-                    ClientMessage::Start {
-                        id,
-                        payload: request,
-                    } => {
-                        this.streams.insert(
-                            id,
-                            Box::pin(
-                                this.schema
-                                    .execute_stream_with_ctx_data(request, Arc::clone(this.data)),
-                            ),
+                    let streams = &this.connection.streams;
+                    if protocol == WebSocketProtocols::GraphQLWS && streams.contains_key(&id) {
+                        return close(
+                            this.closed,
+                            4409,
+                            format!("Subscriber for {} already exists", id),
                         );
                     }
-                    ClientMessage::Stop { id } => {
-                        if this.streams.remove(id).is_some() {
-                            return Poll::Ready(Some(
-                                serde_json::to_string(&ServerMessage::Complete { id }).unwrap(),
-                            ));
+                    if let Some(max_operations) = this.options.max_operations {
+                        if streams.len() >= max_operations && !streams.contains_key(&id) {
+                            return Poll::Ready(Some(error_message(
+                                &id,
+                                "Too many active operations",
+                            )));
                         }
                     }
-                    ClientMessage::ConnectionTerminate => return Poll::Ready(None),
+                    this.connection.start_operation(this.schema, id, request);
                 }
+                ClientMessage::Stop { id } => {
+                    if this.connection.complete_operation(id) {
+                        return Poll::Ready(Some(ServerMessage::Complete { id }.into_ws_message()));
+                    }
+                }
+                ClientMessage::Complete { id } => {
+                    this.connection.complete_operation(id);
+                }
+                ClientMessage::Ping { .. } => {
+                    return Poll::Ready(Some(ServerMessage::Pong.into_ws_message()));
+                }
+                ClientMessage::Pong { .. } => {}
+                ClientMessage::ConnectionTerminate => return Poll::Ready(None),
             }
-            Poll::Pending => {}
         }
 
-        for (id, stream) in &mut *this.streams {
+        if let Some(keepalive_timer) = this.keepalive_timer {
+            if Pin::new(&mut *keepalive_timer).poll(cx).is_ready() {
+                if let Some(interval) = this.options.keepalive_interval {
+                    keepalive_timer.reset(interval);
+                }
+                return Poll::Ready(Some(
+                    match protocol {
+                        WebSocketProtocols::SubscriptionsTransportWS => ServerMessage::KeepAlive,
+                        WebSocketProtocols::GraphQLWS => ServerMessage::Ping,
+                    }
+                    .into_ws_message(),
+                ));
+            }
+        }
+
+        for (id, stream) in &mut this.connection.streams {
             match Pin::new(stream).poll_next(cx) {
-                Poll::Ready(Some(payload)) => {
+                Poll::Ready(Some(Err(errors))) => {
+                    // The operation failed before it was executed, e.g. validation, which ends it.
+                    // Errors of the resolvers are sent in the payload of `data` or `next`.
+                    let id = id.clone();
+                    this.connection.complete_operation(&id);
                     return Poll::Ready(Some(
-                        serde_json::to_string(&ServerMessage::Data {
-                            id,
-                            payload: Box::new(payload),
-                        })
-                        .unwrap(),
+                        ServerMessage::Error {
+                            id: &id,
+                            payload: errors,
+                        }
+                        .into_ws_message(),
+                    ));
+                }
+                Poll::Ready(Some(Ok(payload))) => {
+                    let payload = Box::new(payload);
+                    return Poll::Ready(Some(
+                        match protocol {
+                            WebSocketProtocols::SubscriptionsTransportWS => {
+                                ServerMessage::Data { id, payload }
+                            }
+                            WebSocketProtocols::GraphQLWS => ServerMessage::Next { id, payload },
+                        }
+                        .into_ws_message(),
                     ));
                 }
                 Poll::Ready(None) => {
                     let id = id.clone();
-                    this.streams.remove(&id);
+                    this.connection.complete_operation(&id);
                     return Poll::Ready(Some(
-                        serde_json::to_string(&ServerMessage::Complete { id: &id }).unwrap(),
+                        ServerMessage::Complete { id: &id }.into_ws_message(),
                     ));
                 }
                 Poll::Pending => {}
@@ -166,28 +528,97 @@ where
     }
 }
 
+fn error_message(id: &str, message: &str) -> WsMessage {
+    ServerMessage::Error {
+        id,
+        payload: vec![ServerError::new(message)],
+    }
+    .into_ws_message()
+}
+
+fn close(closed: &mut bool, code: u16, reason: String) -> Poll<Option<WsMessage>> {
+    *closed = true;
+    Poll::Ready(Some(WsMessage::Close(code, reason)))
+}
+
 #[derive(Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum ClientMessage<'a> {
-    ConnectionInit { payload: Option<serde_json::Value> },
-    Start { id: String, payload: Request },
-    Stop { id: &'a str },
+    ConnectionInit {
+        payload: Option<serde_json::Value>,
+    },
+    Start {
+        id: String,
+        payload: Request,
+    },
+    Subscribe {
+        id: String,
+        payload: Request,
+    },
+    Stop {
+        id: &'a str,
+    },
+    Complete {
+        id: &'a str,
+    },
+    Ping {
+        #[allow(dead_code)]
+        payload: Option<serde_json::Value>,
+    },
+    Pong {
+        #[allow(dead_code)]
+        payload: Option<serde_json::Value>,
+    },
     ConnectionTerminate,
 }
 
+impl<'a> ClientMessage<'a> {
+    fn is_supported_by(&self, protocol: WebSocketProtocols) -> bool {
+        match self {
+            ClientMessage::ConnectionInit { .. } => true,
+            ClientMessage::Start { .. }
+            | ClientMessage::Stop { .. }
+            | ClientMessage::ConnectionTerminate => {
+                protocol == WebSocketProtocols::SubscriptionsTransportWS
+            }
+            ClientMessage::Subscribe { .. }
+            | ClientMessage::Complete { .. }
+            | ClientMessage::Ping { .. }
+            | ClientMessage::Pong { .. } => protocol == WebSocketProtocols::GraphQLWS,
+        }
+    }
+}
+
 #[derive(Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum ServerMessage<'a> {
-    ConnectionError { payload: Error },
+    ConnectionError {
+        payload: Error,
+    },
     ConnectionAck,
-    Data { id: &'a str, payload: Box<Response> },
-    // Not used by this library, as it's not necessary to send
-    // Error {
-    //     id: &'a str,
-    //     payload: serde_json::Value,
-    // },
-    Complete { id: &'a str },
-    // Not used by this library
-    // #[serde(rename = "ka")]
-    // KeepAlive
+    Data {
+        id: &'a str,
+        payload: Box<Response>,
+    },
+    Next {
+        id: &'a str,
+        payload: Box<Response>,
+    },
+    Error {
+        id: &'a str,
+        payload: Vec<ServerError>,
+    },
+    Complete {
+        id: &'a str,
+    },
+    Ping,
+    Pong,
+    #[serde(rename = "ka")]
+    KeepAlive,
+}
+
+impl<'a> ServerMessage<'a> {
+    fn into_ws_message(self) -> WsMessage {
+        WsMessage::Text(serde_json::to_string(&self).unwrap())
+    }
 }
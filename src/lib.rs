@@ -44,6 +44,7 @@
 //! * Subscriptions (WebSocket transport)
 //! * Custom extensions
 //! * Apollo Tracing extension
+//! * Apollo Federated Tracing extension
 //! * Limit query complexity/depth
 //! * Error Extensions
 //! * Apollo Federation
@@ -55,6 +56,7 @@
 //! This crate offers the following features, all of which are activated by default:
 //!
 //! - `apollo_tracing`: Enable the [Apollo tracing extension](extensions/struct.ApolloTracing.html).
+//! - `apollo_federated_tracing`: Enable the [Apollo federated tracing extension](extensions/struct.ApolloFederatedTracing.html).
 //! - `apollo_persisted_queries`: Enable the [Apollo persisted queries extension](extensions/apollo_persisted_queries/struct.ApolloPersistedQueries.html).
 //! - `log`: Enable the [logger extension](extensions/struct.Logger.html).
 //! - `tracing`: Enable the [tracing extension](extensions/struct.Tracing.html).
@@ -66,6 +68,9 @@
 //! - `url`: Integrate with the [`url` crate](https://crates.io/crates/url).
 //! - `uuid`: Integrate with the [`uuid` crate](https://crates.io/crates/uuid).
 //!
+//! The `signed_cursor` feature, which enables [signed connection cursors](connection/struct.SignedCursor.html),
+//! is not activated by default.
+//!
 //! ## Integrations
 //!
 //! * Actix-web [async-graphql-actix_web](https://crates.io/crates/async-graphql-actix-web)
@@ -156,14 +161,16 @@ pub use subscription::SubscriptionType;
 
 pub use async_graphql_parser as parser;
 pub use base::{
-    InputObjectType, InputValueType, InterfaceType, ObjectType, OutputValueType, Type, UnionType,
+    InputObjectType, InputValueType, InterfaceType, ObjectType, OutputValueType, ResolverError,
+    ResolverResult, Type, UnionType,
 };
 pub use context::{
     Context, ContextBase, Data, QueryEnv, QueryPathNode, QueryPathSegment, ResolveId, Variables,
+    VisibleContext,
 };
 pub use error::{
-    Error, ExtendError, InputValueError, InputValueResult, ParseRequestError, PathSegment, Result,
-    ServerError, ServerResult,
+    Error, ErrorMaskPolicy, ExtendError, InputValueError, InputValueResult, ParseRequestError,
+    PathSegment, Result, ServerError, ServerResult,
 };
 pub use look_ahead::Lookahead;
 pub use parser::types::{ConstValue as Value, Number};
@@ -171,7 +178,7 @@ pub use registry::CacheControl;
 pub use request::{BatchRequest, Request};
 pub use resolver_utils::{ContainerType, EnumType, ScalarType};
 pub use response::{BatchResponse, Response};
-pub use schema::{Schema, SchemaBuilder, SchemaEnv};
+pub use schema::{IntrospectionMode, Schema, SchemaBuilder, SchemaEnv};
 pub use validation::ValidationMode;
 
 #[doc(no_inline)]
@@ -191,6 +198,8 @@ pub use types::*;
 /// | name          | Object name               | string   | Y        |
 /// | cache_control | Object cache control      | [`CacheControl`](struct.CacheControl.html) | Y        |
 /// | extends       | Add fields to an entity that's defined in another service | bool | Y |
+/// | guard         | Guard checked once per object, before any of its fields. It cannot use field arguments (`arg:`) | [`Guard`](guard/trait.Guard.html) | Y |
+/// | visible       | Function that decides whether the type is visible to the request, `fn(&VisibleContext<'_>) -> bool` | code path | Y |
 ///
 /// # Field parameters
 ///
@@ -206,6 +215,9 @@ pub use types::*;
 /// | requires      | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
 /// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
 /// | post_guard    | Field of post guard       | [`PostGuard`](guard/trait.PostGuard.html) | Y        |
+/// | visible       | Function that decides whether the field is visible to the request, `fn(&VisibleContext<'_>) -> bool` | code path | Y |
+/// | global_id     | Output the `id` field as a [`GlobalId`](struct.GlobalId.html) and make the object implement the Relay `Node` interface | bool | Y |
+/// | node          | Use the method of the query root to load the nodes of its return type for the `node` and `nodes` fields. It takes the id of the object within its type as its only argument | bool | Y |
 ///
 /// # Field argument parameters
 ///
@@ -216,7 +228,8 @@ pub use types::*;
 /// | default      | Use `Default::default` for default value | none        | Y        |
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
-/// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | validator    | Input value validator, `async = "..."` adds an [`AsyncInputValueValidator`](validators/trait.AsyncInputValueValidator.html) | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | deprecation  | Argument deprecation reason, the argument must be nullable or have a default value | string | Y |
 /// | key          | Is entity key                            | bool        | Y        |
 ///
 /// # Valid field return types
@@ -230,7 +243,10 @@ pub use types::*;
 /// - GraphQL objects.
 /// - GraphQL enums.
 /// - References to any of the above types, such as `&i32` or `&Option<String>`.
-/// - `Result<T, E>`, such as `Result<i32, E>`
+/// - `Result<T, E>`, such as `Result<i32, E>`, where `E` is [`Error`](struct.Error.html) or
+/// derives `GraphQLError`
+/// - `Result<T, E>` where `T` is an object and `E` derives `Union`, which resolves to a
+/// [`ResultUnion`](types/enum.ResultUnion.html) named `TOrError`
 ///
 /// # Context
 ///
@@ -307,6 +323,8 @@ pub use async_graphql_derive::Object;
 /// | name          | Object name               | string   | Y        |
 /// | cache_control | Object cache control      | [`CacheControl`](struct.CacheControl.html) | Y        |
 /// | extends       | Add fields to an entity that's defined in another service | bool | Y |
+/// | guard         | Guard checked once per object, before any of its fields. It cannot use field arguments (`arg:`) | [`Guard`](guard/trait.Guard.html) | Y |
+/// | visible       | Function that decides whether the type is visible to the request, `fn(&VisibleContext<'_>) -> bool` | code path | Y |
 ///
 /// # Field parameters
 ///
@@ -316,12 +334,14 @@ pub use async_graphql_derive::Object;
 /// | name          | Field name                | string   | Y        |
 /// | deprecation   | Field deprecation reason  | string   | Y        |
 /// | owned         | Field resolver return a ownedship value  | bool   | Y        |
+/// | global_id     | Output the `id` field as a [`GlobalId`](struct.GlobalId.html) and make the object implement the Relay `Node` interface | bool | Y |
 /// | cache_control | Field cache control       | [`CacheControl`](struct.CacheControl.html) | Y        |
 /// | external      | Mark a field as owned by another service. This allows service A to use fields from service B while also knowing at runtime the types of that field. | bool | Y |
 /// | provides      | Annotate the expected returned fieldset from a field on a base type that is guaranteed to be selectable by the gateway. | string | Y |
 /// | requires      | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
 /// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
 /// | post_guard    | Field of post guard       | [`PostGuard`](guard/trait.PostGuard.html) | Y        |
+/// | visible       | Function that decides whether the field is visible to the request, `fn(&VisibleContext<'_>) -> bool` | code path | Y |
 ///
 /// # Examples
 ///
@@ -353,6 +373,7 @@ pub use async_graphql_derive::SimpleObject;
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Enum name                 | string   | Y        |
 /// | remote      | Derive a remote enum      | string   | Y        |
+/// | visible     | Function that decides whether the type is visible to the request, `fn(&VisibleContext<'_>) -> bool` | code path | Y |
 ///
 /// # Item parameters
 ///
@@ -360,6 +381,7 @@ pub use async_graphql_derive::SimpleObject;
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Item name                 | string   | Y        |
 /// | deprecation | Item deprecation reason   | string   | Y        |
+/// | visible     | Function that decides whether the item is visible to the request, `fn(&VisibleContext<'_>) -> bool` | code path | Y |
 ///
 /// # Examples
 ///
@@ -407,6 +429,8 @@ pub use async_graphql_derive::Enum;
 /// | Attribute   | description               | Type     | Optional |
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Object name               | string   | Y        |
+/// | validator   | Function that validates the whole object, `fn(&Self) -> Result<(), String>` | code path | Y |
+/// | visible     | Function that decides whether the type is visible to the request, `fn(&VisibleContext<'_>) -> bool` | code path | Y |
 ///
 /// # Field parameters
 ///
@@ -418,6 +442,7 @@ pub use async_graphql_derive::Enum;
 /// | default_with | Expression to generate default value     | code string | Y        |
 /// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
 /// | flatten      | Similar to serde (flatten)               | boolean     | Y        |
+/// | deprecation  | Field deprecation reason, the field must be nullable or have a default value | string | Y |
 ///
 /// # Examples
 ///
@@ -464,6 +489,8 @@ pub use async_graphql_derive::InputObject;
 /// | name        | Object name               | string   | Y        |
 /// | field      | Fields of this Interface  | [InterfaceField] | N |
 /// | extends       | Add fields to an entity that's defined in another service | bool | Y |
+/// | guard       | Guard checked once per object, before any of its fields. It cannot use field arguments (`arg:`) | [`Guard`](guard/trait.Guard.html) | Y |
+/// | visible     | Function that decides whether the type is visible to the request, `fn(&VisibleContext<'_>) -> bool` | code path | Y |
 ///
 /// # Field parameters
 ///
@@ -478,6 +505,7 @@ pub use async_graphql_derive::InputObject;
 /// | external      | Mark a field as owned by another service. This allows service A to use fields from service B while also knowing at runtime the types of that field. | bool | Y |
 /// | provides      | Annotate the expected returned fieldset from a field on a base type that is guaranteed to be selectable by the gateway. | string | Y |
 /// | requires      | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
+/// | visible       | Function that decides whether the field is visible to the request, `fn(&VisibleContext<'_>) -> bool` | code path | Y |
 ///
 /// # Field argument parameters
 ///
@@ -489,6 +517,7 @@ pub use async_graphql_derive::InputObject;
 /// | default      | Use `Default::default` for default value | none        | Y        |
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
+/// | deprecation  | Argument deprecation reason, the argument must be nullable or have a default value | string | Y |
 ///
 /// # Define an interface
 ///
@@ -504,6 +533,9 @@ pub use async_graphql_derive::InputObject;
 /// }
 /// ```
 ///
+/// A variant can also be another interface, which then implements `MyInterface`. The objects
+/// implementing that interface become possible types of `MyInterface` too.
+///
 /// # Fields
 ///
 /// The type, name, and parameter fields of the interface must exactly match the type of the
@@ -594,6 +626,8 @@ pub use async_graphql_derive::Interface;
 /// | Attribute   | description               | Type     | Optional |
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Object name               | string   | Y        |
+/// | guard       | Guard checked once per object, before any of its fields. It cannot use field arguments (`arg:`) | [`Guard`](guard/trait.Guard.html) | Y |
+/// | visible     | Function that decides whether the type is visible to the request, `fn(&VisibleContext<'_>) -> bool` | code path | Y |
 ///
 /// # Item parameters
 ///
@@ -689,7 +723,8 @@ pub use async_graphql_derive::Union;
 /// | default      | Use `Default::default` for default value | none        | Y        |
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
-/// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | validator    | Input value validator, `async = "..."` adds an [`AsyncInputValueValidator`](validators/trait.AsyncInputValueValidator.html) | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | deprecation  | Argument deprecation reason, the argument must be nullable or have a default value | string | Y |
 ///
 /// # Examples
 ///
@@ -716,6 +751,7 @@ pub use async_graphql_derive::Subscription;
 /// | Attribute   | description               | Type     | Optional |
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Scalar name               | string   | Y        |
+/// | specified_by_url | URL of the specification of the scalar, exported with `@specifiedBy` | string | Y |
 ///
 pub use async_graphql_derive::Scalar;
 
@@ -798,3 +834,64 @@ pub use async_graphql_derive::MergedObject;
 /// struct Subscription(Subscription1, Subscription2);
 /// ```
 pub use async_graphql_derive::MergedSubscription;
+
+/// Define an error type that can be returned from resolvers
+///
+/// Generates `From<T> for Error`, so resolvers can return `Result<T, MyError>` or use `?` on it.
+/// Each variant becomes an error with the given message, and its `code` and fields are copied
+/// into the error extensions. Because of the generated conversion, the type must not implement
+/// `Display`.
+///
+/// # Macro parameters
+///
+/// | Attribute   | description               | Type     | Optional |
+/// |-------------|---------------------------|----------|----------|
+/// | internal    | For internal use          | bool     | Y        |
+///
+/// # Item parameters
+///
+/// | Attribute   | description                                                           | Type     | Optional |
+/// |-------------|-----------------------------------------------------------------------|----------|----------|
+/// | message     | Message template, `{field}` is replaced with the value of the field | string   | N        |
+/// | code        | Error code, defaults to the variant name in SCREAMING_SNAKE_CASE      | string   | Y        |
+///
+/// # Field parameters
+///
+/// | Attribute   | description                                                           | Type     | Optional |
+/// |-------------|-----------------------------------------------------------------------|----------|----------|
+/// | skip        | Do not copy this field into the extensions                            | bool     | Y        |
+/// | name        | Extension name, fields of tuple variants are only copied if it is set | string   | Y        |
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(GraphQLError)]
+/// enum MyError {
+///     #[graphql(code = "NOT_FOUND", message = "User {id} not found")]
+///     UserNotFound { id: i32 },
+///     #[graphql(message = "Access denied")]
+///     Forbidden,
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn user(&self, id: i32) -> Result<String, MyError> {
+///         Err(MyError::UserNotFound { id })
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///     let err = schema.execute("{ user(id: 10) }").await.into_result().unwrap_err();
+///     assert_eq!(err[0].message, "User 10 not found");
+///     assert_eq!(
+///         serde_json::Value::Object(err[0].extensions.clone().unwrap()),
+///         serde_json::json!({ "code": "NOT_FOUND", "id": 10 })
+///     );
+/// });
+/// ```
+pub use async_graphql_derive::GraphQLError;
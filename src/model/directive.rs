@@ -86,6 +86,10 @@ impl<'a> __Directive<'a> {
         &self.directive.locations
     }
 
+    async fn is_repeatable(&self) -> bool {
+        self.directive.is_repeatable
+    }
+
     async fn args(&self) -> Vec<__InputValue<'a>> {
         self.directive
             .args
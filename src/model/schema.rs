@@ -1,5 +1,5 @@
 use crate::model::{__Directive, __Type};
-use crate::{registry, Object};
+use crate::{registry, Context, Object};
 use itertools::Itertools;
 
 pub struct __Schema<'a> {
@@ -9,12 +9,18 @@ pub struct __Schema<'a> {
 /// A GraphQL Schema defines the capabilities of a GraphQL server. It exposes all available types and directives on the server, as well as the entry points for query, mutation, and subscription operations.
 #[Object(internal)]
 impl<'a> __Schema<'a> {
+    /// The description of the schema.
+    async fn description(&self) -> Option<String> {
+        self.registry.description.clone()
+    }
+
     /// A list of all types supported by this server.
-    async fn types(&self) -> Vec<__Type<'a>> {
+    async fn types(&self, ctx: &Context<'_>) -> Vec<__Type<'a>> {
         let mut types = self
             .registry
             .types
             .values()
+            .filter(|ty| ty.is_visible(&ctx.visible_context()))
             .map(|ty| (ty.name(), __Type::new_simple(self.registry, ty)))
             .collect_vec();
         types.sort_by(|a, b| a.0.cmp(b.0));
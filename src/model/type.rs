@@ -1,5 +1,5 @@
 use crate::model::{__EnumValue, __Field, __InputValue, __TypeKind};
-use crate::{registry, Object};
+use crate::{registry, Context, Object};
 use itertools::Itertools;
 
 enum TypeDetail<'a> {
@@ -92,6 +92,7 @@ impl<'a> __Type<'a> {
 
     async fn fields(
         &self,
+        ctx: &Context<'_>,
         #[graphql(default = false)] include_deprecated: bool,
     ) -> Option<Vec<__Field<'a>>> {
         if let TypeDetail::Named(ty) = &self.detail {
@@ -101,6 +102,9 @@ impl<'a> __Type<'a> {
                     .filter(|field| {
                         (include_deprecated || field.deprecation.is_none())
                             && !field.name.starts_with("__")
+                            && self
+                                .registry
+                                .is_visible_field(&ctx.visible_context(), field)
                     })
                     .map(|field| __Field {
                         registry: self.registry,
@@ -113,29 +117,32 @@ impl<'a> __Type<'a> {
         }
     }
 
-    async fn interfaces(&self) -> Option<Vec<__Type<'a>>> {
-        if let TypeDetail::Named(registry::MetaType::Object { name, .. }) = &self.detail {
-            Some(
-                self.registry
-                    .implements
-                    .get(name)
-                    .unwrap_or(&Default::default())
-                    .iter()
-                    .map(|ty| __Type::new(self.registry, ty))
-                    .collect(),
-            )
-        } else {
-            None
-        }
+    async fn interfaces(&self, ctx: &Context<'_>) -> Option<Vec<__Type<'a>>> {
+        let name = match &self.detail {
+            TypeDetail::Named(registry::MetaType::Object { name, .. }) => name,
+            TypeDetail::Named(registry::MetaType::Interface { name, .. }) => name,
+            _ => return None,
+        };
+        Some(
+            self.registry
+                .implements
+                .get(name)
+                .unwrap_or(&Default::default())
+                .iter()
+                .filter(|ty| self.registry.is_visible_type(&ctx.visible_context(), ty))
+                .map(|ty| __Type::new(self.registry, ty))
+                .collect(),
+        )
     }
 
-    async fn possible_types(&self) -> Option<Vec<__Type<'a>>> {
+    async fn possible_types(&self, ctx: &Context<'_>) -> Option<Vec<__Type<'a>>> {
         if let TypeDetail::Named(registry::MetaType::Interface { possible_types, .. }) =
             &self.detail
         {
             Some(
                 possible_types
                     .iter()
+                    .filter(|ty| self.registry.is_visible_type(&ctx.visible_context(), ty))
                     .map(|ty| __Type::new(self.registry, ty))
                     .collect(),
             )
@@ -145,6 +152,7 @@ impl<'a> __Type<'a> {
             Some(
                 possible_types
                     .iter()
+                    .filter(|ty| self.registry.is_visible_type(&ctx.visible_context(), ty))
                     .map(|ty| __Type::new(self.registry, ty))
                     .collect(),
             )
@@ -155,13 +163,17 @@ impl<'a> __Type<'a> {
 
     async fn enum_values(
         &self,
+        ctx: &Context<'_>,
         #[graphql(default = false)] include_deprecated: bool,
     ) -> Option<Vec<__EnumValue<'a>>> {
         if let TypeDetail::Named(registry::MetaType::Enum { enum_values, .. }) = &self.detail {
             Some(
                 enum_values
                     .values()
-                    .filter(|field| include_deprecated || field.deprecation.is_none())
+                    .filter(|value| {
+                        (include_deprecated || value.deprecation.is_none())
+                            && value.is_visible(&ctx.visible_context())
+                    })
                     .map(|value| __EnumValue {
                         registry: self.registry,
                         value,
@@ -173,13 +185,17 @@ impl<'a> __Type<'a> {
         }
     }
 
-    async fn input_fields(&self) -> Option<Vec<__InputValue<'a>>> {
+    async fn input_fields(
+        &self,
+        #[graphql(default = false)] include_deprecated: bool,
+    ) -> Option<Vec<__InputValue<'a>>> {
         if let TypeDetail::Named(registry::MetaType::InputObject { input_fields, .. }) =
             &self.detail
         {
             Some(
                 input_fields
                     .values()
+                    .filter(|input_value| include_deprecated || input_value.deprecation.is_none())
                     .map(|input_value| __InputValue {
                         registry: self.registry,
                         input_value,
@@ -200,4 +216,16 @@ impl<'a> __Type<'a> {
             None
         }
     }
+
+    #[graphql(name = "specifiedByURL")]
+    async fn specified_by_url(&self) -> Option<String> {
+        if let TypeDetail::Named(registry::MetaType::Scalar {
+            specified_by_url, ..
+        }) = &self.detail
+        {
+            specified_by_url.map(|url| url.to_string())
+        } else {
+            None
+        }
+    }
 }
@@ -1,9 +1,24 @@
 use crate::registry::{MetaField, MetaInputValue, MetaType, Registry};
+use crate::VisibleContext;
 use itertools::Itertools;
 use std::fmt::Write;
 
 impl Registry {
     pub fn export_sdl(&self, federation: bool) -> String {
+        self.export_sdl_with_visibility(federation, None)
+    }
+
+    /// Export the schema as SDL, omitting the fields, enum values and types that are not visible
+    /// to the context.
+    pub fn export_sdl_with_context(&self, federation: bool, ctx: &VisibleContext<'_>) -> String {
+        self.export_sdl_with_visibility(federation, Some(ctx))
+    }
+
+    fn export_sdl_with_visibility(
+        &self,
+        federation: bool,
+        ctx: Option<&VisibleContext<'_>>,
+    ) -> String {
         let mut sdl = String::new();
 
         for ty in self.types.values() {
@@ -11,6 +26,12 @@ impl Registry {
                 continue;
             }
 
+            if let Some(ctx) = ctx {
+                if !ty.is_visible(ctx) {
+                    continue;
+                }
+            }
+
             if federation {
                 const FEDERATION_TYPES: &[&str] = &["_Any", "_Entity", "_Service"];
                 if FEDERATION_TYPES.contains(&ty.name()) {
@@ -18,10 +39,15 @@ impl Registry {
                 }
             }
 
-            self.export_type(ty, &mut sdl, federation);
+            self.export_type(ty, &mut sdl, federation, ctx);
         }
 
         if !federation {
+            self.export_directives(&mut sdl);
+
+            if let Some(description) = &self.description {
+                writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description).ok();
+            }
             writeln!(sdl, "schema {{").ok();
             writeln!(sdl, "\tquery: {}", self.query_type).ok();
             if let Some(mutation_type) = self.mutation_type.as_deref() {
@@ -36,10 +62,66 @@ impl Registry {
         sdl
     }
 
+    fn export_directives(&self, sdl: &mut String) {
+        const BUILTIN_DIRECTIVES: &[&str] = &["include", "skip", "deprecated", "specifiedBy"];
+
+        for directive in self
+            .directives
+            .values()
+            .filter(|directive| !BUILTIN_DIRECTIVES.contains(&directive.name))
+            .sorted_by_key(|directive| directive.name)
+        {
+            if let Some(description) = directive.description {
+                writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description).ok();
+            }
+            write!(sdl, "directive @{}", directive.name).ok();
+            if !directive.args.is_empty() {
+                write!(
+                    sdl,
+                    "({})",
+                    directive.args.values().map(export_input_value).join(", ")
+                )
+                .ok();
+            }
+            if directive.is_repeatable {
+                write!(sdl, " repeatable").ok();
+            }
+            writeln!(
+                sdl,
+                " on {}",
+                directive
+                    .locations
+                    .iter()
+                    .map(|location| format!("{:?}", location))
+                    .join(" | ")
+            )
+            .ok();
+        }
+    }
+
+    fn export_implements(&self, sdl: &mut String, name: &str, ctx: Option<&VisibleContext<'_>>) {
+        if let Some(implements) = self.implements.get(name) {
+            let implements = implements
+                .iter()
+                .filter(|ty| self.is_exported_type(ctx, ty))
+                .collect_vec();
+            if !implements.is_empty() {
+                write!(sdl, "implements {} ", implements.iter().join(" & ")).ok();
+            }
+        }
+    }
+
+    fn is_exported_type(&self, ctx: Option<&VisibleContext<'_>>, type_name: &str) -> bool {
+        ctx.map(|ctx| self.is_visible_type(ctx, type_name))
+            .unwrap_or(true)
+    }
+
     fn export_fields<'a, I: Iterator<Item = &'a MetaField>>(
+        &self,
         sdl: &mut String,
         it: I,
         federation: bool,
+        ctx: Option<&VisibleContext<'_>>,
     ) {
         for field in it {
             if field.name.starts_with("__")
@@ -48,6 +130,12 @@ impl Registry {
                 continue;
             }
 
+            if let Some(ctx) = ctx {
+                if !self.is_visible_field(ctx, field) {
+                    continue;
+                }
+            }
+
             if field.description.is_some() && !federation {
                 writeln!(
                     sdl,
@@ -73,6 +161,10 @@ impl Registry {
                 write!(sdl, "\t{}: {}", field.name, field.ty).ok();
             }
 
+            if let Some(deprecation) = field.deprecation {
+                write!(sdl, " {}", export_deprecation(deprecation)).ok();
+            }
+
             if federation {
                 if field.external {
                     write!(sdl, " @external").ok();
@@ -89,10 +181,19 @@ impl Registry {
         }
     }
 
-    fn export_type(&self, ty: &MetaType, sdl: &mut String, federation: bool) {
+    fn export_type(
+        &self,
+        ty: &MetaType,
+        sdl: &mut String,
+        federation: bool,
+        ctx: Option<&VisibleContext<'_>>,
+    ) {
         match ty {
             MetaType::Scalar {
-                name, description, ..
+                name,
+                description,
+                specified_by_url,
+                ..
             } => {
                 const SYSTEM_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
                 const FEDERATION_SCALARS: &[&str] = &["Any"];
@@ -104,7 +205,11 @@ impl Registry {
                     if description.is_some() && !federation {
                         writeln!(sdl, "\"\"\"\n{}\n\"\"\"", description.unwrap()).ok();
                     }
-                    writeln!(sdl, "scalar {}", name).ok();
+                    write!(sdl, "scalar {}", name).ok();
+                    if let Some(url) = specified_by_url {
+                        write!(sdl, " @specifiedBy(url: {})", export_string(url)).ok();
+                    }
+                    writeln!(sdl).ok();
                 }
             }
             MetaType::Object {
@@ -133,11 +238,7 @@ impl Registry {
                     write!(sdl, "extend ").ok();
                 }
                 write!(sdl, "type {} ", name).ok();
-                if let Some(implements) = self.implements.get(name) {
-                    if !implements.is_empty() {
-                        write!(sdl, "implements {} ", implements.iter().join(" & ")).ok();
-                    }
-                }
+                self.export_implements(sdl, name, ctx);
 
                 if federation {
                     if let Some(keys) = keys {
@@ -148,7 +249,7 @@ impl Registry {
                 }
 
                 writeln!(sdl, "{{").ok();
-                Self::export_fields(sdl, fields.values(), federation);
+                self.export_fields(sdl, fields.values(), federation, ctx);
                 writeln!(sdl, "}}").ok();
             }
             MetaType::Interface {
@@ -166,6 +267,7 @@ impl Registry {
                     write!(sdl, "extend ").ok();
                 }
                 write!(sdl, "interface {} ", name).ok();
+                self.export_implements(sdl, name, ctx);
                 if federation {
                     if let Some(keys) = keys {
                         for key in keys {
@@ -174,7 +276,7 @@ impl Registry {
                     }
                 }
                 writeln!(sdl, "{{").ok();
-                Self::export_fields(sdl, fields.values(), federation);
+                self.export_fields(sdl, fields.values(), federation, ctx);
                 writeln!(sdl, "}}").ok();
             }
             MetaType::Enum {
@@ -189,7 +291,18 @@ impl Registry {
                 write!(sdl, "enum {} ", name).ok();
                 writeln!(sdl, "{{").ok();
                 for value in enum_values.values() {
-                    writeln!(sdl, "\t{}", value.name).ok();
+                    if let Some(ctx) = ctx {
+                        if !value.is_visible(ctx) {
+                            continue;
+                        }
+                    }
+                    match value.deprecation {
+                        Some(deprecation) => {
+                            writeln!(sdl, "\t{} {}", value.name, export_deprecation(deprecation))
+                        }
+                        None => writeln!(sdl, "\t{}", value.name),
+                    }
+                    .ok();
                 }
                 writeln!(sdl, "}}").ok();
             }
@@ -225,7 +338,10 @@ impl Registry {
                     sdl,
                     "union {} = {}",
                     name,
-                    possible_types.iter().join(" | ")
+                    possible_types
+                        .iter()
+                        .filter(|ty| self.is_exported_type(ctx, ty))
+                        .join(" | ")
                 )
                 .ok();
             }
@@ -234,12 +350,30 @@ impl Registry {
 }
 
 fn export_input_value(input_value: &MetaInputValue) -> String {
-    if let Some(default_value) = &input_value.default_value {
+    let mut sdl = if let Some(default_value) = &input_value.default_value {
         format!(
             "{}: {} = {}",
             input_value.name, input_value.ty, default_value
         )
     } else {
         format!("{}: {}", input_value.name, input_value.ty)
+    };
+    if let Some(deprecation) = input_value.deprecation {
+        write!(sdl, " {}", export_deprecation(deprecation)).ok();
     }
+    sdl
+}
+
+fn export_deprecation(reason: &str) -> String {
+    format!("@deprecated(reason: {})", export_string(reason))
+}
+
+fn export_string(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
 }
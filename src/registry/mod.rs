@@ -3,7 +3,7 @@ mod export_sdl;
 
 use crate::parser::types::{BaseType as ParsedBaseType, Type as ParsedType};
 use crate::validators::InputValueValidator;
-use crate::{model, Any, Type, Value};
+use crate::{model, Any, Type, Value, VisibleContext};
 use indexmap::map::IndexMap;
 use indexmap::set::IndexSet;
 use std::collections::{HashMap, HashSet};
@@ -83,15 +83,56 @@ impl<'a> MetaTypeName<'a> {
     }
 }
 
+/// A function that decides whether a field, enum value or type is visible to the current request.
+pub type MetaVisibleFn = fn(&VisibleContext<'_>) -> bool;
+
+fn is_visible(ctx: &VisibleContext<'_>, visible: &Option<MetaVisibleFn>) -> bool {
+    match visible {
+        Some(f) => f(ctx),
+        None => true,
+    }
+}
+
 #[derive(Clone)]
 pub struct MetaInputValue {
     pub name: &'static str,
     pub description: Option<&'static str>,
     pub ty: String,
     pub default_value: Option<String>,
+    pub deprecation: Option<&'static str>,
     pub validator: Option<Arc<dyn InputValueValidator>>,
 }
 
+/// Required arguments and input fields must always be provided, so they cannot be deprecated.
+fn check_deprecations(ty: &MetaType) {
+    let check = |owner: &str, value: &MetaInputValue| {
+        if value.deprecation.is_some() && value.ty.ends_with('!') && value.default_value.is_none() {
+            panic!(
+                "\"{}.{}\" is required and cannot be deprecated, make it nullable or give it a default value.",
+                owner, value.name
+            );
+        }
+    };
+
+    match ty {
+        MetaType::Object { name, fields, .. } | MetaType::Interface { name, fields, .. } => {
+            for field in fields.values() {
+                for arg in field.args.values() {
+                    check(&format!("{}.{}", name, field.name), arg);
+                }
+            }
+        }
+        MetaType::InputObject {
+            name, input_fields, ..
+        } => {
+            for field in input_fields.values() {
+                check(name, field);
+            }
+        }
+        _ => {}
+    }
+}
+
 #[derive(Clone)]
 pub struct MetaField {
     pub name: String,
@@ -103,6 +144,13 @@ pub struct MetaField {
     pub external: bool,
     pub requires: Option<&'static str>,
     pub provides: Option<&'static str>,
+    pub visible: Option<MetaVisibleFn>,
+}
+
+impl MetaField {
+    pub fn is_visible(&self, ctx: &VisibleContext<'_>) -> bool {
+        is_visible(ctx, &self.visible)
+    }
 }
 
 #[derive(Clone)]
@@ -110,6 +158,13 @@ pub struct MetaEnumValue {
     pub name: &'static str,
     pub description: Option<&'static str>,
     pub deprecation: Option<&'static str>,
+    pub visible: Option<MetaVisibleFn>,
+}
+
+impl MetaEnumValue {
+    pub fn is_visible(&self, ctx: &VisibleContext<'_>) -> bool {
+        is_visible(ctx, &self.visible)
+    }
 }
 
 pub enum MetaType {
@@ -117,6 +172,7 @@ pub enum MetaType {
         name: String,
         description: Option<&'static str>,
         is_valid: fn(value: &Value) -> bool,
+        specified_by_url: Option<&'static str>,
     },
     Object {
         name: String,
@@ -125,6 +181,7 @@ pub enum MetaType {
         cache_control: CacheControl,
         extends: bool,
         keys: Option<Vec<String>>,
+        visible: Option<MetaVisibleFn>,
     },
     Interface {
         name: String,
@@ -133,21 +190,26 @@ pub enum MetaType {
         possible_types: IndexSet<String>,
         extends: bool,
         keys: Option<Vec<String>>,
+        visible: Option<MetaVisibleFn>,
     },
     Union {
         name: String,
         description: Option<&'static str>,
         possible_types: IndexSet<String>,
+        visible: Option<MetaVisibleFn>,
     },
     Enum {
         name: String,
         description: Option<&'static str>,
         enum_values: IndexMap<&'static str, MetaEnumValue>,
+        visible: Option<MetaVisibleFn>,
     },
     InputObject {
         name: String,
         description: Option<&'static str>,
         input_fields: IndexMap<String, MetaInputValue>,
+        validator: Option<Arc<dyn InputValueValidator>>,
+        visible: Option<MetaVisibleFn>,
     },
 }
 
@@ -175,6 +237,38 @@ impl MetaType {
         }
     }
 
+    pub fn is_visible(&self, ctx: &VisibleContext<'_>) -> bool {
+        match self {
+            MetaType::Scalar { .. } => true,
+            MetaType::Object { visible, .. } => is_visible(ctx, visible),
+            MetaType::Interface { visible, .. } => is_visible(ctx, visible),
+            MetaType::Union { visible, .. } => is_visible(ctx, visible),
+            MetaType::Enum { visible, .. } => is_visible(ctx, visible),
+            MetaType::InputObject { visible, .. } => is_visible(ctx, visible),
+        }
+    }
+
+    /// Returns `true` if the type, one of its fields or one of its enum values has a visibility
+    /// function.
+    fn has_visible_fn(&self) -> bool {
+        match self {
+            MetaType::Scalar { .. } => false,
+            MetaType::Object {
+                visible, fields, ..
+            }
+            | MetaType::Interface {
+                visible, fields, ..
+            } => visible.is_some() || fields.values().any(|field| field.visible.is_some()),
+            MetaType::Union { visible, .. } => visible.is_some(),
+            MetaType::Enum {
+                visible,
+                enum_values,
+                ..
+            } => visible.is_some() || enum_values.values().any(|value| value.visible.is_some()),
+            MetaType::InputObject { visible, .. } => visible.is_some(),
+        }
+    }
+
     pub fn is_composite(&self) -> bool {
         match self {
             MetaType::Object { .. } => true,
@@ -250,15 +344,19 @@ pub struct MetaDirective {
     pub description: Option<&'static str>,
     pub locations: Vec<model::__DirectiveLocation>,
     pub args: IndexMap<&'static str, MetaInputValue>,
+    pub is_repeatable: bool,
 }
 
 pub struct Registry {
+    pub description: Option<String>,
     pub types: IndexMap<String, MetaType>,
     pub directives: HashMap<String, MetaDirective>,
     pub implements: HashMap<String, HashSet<String>>,
     pub query_type: String,
     pub mutation_type: Option<String>,
     pub subscription_type: Option<String>,
+    /// Whether any type, field or enum value has a visibility function.
+    pub has_visible_fn: bool,
 }
 
 impl Registry {
@@ -278,9 +376,12 @@ impl Registry {
                     cache_control: Default::default(),
                     extends: false,
                     keys: None,
+                    visible: None,
                 },
             );
             let ty = f(self);
+            check_deprecations(&ty);
+            self.has_visible_fn |= ty.has_visible_fn();
             *self.types.get_mut(&*name).unwrap() = ty;
         }
         T::qualified_type_name()
@@ -304,6 +405,179 @@ impl Registry {
             });
     }
 
+    /// Replace the interfaces in the possible types of each interface with the object types that
+    /// implement them.
+    ///
+    /// An interface that implements another interface is registered as one of its possible types,
+    /// but the possible types of an interface must be object types, and every type implementing
+    /// the interface must implement the other interface too.
+    pub(crate) fn update_interface_implementations(&mut self) {
+        fn collect_objects(
+            registry: &Registry,
+            interface: &str,
+            objects: &mut IndexSet<String>,
+            interfaces: &mut IndexSet<String>,
+        ) {
+            if let Some(MetaType::Interface { possible_types, .. }) = registry.types.get(interface)
+            {
+                for ty in possible_types {
+                    match registry.types.get(ty) {
+                        Some(MetaType::Interface { .. }) => {
+                            if interfaces.insert(ty.clone()) {
+                                collect_objects(registry, ty, objects, interfaces);
+                            }
+                        }
+                        _ => {
+                            objects.insert(ty.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        let interface_names = self
+            .types
+            .values()
+            .filter_map(|ty| match ty {
+                MetaType::Interface { name, .. } => Some(name.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut updates = Vec::new();
+        for interface in interface_names {
+            let mut objects = IndexSet::new();
+            let mut interfaces = IndexSet::new();
+            collect_objects(self, &interface, &mut objects, &mut interfaces);
+            if !interfaces.is_empty() {
+                updates.push((interface, objects, interfaces));
+            }
+        }
+
+        for (interface, objects, interfaces) in updates {
+            for ty in objects.iter().chain(&interfaces) {
+                self.add_implements(ty, &interface);
+            }
+            if let Some(MetaType::Interface { possible_types, .. }) = self.types.get_mut(&interface)
+            {
+                *possible_types = objects;
+            }
+        }
+    }
+
+    /// Make the object type implement the Relay `Node` interface, which is created if needed.
+    pub fn add_node_type(&mut self, ty: &str) {
+        let node = self
+            .types
+            .entry("Node".to_string())
+            .or_insert_with(|| MetaType::Interface {
+                name: "Node".to_string(),
+                description: Some("An object with a globally unique ID."),
+                fields: {
+                    let mut fields = IndexMap::new();
+                    fields.insert(
+                        "id".to_string(),
+                        MetaField {
+                            name: "id".to_string(),
+                            description: Some("The globally unique ID of the object."),
+                            args: Default::default(),
+                            ty: "ID!".to_string(),
+                            deprecation: None,
+                            cache_control: Default::default(),
+                            external: false,
+                            requires: None,
+                            provides: None,
+                            visible: None,
+                        },
+                    );
+                    fields
+                },
+                possible_types: Default::default(),
+                extends: false,
+                keys: None,
+                visible: None,
+            });
+        if let MetaType::Interface { possible_types, .. } = node {
+            possible_types.insert(ty.to_string());
+        } else {
+            return;
+        }
+        self.add_implements(ty, "Node");
+    }
+
+    /// Add the Relay `node` and `nodes` fields to the query root if any type implements `Node`.
+    pub(crate) fn create_node_fields(&mut self) {
+        if !matches!(self.types.get("Node"), Some(MetaType::Interface { .. })) {
+            return;
+        }
+
+        if let Some(MetaType::Object { fields, .. }) = self.types.get_mut(&self.query_type) {
+            if !fields.contains_key("node") {
+                fields.insert(
+                    "node".to_string(),
+                    MetaField {
+                        name: "node".to_string(),
+                        description: Some("Fetches an object given its ID."),
+                        args: {
+                            let mut args = IndexMap::new();
+                            args.insert(
+                                "id",
+                                MetaInputValue {
+                                    name: "id",
+                                    description: Some("ID of the object."),
+                                    ty: "ID!".to_string(),
+                                    default_value: None,
+                                    deprecation: None,
+                                    validator: None,
+                                },
+                            );
+                            args
+                        },
+                        ty: "Node".to_string(),
+                        deprecation: None,
+                        cache_control: Default::default(),
+                        external: false,
+                        requires: None,
+                        provides: None,
+                        visible: None,
+                    },
+                );
+            }
+
+            if !fields.contains_key("nodes") {
+                fields.insert(
+                    "nodes".to_string(),
+                    MetaField {
+                        name: "nodes".to_string(),
+                        description: Some("Lookup nodes by a list of IDs."),
+                        args: {
+                            let mut args = IndexMap::new();
+                            args.insert(
+                                "ids",
+                                MetaInputValue {
+                                    name: "ids",
+                                    description: Some("The list of node IDs."),
+                                    ty: "[ID!]!".to_string(),
+                                    default_value: None,
+                                    deprecation: None,
+                                    validator: None,
+                                },
+                            );
+                            args
+                        },
+                        ty: "[Node]!".to_string(),
+                        deprecation: None,
+                        cache_control: Default::default(),
+                        external: false,
+                        requires: None,
+                        provides: None,
+                        visible: None,
+                    },
+                );
+            }
+        }
+    }
+
     pub fn add_keys(&mut self, ty: &str, keys: &str) {
         let all_keys = match self.types.get_mut(ty) {
             Some(MetaType::Object { keys: all_keys, .. }) => all_keys,
@@ -328,6 +602,18 @@ impl Registry {
         }
     }
 
+    /// Returns `true` if the named type is visible to the current request.
+    pub fn is_visible_type(&self, ctx: &VisibleContext<'_>, type_name: &str) -> bool {
+        self.concrete_type_by_name(type_name)
+            .map(|ty| ty.is_visible(ctx))
+            .unwrap_or(true)
+    }
+
+    /// Returns `true` if the field and the type it returns are visible to the current request.
+    pub fn is_visible_field(&self, ctx: &VisibleContext<'_>, field: &MetaField) -> bool {
+        field.is_visible(ctx) && self.is_visible_type(ctx, &field.ty)
+    }
+
     pub(crate) fn has_entities(&self) -> bool {
         self.types.values().any(|ty| match ty {
             MetaType::Object {
@@ -365,6 +651,7 @@ impl Registry {
                 name: "_Entity".to_string(),
                 description: None,
                 possible_types,
+                visible: None,
             },
         );
     }
@@ -391,6 +678,7 @@ impl Registry {
                             external: false,
                             requires: None,
                             provides: None,
+                            visible: None,
                         },
                     );
                     fields
@@ -398,6 +686,7 @@ impl Registry {
                 cache_control: Default::default(),
                 extends: false,
                 keys: None,
+                visible: None,
             },
         );
 
@@ -417,6 +706,7 @@ impl Registry {
                     external: false,
                     requires: None,
                     provides: None,
+                    visible: None,
                 },
             );
 
@@ -434,6 +724,7 @@ impl Registry {
                                 description: None,
                                 ty: "[_Any!]!".to_string(),
                                 default_value: None,
+                                deprecation: None,
                                 validator: None,
                             },
                         );
@@ -445,6 +736,7 @@ impl Registry {
                     external: false,
                     requires: None,
                     provides: None,
+                    visible: None,
                 },
             );
         }
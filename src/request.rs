@@ -1,5 +1,5 @@
 use crate::parser::types::UploadValue;
-use crate::{Data, ParseRequestError, Value, Variables};
+use crate::{Data, IntrospectionMode, ParseRequestError, Value, Variables};
 use serde::{Deserialize, Deserializer};
 use std::any::Any;
 use std::collections::HashMap;
@@ -34,6 +34,15 @@ pub struct Request {
     /// The extensions config of the request.
     #[serde(default)]
     pub extensions: HashMap<String, serde_json::Value>,
+
+    /// Overrides the introspection mode of the schema for this request.
+    #[serde(skip)]
+    pub introspection_mode: Option<IntrospectionMode>,
+
+    /// The uploads of the request that are not stored in files.
+    #[cfg(feature = "multipart")]
+    #[serde(skip)]
+    pub(crate) uploads: Vec<crate::UploadContent>,
 }
 
 fn deserialize_variables<'de, D: Deserializer<'de>>(
@@ -51,6 +60,9 @@ impl Request {
             variables: Variables::default(),
             data: Data::default(),
             extensions: Default::default(),
+            introspection_mode: None,
+            #[cfg(feature = "multipart")]
+            uploads: Vec::new(),
         }
     }
 
@@ -73,6 +85,27 @@ impl Request {
         self
     }
 
+    /// Override the introspection mode of the schema for this request.
+    ///
+    /// For example, a server can allow authenticated developers to introspect a schema that has
+    /// introspection disabled.
+    pub fn introspection_mode(self, mode: IntrospectionMode) -> Self {
+        Self {
+            introspection_mode: Some(mode),
+            ..self
+        }
+    }
+
+    /// Disable introspection queries for this request.
+    pub fn disable_introspection(self) -> Self {
+        self.introspection_mode(IntrospectionMode::Disabled)
+    }
+
+    /// Only allow introspection queries for this request.
+    pub fn introspection_only(self) -> Self {
+        self.introspection_mode(IntrospectionMode::IntrospectionOnly)
+    }
+
     /// Set a variable to an upload value.
     ///
     /// `var_path` is a dot-separated path to the item that begins with `variables`, for example
@@ -96,6 +129,30 @@ impl Request {
             content,
         });
     }
+
+    /// Set a variable to an upload that is not stored in a file, which refers to the uploads of
+    /// the request.
+    ///
+    /// The filename is `None` for a file that is streamed from the request body.
+    #[cfg(feature = "multipart")]
+    pub(crate) fn set_upload_content(
+        &mut self,
+        var_path: &str,
+        filename: Option<String>,
+        content_type: Option<String>,
+        content: crate::UploadContent,
+    ) {
+        let variable = match self.variables.variable_path(var_path) {
+            Some(variable) => variable,
+            None => return,
+        };
+        *variable = Value::StoredUpload(crate::parser::types::StoredUploadValue {
+            index: self.uploads.len(),
+            filename,
+            content_type,
+        });
+        self.uploads.push(content);
+    }
 }
 
 impl<T: Into<String>> From<T> for Request {
@@ -1,8 +1,9 @@
 use crate::extensions::{ErrorLogger, Extension, ExtensionContext, ResolveInfo};
-use crate::parser::types::Selection;
+use crate::parser::types::{Field, Selection, SelectionSet};
 use crate::registry::MetaType;
 use crate::{
-    Context, ContextSelectionSet, OutputValueType, PathSegment, ServerError, ServerResult, Value,
+    Context, ContextSelectionSet, GlobalId, OutputValueType, PathSegment, Positioned, ServerError,
+    ServerResult, Value,
 };
 use std::future::Future;
 use std::pin::Pin;
@@ -39,6 +40,19 @@ pub trait ContainerType: OutputValueType {
         fields.add_set(ctx, self)
     }
 
+    /// Check the guard of the container type.
+    ///
+    /// This is called once for every instance of the container before its fields are resolved.
+    /// Objects with a type-level guard override this, and interfaces and unions must call it on
+    /// their internal type.
+    ///
+    /// The root types have no parent field, so their guard is called with the context of the
+    /// first selected root field, except for `__typename` and the fields answered by the schema
+    /// itself.
+    async fn check_guard(&self, _ctx: &Context<'_>) -> ServerResult<()> {
+        Ok(())
+    }
+
     /// Find the GraphQL entity with the given name from the parameter.
     ///
     /// Objects should override this in case they are the query root.
@@ -49,6 +63,17 @@ pub trait ContainerType: OutputValueType {
     ) -> ServerResult<Option<serde_json::Value>> {
         Ok(None)
     }
+
+    /// Find the Relay node with the given global id.
+    ///
+    /// Objects should override this in case they are the query root.
+    async fn find_node(
+        &self,
+        _: &Context<'_>,
+        _id: &GlobalId,
+    ) -> ServerResult<Option<serde_json::Value>> {
+        Ok(None)
+    }
 }
 
 #[async_trait::async_trait]
@@ -56,6 +81,10 @@ impl<T: ContainerType + Send + Sync> ContainerType for &T {
     async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<serde_json::Value>> {
         T::resolve_field(*self, ctx).await
     }
+
+    async fn check_guard(&self, ctx: &Context<'_>) -> ServerResult<()> {
+        T::check_guard(*self, ctx).await
+    }
 }
 
 // TODO: reduce code duplication between the two below functions?
@@ -65,6 +94,8 @@ pub async fn resolve_container<'a, T: ContainerType + Send + Sync>(
     ctx: &ContextSelectionSet<'a>,
     root: &'a T,
 ) -> ServerResult<serde_json::Value> {
+    check_root_guard(ctx, root).await?;
+
     let mut fields = Fields(Vec::new());
     fields.add_set(ctx, root)?;
     let futures = fields.0;
@@ -90,6 +121,8 @@ pub async fn resolve_container_serial<'a, T: ContainerType + Send + Sync>(
     ctx: &ContextSelectionSet<'a>,
     root: &'a T,
 ) -> ServerResult<serde_json::Value> {
+    check_root_guard(ctx, root).await?;
+
     let mut fields = Fields(Vec::new());
     fields.add_set(ctx, root)?;
     let futures = fields.0;
@@ -111,6 +144,66 @@ pub async fn resolve_container_serial<'a, T: ContainerType + Send + Sync>(
     Ok(map.into())
 }
 
+/// Root fields that are answered by the schema itself, so the guard of the root type does not apply
+/// to them.
+const UNGUARDED_ROOT_FIELDS: &[&str] = &["__typename", "__schema", "__type", "_service"];
+
+/// Check the guard of a root type once, before any of its fields are resolved.
+async fn check_root_guard<'a, T: ContainerType + Send + Sync>(
+    ctx: &ContextSelectionSet<'a>,
+    root: &'a T,
+) -> ServerResult<()> {
+    if ctx.path_node.is_some() {
+        return Ok(());
+    }
+
+    if let Some(field) = first_guarded_field(ctx, &ctx.item.node)? {
+        let ctx_field = ctx.with_field(field);
+        let ctx_extension = ExtensionContext {
+            schema_data: &ctx.schema_env.data,
+            query_data: &ctx.query_env.ctx_data,
+            error_mask: ctx.query_env.error_mask(ctx.schema_env),
+        };
+        let field_name = field.node.response_key().node.clone().into_string();
+        root.check_guard(&ctx_field)
+            .await
+            .map_err(|e| e.path(PathSegment::Field(field_name)))
+            .log_error(&ctx_extension, &ctx.query_env.extensions)?;
+    }
+    Ok(())
+}
+
+fn first_guarded_field<'a>(
+    ctx: &ContextSelectionSet<'a>,
+    selection_set: &'a SelectionSet,
+) -> ServerResult<Option<&'a Positioned<Field>>> {
+    for selection in &selection_set.items {
+        if ctx.is_skip(selection.node.directives())? {
+            continue;
+        }
+
+        let selection_set = match &selection.node {
+            Selection::Field(field) => {
+                if UNGUARDED_ROOT_FIELDS.contains(&field.node.name.node.as_str()) {
+                    continue;
+                }
+                return Ok(Some(field));
+            }
+            Selection::FragmentSpread(spread) => {
+                match ctx.query_env.fragments.get(&spread.node.fragment_name.node) {
+                    Some(fragment) => &fragment.node.selection_set,
+                    None => continue,
+                }
+            }
+            Selection::InlineFragment(fragment) => &fragment.node.selection_set,
+        };
+        if let Some(field) = first_guarded_field(ctx, &selection_set.node)? {
+            return Ok(Some(field));
+        }
+    }
+    Ok(None)
+}
+
 type BoxFieldFuture<'a> =
     Pin<Box<dyn Future<Output = ServerResult<(String, serde_json::Value)>> + 'a + Send>>;
 
@@ -174,6 +267,7 @@ impl<'a> Fields<'a> {
                             let ctx_extension = ExtensionContext {
                                 schema_data: &ctx.schema_env.data,
                                 query_data: &ctx.query_env.ctx_data,
+                                error_mask: ctx.query_env.error_mask(ctx.schema_env),
                             };
 
                             let resolve_info = ResolveInfo {
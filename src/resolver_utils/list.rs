@@ -22,6 +22,7 @@ pub async fn resolve_list<'a, T: OutputValueType + Send + Sync + 'a>(
             let ctx_extension = ExtensionContext {
                 schema_data: &ctx.schema_env.data,
                 query_data: &ctx.query_env.ctx_data,
+                error_mask: ctx.query_env.error_mask(ctx.schema_env),
             };
 
             ctx_idx
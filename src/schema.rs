@@ -7,10 +7,10 @@ use crate::registry::{MetaDirective, MetaInputValue, Registry};
 use crate::resolver_utils::{resolve_container, resolve_container_serial, ContainerType};
 use crate::subscription::collect_subscription_streams;
 use crate::types::QueryRoot;
-use crate::validation::{check_rules, CheckResult, ValidationMode};
+use crate::validation::{check_rules, check_visibility, CheckResult, ValidationMode};
 use crate::{
-    BatchRequest, BatchResponse, CacheControl, ContextBase, ObjectType, QueryEnv, Request,
-    Response, ServerError, SubscriptionType, Type, ID,
+    BatchRequest, BatchResponse, CacheControl, ContextBase, ErrorMaskPolicy, ObjectType, QueryEnv,
+    Request, Response, ServerError, SubscriptionType, Type, VisibleContext, ID,
 };
 use futures::stream::{self, Stream, StreamExt};
 use indexmap::map::IndexMap;
@@ -20,9 +20,33 @@ use std::ops::Deref;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 
+/// Which parts of the schema a request can query.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum IntrospectionMode {
+    /// Both introspection and regular queries are allowed.
+    Enabled,
+
+    /// Introspection queries are rejected.
+    Disabled,
+
+    /// Only introspection queries are allowed, for example for an endpoint that schema registries
+    /// pull the schema from.
+    IntrospectionOnly,
+}
+
+impl Default for IntrospectionMode {
+    fn default() -> Self {
+        IntrospectionMode::Enabled
+    }
+}
+
+type IntrospectionPolicyFn = Box<dyn Fn(&Data) -> IntrospectionMode + Send + Sync>;
+
 /// Schema builder
 pub struct SchemaBuilder<Query, Mutation, Subscription> {
     validation_mode: ValidationMode,
+    introspection_mode: IntrospectionMode,
+    introspection_policy: Option<IntrospectionPolicyFn>,
     query: QueryRoot<Query>,
     mutation: Mutation,
     subscription: Subscription,
@@ -30,8 +54,11 @@ pub struct SchemaBuilder<Query, Mutation, Subscription> {
     data: Data,
     complexity: Option<usize>,
     depth: Option<usize>,
+    introspection_depth: Option<usize>,
+    introspection_breadth: Option<usize>,
     extensions: Vec<Box<dyn ExtensionFactory>>,
     enable_federation: bool,
+    mask_errors: Option<ErrorMaskPolicy>,
 }
 
 impl<Query: ContainerType, Mutation: ContainerType, Subscription: SubscriptionType>
@@ -46,8 +73,36 @@ impl<Query: ContainerType, Mutation: ContainerType, Subscription: SubscriptionTy
     }
 
     /// Disable introspection queries.
+    ///
+    /// Requests can override this with [`Request::introspection_mode`](struct.Request.html#method.introspection_mode).
     pub fn disable_introspection(mut self) -> Self {
-        self.query.disable_introspection = true;
+        self.introspection_mode = IntrospectionMode::Disabled;
+        self
+    }
+
+    /// Only allow introspection queries.
+    ///
+    /// Requests can override this with [`Request::introspection_mode`](struct.Request.html#method.introspection_mode).
+    pub fn introspection_only(mut self) -> Self {
+        self.introspection_mode = IntrospectionMode::IntrospectionOnly;
+        self
+    }
+
+    /// Decide the introspection mode of each request from its data, instead of using the same mode
+    /// for all requests.
+    ///
+    /// The mode set on the request itself takes precedence over the policy.
+    pub fn introspection_policy(
+        mut self,
+        f: impl Fn(&Data) -> IntrospectionMode + Send + Sync + 'static,
+    ) -> Self {
+        self.introspection_policy = Some(Box::new(f));
+        self
+    }
+
+    /// Set the description of the schema, which is returned by `__Schema.description`.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.registry.description = Some(description.into());
         self
     }
 
@@ -63,6 +118,24 @@ impl<Query: ContainerType, Mutation: ContainerType, Subscription: SubscriptionTy
         self
     }
 
+    /// Set the maximum number of nested `__Type` fields, such as `ofType` or `type`, an
+    /// introspection query can have. By default there is no limit.
+    ///
+    /// Once an introspection limit is set, introspection fields are no longer counted by
+    /// [`limit_depth`](#method.limit_depth) and [`limit_complexity`](#method.limit_complexity).
+    /// The introspection query used by GraphiQL and GraphQL Playground needs a limit of at least 9.
+    pub fn limit_introspection_depth(mut self, depth: usize) -> Self {
+        self.introspection_depth = Some(depth);
+        self
+    }
+
+    /// Set the maximum number of introspection fields that can be selected on a single object,
+    /// including aliased fields. By default there is no limit.
+    pub fn limit_introspection_breadth(mut self, breadth: usize) -> Self {
+        self.introspection_breadth = Some(breadth);
+        self
+    }
+
     /// Add an extension to the schema.
     ///
     /// # Examples
@@ -94,6 +167,13 @@ impl<Query: ContainerType, Mutation: ContainerType, Subscription: SubscriptionTy
         self
     }
 
+    /// Mask the messages of internal errors with the given policy, so that they are not sent to
+    /// the clients. By default errors are not masked.
+    pub fn mask_errors(mut self, policy: ErrorMaskPolicy) -> Self {
+        self.mask_errors = Some(policy);
+        self
+    }
+
     /// Enable federation, which is automatically enabled if the Query has least one entity definition.
     pub fn enable_federation(mut self) -> Self {
         self.enable_federation = true;
@@ -102,6 +182,10 @@ impl<Query: ContainerType, Mutation: ContainerType, Subscription: SubscriptionTy
 
     /// Build schema.
     pub fn finish(mut self) -> Schema<Query, Mutation, Subscription> {
+        // the types registered with `register_type` can contain more interface implementations
+        self.registry.update_interface_implementations();
+        self.registry.create_node_fields();
+
         // federation
         if self.enable_federation || self.registry.has_entities() {
             self.registry.create_federation_types();
@@ -109,15 +193,20 @@ impl<Query: ContainerType, Mutation: ContainerType, Subscription: SubscriptionTy
 
         Schema(Arc::new(SchemaInner {
             validation_mode: self.validation_mode,
+            introspection_mode: self.introspection_mode,
+            introspection_policy: self.introspection_policy,
             query: self.query,
             mutation: self.mutation,
             subscription: self.subscription,
             complexity: self.complexity,
             depth: self.depth,
+            introspection_depth: self.introspection_depth,
+            introspection_breadth: self.introspection_breadth,
             extensions: self.extensions,
             env: SchemaEnv(Arc::new(SchemaEnvInner {
                 registry: self.registry,
                 data: self.data,
+                mask_errors: self.mask_errors,
             })),
         }))
     }
@@ -127,6 +216,7 @@ impl<Query: ContainerType, Mutation: ContainerType, Subscription: SubscriptionTy
 pub struct SchemaEnvInner {
     pub registry: Registry,
     pub data: Data,
+    pub mask_errors: Option<ErrorMaskPolicy>,
 }
 
 #[doc(hidden)]
@@ -144,11 +234,15 @@ impl Deref for SchemaEnv {
 #[doc(hidden)]
 pub struct SchemaInner<Query, Mutation, Subscription> {
     pub(crate) validation_mode: ValidationMode,
+    pub(crate) introspection_mode: IntrospectionMode,
+    pub(crate) introspection_policy: Option<IntrospectionPolicyFn>,
     pub(crate) query: QueryRoot<Query>,
     pub(crate) mutation: Mutation,
     pub(crate) subscription: Subscription,
     pub(crate) complexity: Option<usize>,
     pub(crate) depth: Option<usize>,
+    pub(crate) introspection_depth: Option<usize>,
+    pub(crate) introspection_breadth: Option<usize>,
     pub(crate) extensions: Vec<Box<dyn ExtensionFactory>>,
     pub(crate) env: SchemaEnv,
 }
@@ -205,23 +299,26 @@ where
     ) -> SchemaBuilder<Query, Mutation, Subscription> {
         SchemaBuilder {
             validation_mode: ValidationMode::Strict,
-            query: QueryRoot {
-                inner: query,
-                disable_introspection: false,
-            },
+            introspection_mode: IntrospectionMode::Enabled,
+            introspection_policy: None,
+            query: QueryRoot { inner: query },
             mutation,
             subscription,
             registry: Self::create_registry(),
             data: Default::default(),
             complexity: None,
             depth: None,
+            introspection_depth: None,
+            introspection_breadth: None,
             extensions: Default::default(),
             enable_federation: false,
+            mask_errors: None,
         }
     }
 
     fn create_registry() -> Registry {
         let mut registry = Registry {
+            description: None,
             types: Default::default(),
             directives: Default::default(),
             implements: Default::default(),
@@ -236,6 +333,7 @@ where
             } else {
                 Some(Subscription::type_name().to_string())
             },
+            has_visible_fn: false,
         };
 
         registry.add_directive(MetaDirective {
@@ -253,10 +351,12 @@ where
                     description: Some("Included when true."),
                     ty: "Boolean!".to_string(),
                     default_value: None,
+                    deprecation: None,
                     validator: None,
                 });
                 args
-            }
+            },
+            is_repeatable: false,
         });
 
         registry.add_directive(MetaDirective {
@@ -274,10 +374,12 @@ where
                     description: Some("Skipped when true."),
                     ty: "Boolean!".to_string(),
                     default_value: None,
+                    deprecation: None,
                     validator: None,
                 });
                 args
-            }
+            },
+            is_repeatable: false,
         });
 
         registry.add_directive(MetaDirective {
@@ -285,6 +387,53 @@ where
             description: Some("Directs the executor to query only when the field exists."),
             locations: vec![__DirectiveLocation::FIELD],
             args: Default::default(),
+            is_repeatable: false,
+        });
+
+        registry.add_directive(MetaDirective {
+            name: "deprecated",
+            description: Some("Marks an element of a GraphQL schema as no longer supported."),
+            locations: vec![
+                __DirectiveLocation::FIELD_DEFINITION,
+                __DirectiveLocation::ARGUMENT_DEFINITION,
+                __DirectiveLocation::INPUT_FIELD_DEFINITION,
+                __DirectiveLocation::ENUM_VALUE
+            ],
+            args: {
+                let mut args = IndexMap::new();
+                args.insert("reason", MetaInputValue {
+                    name: "reason",
+                    description: Some("Explains why this element was deprecated, usually also including a suggestion for how to access supported similar data."),
+                    ty: "String".to_string(),
+                    default_value: Some(r#""No longer supported""#.to_string()),
+                    deprecation: None,
+                    validator: None,
+                });
+                args
+            },
+            is_repeatable: false,
+        });
+
+        registry.add_directive(MetaDirective {
+            name: "specifiedBy",
+            description: Some("Exposes a URL that specifies the behaviour of this scalar."),
+            locations: vec![__DirectiveLocation::SCALAR],
+            args: {
+                let mut args = IndexMap::new();
+                args.insert(
+                    "url",
+                    MetaInputValue {
+                        name: "url",
+                        description: Some("The URL that specifies the behaviour of this scalar."),
+                        ty: "String!".to_string(),
+                        default_value: None,
+                        deprecation: None,
+                        validator: None,
+                    },
+                );
+                args
+            },
+            is_repeatable: false,
         });
 
         // register scalars
@@ -302,6 +451,8 @@ where
             Subscription::create_type_info(&mut registry);
         }
 
+        registry.update_interface_implementations();
+        registry.create_node_fields();
         registry
     }
 
@@ -319,10 +470,19 @@ where
         Self::create_registry().export_sdl(false)
     }
 
+    /// Returns SDL(Schema Definition Language) of this schema, omitting the fields, enum values
+    /// and types that are not visible to a request with the given data.
+    pub fn sdl_with_data(&self, data: Data) -> String {
+        self.env
+            .registry
+            .export_sdl_with_context(false, &VisibleContext::new(&self.env.data, &data))
+    }
+
     async fn prepare_request(
         &self,
         request: Request,
-    ) -> Result<(QueryEnvInner, CacheControl), Vec<ServerError>> {
+        ctx_data: Option<Arc<Data>>,
+    ) -> Result<(QueryEnv, CacheControl), Vec<ServerError>> {
         // create extension instances
         let extensions = spin::Mutex::new(Extensions(
             self.0
@@ -338,6 +498,7 @@ where
                 &ExtensionContext {
                     schema_data: &self.env.data,
                     query_data: &Default::default(),
+                    error_mask: None,
                 },
                 request,
             )
@@ -346,6 +507,7 @@ where
         let ctx_extension = ExtensionContext {
             schema_data: &self.env.data,
             query_data: &request.data,
+            error_mask: None,
         };
 
         extensions
@@ -362,11 +524,15 @@ where
             cache_control,
             complexity,
             depth,
+            introspection_depth,
+            introspection_breadth,
         } = check_rules(
             &self.env.registry,
             &document,
             Some(&request.variables),
             self.validation_mode,
+            self.introspection_depth,
+            self.introspection_breadth,
         )
         .log_error(&ctx_extension, &extensions)?;
         extensions.lock().validation_end(&ctx_extension);
@@ -386,6 +552,22 @@ where
             }
         }
 
+        if let Some(limit_depth) = self.introspection_depth {
+            if introspection_depth > limit_depth {
+                return Err(vec![ServerError::new(
+                    "Introspection query is nested too deep.",
+                )])
+                .log_error(&ctx_extension, &extensions);
+            }
+        }
+
+        if let Some(limit_breadth) = self.introspection_breadth {
+            if introspection_breadth > limit_breadth {
+                return Err(vec![ServerError::new("Introspection query is too broad.")])
+                    .log_error(&ctx_extension, &extensions);
+            }
+        }
+
         let operation = if let Some(operation_name) = &request.operation_name {
             match document.operations {
                 DocumentOperations::Single(_) => None,
@@ -415,13 +597,59 @@ where
             }
         };
 
-        let env = QueryEnvInner {
+        let ctx_data = match ctx_data {
+            Some(ctx_data) => ctx_data,
+            None => Arc::new(request.data),
+        };
+        let introspection_mode = match (request.introspection_mode, &self.introspection_policy) {
+            (Some(mode), _) => mode,
+            (None, Some(policy)) => policy(&ctx_data),
+            (None, None) => self.introspection_mode,
+        };
+        if introspection_mode == IntrospectionMode::IntrospectionOnly
+            && operation.node.ty != OperationType::Query
+        {
+            let ctx_extension = ExtensionContext {
+                schema_data: &self.env.data,
+                query_data: &ctx_data,
+                error_mask: None,
+            };
+            let err = ServerError::new("Only introspection queries are allowed.").at(operation.pos);
+            extensions.lock().error(&ctx_extension, &err);
+            return Err(vec![err]);
+        }
+
+        let env = QueryEnv::new(QueryEnvInner {
             extensions,
             variables: request.variables,
             operation,
             fragments: document.fragments,
-            ctx_data: Arc::new(request.data),
-        };
+            ctx_data,
+            introspection_mode,
+            masked_errors: Default::default(),
+            #[cfg(feature = "multipart")]
+            uploads: request.uploads,
+        });
+
+        // check the visibility of the fields with the context of the request, which can only fail
+        // if the schema has visibility functions
+        if self.env.registry.has_visible_fn {
+            let resolve_id = AtomicUsize::default();
+            let ctx = env.create_context(
+                &self.env,
+                None,
+                &env.operation.node.selection_set,
+                ResolveId::root(),
+                &resolve_id,
+            );
+            let ctx_extension = ExtensionContext {
+                schema_data: &self.env.data,
+                query_data: &env.ctx_data,
+                error_mask: env.error_mask(&self.env),
+            };
+            check_visibility(&ctx).log_error(&ctx_extension, &env.extensions)?;
+        }
+
         Ok((env, cache_control))
     }
 
@@ -439,6 +667,7 @@ where
         let ctx_extension = ExtensionContext {
             schema_data: &self.env.data,
             query_data: &env.ctx_data,
+            error_mask: env.error_mask(&self.env),
         };
 
         env.extensions.lock().execution_start(&ctx_extension);
@@ -456,21 +685,31 @@ where
         env.extensions.lock().execution_end(&ctx_extension);
         let extensions = env.extensions.lock().result(&ctx_extension);
 
-        match data {
+        let response = match data {
             Ok(data) => Response::new(data),
             Err(e) => Response::from_errors(vec![e]),
         }
-        .extensions(extensions)
+        .extensions(extensions);
+        self.mask_errors(&env, response)
+    }
+
+    /// Mask the internal errors of a response, see `ErrorMaskPolicy`.
+    fn mask_errors(&self, env: &QueryEnv, mut response: Response) -> Response {
+        if let Some(error_mask) = env.error_mask(&self.env) {
+            for err in &mut response.errors {
+                if let Some(masked) = error_mask.take(err) {
+                    *err = masked;
+                }
+            }
+        }
+        response
     }
 
     /// Execute an GraphQL query.
     pub async fn execute(&self, request: impl Into<Request>) -> Response {
         let request = request.into();
-        match self.prepare_request(request).await {
-            Ok((env, cache_control)) => self
-                .execute_once(QueryEnv::new(env))
-                .await
-                .cache_control(cache_control),
+        match self.prepare_request(request, None).await {
+            Ok((env, cache_control)) => self.execute_once(env).await.cache_control(cache_control),
             Err(errors) => Response::from_errors(errors),
         }
     }
@@ -488,30 +727,30 @@ where
         }
     }
 
+    /// Execute a subscription, the stream yields `Err` only if the request fails before it is
+    /// executed, e.g. parsing or validation.
     pub(crate) fn execute_stream_with_ctx_data(
         &self,
         request: impl Into<Request> + Send,
         ctx_data: Arc<Data>,
-    ) -> impl Stream<Item = Response> + Send {
+    ) -> impl Stream<Item = Result<Response, Vec<ServerError>>> + Send {
         let schema = self.clone();
 
         async_stream::stream! {
             let request = request.into();
-            let (mut env, cache_control) = match schema.prepare_request(request).await {
+            let (env, cache_control) = match schema.prepare_request(request, Some(ctx_data)).await {
                 Ok(res) => res,
                 Err(errors) => {
-                    yield Response::from_errors(errors);
+                    yield Err(errors);
                     return;
                 }
             };
-            env.ctx_data = ctx_data;
-            let env = QueryEnv::new(env);
 
             if env.operation.node.ty != OperationType::Subscription {
-                yield schema
+                yield Ok(schema
                     .execute_once(env)
                     .await
-                    .cache_control(cache_control);
+                    .cache_control(cache_control));
                 return;
             }
 
@@ -526,6 +765,7 @@ where
             let ctx_extension = ExtensionContext {
                 schema_data: &schema.env.data,
                 query_data: &env.ctx_data,
+                error_mask: env.error_mask(&schema.env),
             };
 
             env.extensions.lock().execution_start(&ctx_extension);
@@ -533,7 +773,7 @@ where
             let mut streams = Vec::new();
             if let Err(e) = collect_subscription_streams(&ctx, &schema.subscription, &mut streams) {
                 env.extensions.lock().execution_end(&ctx_extension);
-                yield Response::from_errors(vec![e]);
+                yield Ok(schema.mask_errors(&env, Response::from_errors(vec![e])));
                 return;
             }
 
@@ -543,7 +783,7 @@ where
             while let Some(data) = stream.next().await {
                 let is_err = data.is_err();
                 let extensions = env.extensions.lock().result(&ctx_extension);
-                yield match data {
+                let response = match data {
                     Ok((name, value)) => Response::new(
                         serde_json::json!({
                             name: value,
@@ -551,6 +791,7 @@ where
                     ),
                     Err(e) => Response::from_errors(vec![e]),
                 }.extensions(extensions);
+                yield Ok(schema.mask_errors(&env, response));
                 if is_err {
                     break;
                 }
@@ -566,5 +807,6 @@ where
         let mut request = request.into();
         let ctx_data = std::mem::take(&mut request.data);
         self.execute_stream_with_ctx_data(request, Arc::new(ctx_data))
+            .map(|res| res.unwrap_or_else(Response::from_errors))
     }
 }
@@ -2,27 +2,42 @@ use crate::connection::edge::Edge;
 use crate::connection::page_info::PageInfo;
 use crate::parser::types::Field;
 use crate::resolver_utils::{resolve_container, ContainerType};
-use crate::types::connection::{CursorType, EmptyFields};
+use crate::types::connection::{
+    ConnectionNameType, CursorType, DefaultConnectionName, DefaultEdgeName, EdgeNameType,
+    EmptyFields, TotalCount,
+};
 use crate::{
     registry, Context, ContextSelectionSet, ObjectType, OutputValueType, Positioned, Result,
-    ServerResult, Type,
+    ServerError, ServerResult, Type,
 };
-use futures::{Stream, StreamExt, TryStreamExt};
+use futures::{Future, Stream, StreamExt, TryStreamExt};
 use indexmap::map::IndexMap;
 use std::borrow::Cow;
+use std::marker::PhantomData;
 
 /// Connection type
 ///
 /// Connection is the result of a query for `connection::query`.
-pub struct Connection<C, T, EC = EmptyFields, EE = EmptyFields> {
+///
+/// The GraphQL names of the connection and edge types are given by `Name` and `EdgeName`, see
+/// `ConnectionNameType` and `EdgeNameType`.
+pub struct Connection<
+    C,
+    T,
+    EC = EmptyFields,
+    EE = EmptyFields,
+    Name = DefaultConnectionName,
+    EdgeName = DefaultEdgeName,
+> {
     /// All edges of the current page.
-    edges: Vec<Edge<C, T, EE>>,
-    additional_fields: EC,
-    has_previous_page: bool,
-    has_next_page: bool,
+    pub(crate) edges: Vec<Edge<C, T, EE, EdgeName>>,
+    pub(crate) additional_fields: EC,
+    pub(crate) has_previous_page: bool,
+    pub(crate) has_next_page: bool,
+    pub(crate) _mark: PhantomData<Name>,
 }
 
-impl<C, T, EE> Connection<C, T, EmptyFields, EE> {
+impl<C, T, EE, Name, EdgeName> Connection<C, T, EmptyFields, EE, Name, EdgeName> {
     /// Create a new connection.
     pub fn new(has_previous_page: bool, has_next_page: bool) -> Self {
         Connection {
@@ -30,11 +45,32 @@ impl<C, T, EE> Connection<C, T, EmptyFields, EE> {
             has_previous_page,
             has_next_page,
             edges: Vec::new(),
+            _mark: PhantomData,
         }
     }
 }
 
-impl<C, T, EC, EE> Connection<C, T, EC, EE> {
+impl<C, T, EC, EE, Name, EdgeName> Connection<C, T, EC, EE, Name, EdgeName> {
+    /// Add a lazily computed `totalCount` field next to the additional fields, see `TotalCount`.
+    pub fn with_total_count<F, R>(
+        self,
+        f: F,
+    ) -> Connection<C, T, TotalCount<EC>, EE, Name, EdgeName>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Future<Output = Result<usize>> + Send + 'static,
+    {
+        Connection {
+            edges: self.edges,
+            additional_fields: TotalCount::new(f).with_fields(self.additional_fields),
+            has_previous_page: self.has_previous_page,
+            has_next_page: self.has_next_page,
+            _mark: PhantomData,
+        }
+    }
+}
+
+impl<C, T, EC, EE, Name, EdgeName> Connection<C, T, EC, EE, Name, EdgeName> {
     /// Create a new connection, it can have some additional fields.
     pub fn with_additional_fields(
         has_previous_page: bool,
@@ -46,15 +82,16 @@ impl<C, T, EC, EE> Connection<C, T, EC, EE> {
             has_previous_page,
             has_next_page,
             edges: Vec::new(),
+            _mark: PhantomData,
         }
     }
 }
 
-impl<C, T, EC, EE> Connection<C, T, EC, EE> {
+impl<C, T, EC, EE, Name, EdgeName> Connection<C, T, EC, EE, Name, EdgeName> {
     /// Convert the edge type and return a new `Connection`.
-    pub fn map<T2, EE2, F>(self, mut f: F) -> Connection<C, T2, EC, EE2>
+    pub fn map<T2, EE2, F>(self, mut f: F) -> Connection<C, T2, EC, EE2, Name, EdgeName>
     where
-        F: FnMut(Edge<C, T, EE>) -> Edge<C, T2, EE2>,
+        F: FnMut(Edge<C, T, EE, EdgeName>) -> Edge<C, T2, EE2, EdgeName>,
     {
         let mut new_edges = Vec::with_capacity(self.edges.len());
         for edge in self.edges {
@@ -65,11 +102,12 @@ impl<C, T, EC, EE> Connection<C, T, EC, EE> {
             additional_fields: self.additional_fields,
             has_previous_page: self.has_previous_page,
             has_next_page: self.has_next_page,
+            _mark: PhantomData,
         }
     }
 
     /// Convert the node type and return a new `Connection`.
-    pub fn map_node<T2, F>(self, mut f: F) -> Connection<C, T2, EC, EE>
+    pub fn map_node<T2, F>(self, mut f: F) -> Connection<C, T2, EC, EE, Name, EdgeName>
     where
         F: FnMut(T) -> T2,
     {
@@ -77,21 +115,42 @@ impl<C, T, EC, EE> Connection<C, T, EC, EE> {
             cursor: edge.cursor,
             node: f(edge.node),
             additional_fields: edge.additional_fields,
+            _mark: PhantomData,
         })
     }
 
-    /// Append edges with `IntoIterator<Item = Edge<C, T, EE>>`
+    /// Rename the connection and edge types.
+    pub fn rename<Name2, EdgeName2>(self) -> Connection<C, T, EC, EE, Name2, EdgeName2> {
+        Connection {
+            edges: self
+                .edges
+                .into_iter()
+                .map(|edge| Edge {
+                    cursor: edge.cursor,
+                    node: edge.node,
+                    additional_fields: edge.additional_fields,
+                    _mark: PhantomData,
+                })
+                .collect(),
+            additional_fields: self.additional_fields,
+            has_previous_page: self.has_previous_page,
+            has_next_page: self.has_next_page,
+            _mark: PhantomData,
+        }
+    }
+
+    /// Append edges with `IntoIterator<Item = Edge<C, T, EE, EdgeName>>`
     pub fn append<I>(&mut self, iter: I)
     where
-        I: IntoIterator<Item = Edge<C, T, EE>>,
+        I: IntoIterator<Item = Edge<C, T, EE, EdgeName>>,
     {
         self.edges.extend(iter);
     }
 
-    /// Append edges with `IntoIterator<Item = Edge<C, T, EE>>`
+    /// Append edges with `IntoIterator<Item = Edge<C, T, EE, EdgeName>>`
     pub fn try_append<I>(&mut self, iter: I) -> Result<()>
     where
-        I: IntoIterator<Item = Result<Edge<C, T, EE>>>,
+        I: IntoIterator<Item = Result<Edge<C, T, EE, EdgeName>>>,
     {
         for edge in iter {
             self.edges.push(edge?);
@@ -99,33 +158,35 @@ impl<C, T, EC, EE> Connection<C, T, EC, EE> {
         Ok(())
     }
 
-    /// Append edges with `Stream<Item = Result<Edge<C, T, EE>>>`
+    /// Append edges with `Stream<Item = Result<Edge<C, T, EE, EdgeName>>>`
     pub async fn append_stream<S>(&mut self, stream: S)
     where
-        S: Stream<Item = Edge<C, T, EE>> + Unpin,
+        S: Stream<Item = Edge<C, T, EE, EdgeName>> + Unpin,
     {
         self.edges.extend(stream.collect::<Vec<_>>().await);
     }
 
-    /// Append edges with `Stream<Item = Result<Edge<C, T, EE>>>`
+    /// Append edges with `Stream<Item = Result<Edge<C, T, EE, EdgeName>>>`
     pub async fn try_append_stream<S>(&mut self, stream: S) -> Result<()>
     where
-        S: Stream<Item = Result<Edge<C, T, EE>>> + Unpin,
+        S: Stream<Item = Result<Edge<C, T, EE, EdgeName>>> + Unpin,
     {
         self.edges.extend(stream.try_collect::<Vec<_>>().await?);
         Ok(())
     }
 }
 
-impl<C, T, EC, EE> Type for Connection<C, T, EC, EE>
+impl<C, T, EC, EE, Name, EdgeName> Type for Connection<C, T, EC, EE, Name, EdgeName>
 where
     C: CursorType,
     T: OutputValueType + Send + Sync,
     EC: ObjectType + Sync + Send,
     EE: ObjectType + Sync + Send,
+    Name: ConnectionNameType,
+    EdgeName: EdgeNameType,
 {
     fn type_name() -> Cow<'static, str> {
-        Cow::Owned(format!("{}Connection", T::type_name()))
+        Cow::Owned(Name::type_name::<T>())
     }
 
     fn create_type_info(registry: &mut registry::Registry) -> String {
@@ -157,6 +218,7 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            visible: None,
                         },
                     );
 
@@ -166,7 +228,7 @@ where
                             name: "edges".to_string(),
                             description: Some("A list of edges."),
                             args: Default::default(),
-                            ty: <Option<Vec<Option<Edge<C, T, EE>>>> as Type>::create_type_info(
+                            ty: <Option<Vec<Option<Edge<C, T, EE, EdgeName>>>> as Type>::create_type_info(
                                 registry,
                             ),
                             deprecation: None,
@@ -174,6 +236,7 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            visible: None,
                         },
                     );
 
@@ -183,26 +246,34 @@ where
                 cache_control: Default::default(),
                 extends: false,
                 keys: None,
+                visible: None,
             }
         })
     }
 }
 
 #[async_trait::async_trait]
-impl<C, T, EC, EE> ContainerType for Connection<C, T, EC, EE>
+impl<C, T, EC, EE, Name, EdgeName> ContainerType for Connection<C, T, EC, EE, Name, EdgeName>
 where
     C: CursorType + Send + Sync,
     T: OutputValueType + Send + Sync,
     EC: ObjectType + Sync + Send,
     EE: ObjectType + Sync + Send,
+    Name: ConnectionNameType,
+    EdgeName: EdgeNameType,
 {
     async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<serde_json::Value>> {
         if ctx.item.node.name.node == "pageInfo" {
+            let encode_cursor = |edge: &Edge<C, T, EE, EdgeName>| {
+                edge.cursor
+                    .encode_cursor_with_context(ctx)
+                    .map_err(|err| ServerError::new(err.to_string()).at(ctx.item.pos))
+            };
             let page_info = PageInfo {
                 has_previous_page: self.has_previous_page,
                 has_next_page: self.has_next_page,
-                start_cursor: self.edges.first().map(|edge| edge.cursor.encode_cursor()),
-                end_cursor: self.edges.last().map(|edge| edge.cursor.encode_cursor()),
+                start_cursor: self.edges.first().map(encode_cursor).transpose()?,
+                end_cursor: self.edges.last().map(encode_cursor).transpose()?,
             };
             let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
             return OutputValueType::resolve(&page_info, &ctx_obj, ctx.item)
@@ -220,12 +291,14 @@ where
 }
 
 #[async_trait::async_trait]
-impl<C, T, EC, EE> OutputValueType for Connection<C, T, EC, EE>
+impl<C, T, EC, EE, Name, EdgeName> OutputValueType for Connection<C, T, EC, EE, Name, EdgeName>
 where
     C: CursorType + Send + Sync,
     T: OutputValueType + Send + Sync,
     EC: ObjectType + Sync + Send,
     EE: ObjectType + Sync + Send,
+    Name: ConnectionNameType,
+    EdgeName: EdgeNameType,
 {
     async fn resolve(
         &self,
@@ -236,11 +309,13 @@ where
     }
 }
 
-impl<C, T, EC, EE> ObjectType for Connection<C, T, EC, EE>
+impl<C, T, EC, EE, Name, EdgeName> ObjectType for Connection<C, T, EC, EE, Name, EdgeName>
 where
     C: CursorType + Send + Sync,
     T: OutputValueType + Send + Sync,
     EC: ObjectType + Sync + Send,
     EE: ObjectType + Sync + Send,
+    Name: ConnectionNameType,
+    EdgeName: EdgeNameType,
 {
 }
@@ -1,4 +1,4 @@
-use crate::ID;
+use crate::{Context, ID};
 use std::convert::Infallible;
 use std::fmt::Display;
 use std::num::ParseIntError;
@@ -16,6 +16,22 @@ pub trait CursorType: Sized {
 
     /// Encode cursor to string.
     fn encode_cursor(&self) -> String;
+
+    /// Decode cursor from string with access to the context of the connection field.
+    ///
+    /// Cursors that need data from the context, such as a signing key, override this. It is called
+    /// by `connection::query_with_context` and defaults to `decode_cursor`.
+    fn decode_cursor_with_context(_ctx: &Context<'_>, s: &str) -> Result<Self, Self::Error> {
+        Self::decode_cursor(s)
+    }
+
+    /// Encode cursor to string with access to the context of the cursor field.
+    ///
+    /// Connections and edges always encode their cursors with this, which defaults to
+    /// `encode_cursor`.
+    fn encode_cursor_with_context(&self, _ctx: &Context<'_>) -> Result<String, Self::Error> {
+        Ok(self.encode_cursor())
+    }
 }
 
 impl CursorType for usize {
@@ -1,51 +1,58 @@
-use crate::connection::EmptyFields;
+use crate::connection::{DefaultEdgeName, EdgeNameType, EmptyFields};
 use crate::parser::types::Field;
 use crate::resolver_utils::{resolve_container, ContainerType};
 use crate::types::connection::CursorType;
 use crate::{
-    registry, Context, ContextSelectionSet, ObjectType, OutputValueType, Positioned, ServerResult,
-    Type,
+    registry, Context, ContextSelectionSet, ObjectType, OutputValueType, Positioned, ServerError,
+    ServerResult, Type,
 };
 use indexmap::map::IndexMap;
 use std::borrow::Cow;
+use std::marker::PhantomData;
 
 /// The edge type output by the data source
-pub struct Edge<C, T, E> {
+///
+/// The GraphQL name of the edge type is given by `Name`, see `EdgeNameType`.
+pub struct Edge<C, T, E, Name = DefaultEdgeName> {
     pub(crate) cursor: C,
     pub(crate) node: T,
     pub(crate) additional_fields: E,
+    pub(crate) _mark: PhantomData<Name>,
 }
 
-impl<C, T, E> Edge<C, T, E> {
+impl<C, T, E, Name> Edge<C, T, E, Name> {
     /// Create a new edge, it can have some additional fields.
     pub fn with_additional_fields(cursor: C, node: T, additional_fields: E) -> Self {
         Self {
             cursor,
             additional_fields,
             node,
+            _mark: PhantomData,
         }
     }
 }
 
-impl<C: CursorType, T> Edge<C, T, EmptyFields> {
+impl<C: CursorType, T, Name> Edge<C, T, EmptyFields, Name> {
     /// Create a new edge.
     pub fn new(cursor: C, node: T) -> Self {
         Self {
             cursor,
             node,
             additional_fields: EmptyFields,
+            _mark: PhantomData,
         }
     }
 }
 
-impl<C, T, E> Type for Edge<C, T, E>
+impl<C, T, E, Name> Type for Edge<C, T, E, Name>
 where
     C: CursorType,
     T: OutputValueType + Send + Sync,
     E: ObjectType + Sync + Send,
+    Name: EdgeNameType,
 {
     fn type_name() -> Cow<'static, str> {
-        Cow::Owned(format!("{}Edge", T::type_name()))
+        Cow::Owned(Name::type_name::<T>())
     }
 
     fn create_type_info(registry: &mut registry::Registry) -> String {
@@ -77,6 +84,7 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            visible: None,
                         },
                     );
 
@@ -92,6 +100,7 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            visible: None,
                         },
                     );
 
@@ -101,17 +110,19 @@ where
                 cache_control: Default::default(),
                 extends: false,
                 keys: None,
+                visible: None,
             }
         })
     }
 }
 
 #[async_trait::async_trait]
-impl<C, T, E> ContainerType for Edge<C, T, E>
+impl<C, T, E, Name> ContainerType for Edge<C, T, E, Name>
 where
     C: CursorType + Send + Sync,
     T: OutputValueType + Send + Sync,
     E: ObjectType + Sync + Send,
+    Name: EdgeNameType,
 {
     async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<serde_json::Value>> {
         if ctx.item.node.name.node == "node" {
@@ -120,7 +131,11 @@ where
                 .await
                 .map(Some);
         } else if ctx.item.node.name.node == "cursor" {
-            return Ok(Some(self.cursor.encode_cursor().into()));
+            let cursor = self
+                .cursor
+                .encode_cursor_with_context(ctx)
+                .map_err(|err| ServerError::new(err.to_string()).at(ctx.item.pos))?;
+            return Ok(Some(cursor.into()));
         }
 
         self.additional_fields.resolve_field(ctx).await
@@ -128,11 +143,12 @@ where
 }
 
 #[async_trait::async_trait]
-impl<C, T, E> OutputValueType for Edge<C, T, E>
+impl<C, T, E, Name> OutputValueType for Edge<C, T, E, Name>
 where
     C: CursorType + Send + Sync,
     T: OutputValueType + Send + Sync,
     E: ObjectType + Sync + Send,
+    Name: EdgeNameType,
 {
     async fn resolve(
         &self,
@@ -143,10 +159,11 @@ where
     }
 }
 
-impl<C, T, E> ObjectType for Edge<C, T, E>
+impl<C, T, E, Name> ObjectType for Edge<C, T, E, Name>
 where
     C: CursorType + Send + Sync,
     T: OutputValueType + Send + Sync,
     E: ObjectType + Sync + Send,
+    Name: EdgeNameType,
 {
 }
@@ -3,15 +3,25 @@
 mod connection_type;
 mod cursor;
 mod edge;
+mod name;
+mod opaque_cursor;
 mod page_info;
+mod paginate;
+mod total_count;
 
-use crate::{Result, SimpleObject};
+use crate::{Context, Error, Result, SimpleObject};
 pub use connection_type::Connection;
 pub use cursor::CursorType;
 pub use edge::Edge;
 use futures::Future;
+pub use name::{ConnectionNameType, DefaultConnectionName, DefaultEdgeName, EdgeNameType};
+pub use opaque_cursor::{CursorError, OpaqueCursor};
+#[cfg(feature = "signed_cursor")]
+pub use opaque_cursor::{CursorSigningKey, SignedCursor};
 pub use page_info::PageInfo;
+use std::any::Any;
 use std::fmt::Display;
+pub use total_count::TotalCount;
 
 /// Empty additional fields
 #[derive(SimpleObject)]
@@ -20,6 +30,9 @@ pub struct EmptyFields;
 
 /// Parses the parameters and executes the query.
 ///
+/// `Connection::from_slice`, `Connection::from_stream` and `Connection::from_sorted_keyset` create
+/// the page from the parsed parameters for common data sources.
+///
 /// # Examples
 ///
 /// ```rust
@@ -89,26 +102,77 @@ pub struct EmptyFields;
 ///     }));
 /// }
 /// ```
-pub async fn query<Cursor, Node, ConnectionFields, EdgeFields, F, R>(
+pub async fn query<Cursor, Node, ConnectionFields, EdgeFields, Name, EdgeName, F, R>(
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+    f: F,
+) -> Result<Connection<Cursor, Node, ConnectionFields, EdgeFields, Name, EdgeName>>
+where
+    Cursor: CursorType + Send + Sync,
+    <Cursor as CursorType>::Error: Display + Send + Sync + 'static,
+    F: FnOnce(Option<Cursor>, Option<Cursor>, Option<usize>, Option<usize>) -> R,
+    R: Future<
+        Output = Result<Connection<Cursor, Node, ConnectionFields, EdgeFields, Name, EdgeName>>,
+    >,
+{
+    let (after, before, first, last) =
+        parse_args(after, before, first, last, Cursor::decode_cursor)?;
+    f(after, before, first, last).await
+}
+
+/// Parses the parameters with access to the context and executes the query.
+///
+/// This is the same as `query`, except that the cursors are decoded with
+/// `CursorType::decode_cursor_with_context`, which cursors like `SignedCursor` need.
+pub async fn query_with_context<Cursor, Node, ConnectionFields, EdgeFields, Name, EdgeName, F, R>(
+    ctx: &Context<'_>,
     after: Option<String>,
     before: Option<String>,
     first: Option<i32>,
     last: Option<i32>,
     f: F,
-) -> Result<Connection<Cursor, Node, ConnectionFields, EdgeFields>>
+) -> Result<Connection<Cursor, Node, ConnectionFields, EdgeFields, Name, EdgeName>>
 where
     Cursor: CursorType + Send + Sync,
     <Cursor as CursorType>::Error: Display + Send + Sync + 'static,
     F: FnOnce(Option<Cursor>, Option<Cursor>, Option<usize>, Option<usize>) -> R,
-    R: Future<Output = Result<Connection<Cursor, Node, ConnectionFields, EdgeFields>>>,
+    R: Future<
+        Output = Result<Connection<Cursor, Node, ConnectionFields, EdgeFields, Name, EdgeName>>,
+    >,
+{
+    let (after, before, first, last) = parse_args(after, before, first, last, |s| {
+        Cursor::decode_cursor_with_context(ctx, s)
+    })?;
+    f(after, before, first, last).await
+}
+
+type Args<Cursor> = (Option<Cursor>, Option<Cursor>, Option<usize>, Option<usize>);
+
+fn parse_args<Cursor, D>(
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+    decode_cursor: D,
+) -> Result<Args<Cursor>>
+where
+    Cursor: CursorType,
+    <Cursor as CursorType>::Error: Display + Send + Sync + 'static,
+    D: Fn(&str) -> std::result::Result<Cursor, Cursor::Error>,
 {
     if first.is_some() && last.is_some() {
-        return Err("The \"first\" and \"last\" parameters cannot exist at the same time".into());
+        return Err(Error::new(
+            "The \"first\" and \"last\" parameters cannot exist at the same time",
+        ));
     }
 
     let first = match first {
         Some(first) if first < 0 => {
-            return Err("The \"first\" parameter must be a non-negative number".into());
+            return Err(Error::new(
+                "The \"first\" parameter must be a non-negative number",
+            ));
         }
         Some(first) => Some(first as usize),
         None => None,
@@ -116,21 +180,32 @@ where
 
     let last = match last {
         Some(last) if last < 0 => {
-            return Err("The \"last\" parameter must be a non-negative number".into());
+            return Err(Error::new(
+                "The \"last\" parameter must be a non-negative number",
+            ));
         }
         Some(last) => Some(last as usize),
         None => None,
     };
 
     let before = match before {
-        Some(before) => Some(Cursor::decode_cursor(&before)?),
+        Some(before) => Some(decode_cursor(&before).map_err(cursor_error)?),
         None => None,
     };
 
     let after = match after {
-        Some(after) => Some(Cursor::decode_cursor(&after)?),
+        Some(after) => Some(decode_cursor(&after).map_err(cursor_error)?),
         None => None,
     };
 
-    f(after, before, first, last).await
+    Ok((after, before, first, last))
+}
+
+/// A cursor that cannot be decoded was sent by the client, so the error is user-facing, unless the
+/// schema is missing what `SignedCursor` needs to decode it.
+fn cursor_error<E: Display + Send + Sync + 'static>(err: E) -> Error {
+    match (&err as &dyn Any).downcast_ref::<CursorError>() {
+        Some(CursorError::MissingSigningKey) | Some(CursorError::ContextRequired) => err.into(),
+        _ => Error::new(err.to_string()),
+    }
 }
@@ -0,0 +1,66 @@
+use crate::OutputValueType;
+
+/// The GraphQL name of a connection type.
+///
+/// Implement this on a marker type and use it as the `Name` parameter of `Connection` to rename the
+/// connection type, for example to give two connections of the same node type different names.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::connection::*;
+///
+/// struct FriendsConnectionName;
+///
+/// impl ConnectionNameType for FriendsConnectionName {
+///     fn type_name<T: OutputValueType>() -> String {
+///         "FriendsConnection".to_string()
+///     }
+/// }
+///
+/// struct FriendEdgeName;
+///
+/// impl EdgeNameType for FriendEdgeName {
+///     fn type_name<T: OutputValueType>() -> String {
+///         "FriendEdge".to_string()
+///     }
+/// }
+///
+/// type FriendsConnection =
+///     Connection<usize, String, EmptyFields, EmptyFields, FriendsConnectionName, FriendEdgeName>;
+///
+/// assert_eq!(<FriendsConnection as Type>::type_name(), "FriendsConnection");
+/// assert_eq!(<Connection<usize, String> as Type>::type_name(), "StringConnection");
+/// ```
+pub trait ConnectionNameType: Send + Sync {
+    /// Returns the name of the connection type of the node type `T`.
+    fn type_name<T: OutputValueType>() -> String;
+}
+
+/// The GraphQL name of an edge type.
+///
+/// Implement this on a marker type and use it as the `EdgeName` parameter of `Connection` to
+/// rename the edge type.
+pub trait EdgeNameType: Send + Sync {
+    /// Returns the name of the edge type of the node type `T`.
+    fn type_name<T: OutputValueType>() -> String;
+}
+
+/// The default connection name, `{NodeType}Connection`.
+pub struct DefaultConnectionName;
+
+impl ConnectionNameType for DefaultConnectionName {
+    fn type_name<T: OutputValueType>() -> String {
+        format!("{}Connection", T::type_name())
+    }
+}
+
+/// The default edge name, `{NodeType}Edge`.
+pub struct DefaultEdgeName;
+
+impl EdgeNameType for DefaultEdgeName {
+    fn type_name<T: OutputValueType>() -> String {
+        format!("{}Edge", T::type_name())
+    }
+}
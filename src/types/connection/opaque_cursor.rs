@@ -0,0 +1,224 @@
+use crate::connection::CursorType;
+#[cfg(feature = "signed_cursor")]
+use crate::Context;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::ops::{Deref, DerefMut};
+
+/// Error for decoding and encoding opaque and signed cursors.
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+pub enum CursorError {
+    /// The cursor is not a valid encoded value.
+    #[error("Invalid cursor.")]
+    Invalid,
+
+    /// The signature of the cursor does not match its value.
+    #[error("Invalid cursor signature.")]
+    InvalidSignature,
+
+    /// There is no `CursorSigningKey` in the schema data.
+    #[error("Missing cursor signing key.")]
+    MissingSigningKey,
+
+    /// The cursor was decoded without the context, so its signature cannot be checked.
+    #[error("Signed cursors must be decoded with `connection::query_with_context`.")]
+    ContextRequired,
+}
+
+fn encode_value<T: Serialize>(value: &T) -> String {
+    base64::encode_config(
+        serde_json::to_vec(value).unwrap_or_default(),
+        base64::URL_SAFE_NO_PAD,
+    )
+}
+
+fn decode_value<T: DeserializeOwned>(s: &str) -> Result<T, CursorError> {
+    let data =
+        base64::decode_config(s, base64::URL_SAFE_NO_PAD).map_err(|_| CursorError::Invalid)?;
+    serde_json::from_slice(&data).map_err(|_| CursorError::Invalid)
+}
+
+/// Opaque cursor
+///
+/// A cursor of any serializable key, encoded as base64 so that clients do not rely on its format.
+/// It is not protected against tampering, use `SignedCursor` for that.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::connection::*;
+///
+/// let cursor = OpaqueCursor((10, "abc".to_string()));
+/// let s = cursor.encode_cursor();
+/// assert_eq!(s, "WzEwLCJhYmMiXQ");
+/// assert_eq!(OpaqueCursor::<(i32, String)>::decode_cursor(&s), Ok(cursor));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct OpaqueCursor<T>(pub T);
+
+impl<T> Deref for OpaqueCursor<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for OpaqueCursor<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> CursorType for OpaqueCursor<T> {
+    type Error = CursorError;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        decode_value(s).map(OpaqueCursor)
+    }
+
+    fn encode_cursor(&self) -> String {
+        encode_value(&self.0)
+    }
+}
+
+/// The key for signing `SignedCursor`, which must be put in the schema data.
+#[cfg(feature = "signed_cursor")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "signed_cursor")))]
+pub struct CursorSigningKey(Vec<u8>);
+
+#[cfg(feature = "signed_cursor")]
+impl CursorSigningKey {
+    /// Create a signing key from a secret.
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self(secret.into())
+    }
+
+    fn mac(&self, payload: &str) -> hmac::Hmac<sha2::Sha256> {
+        use hmac::{Mac, NewMac};
+
+        let mut mac = hmac::Hmac::<sha2::Sha256>::new_varkey(&self.0)
+            .expect("HMAC can take a key of any size");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+/// Signed cursor
+///
+/// An `OpaqueCursor` with an HMAC-SHA256 signature, made with the `CursorSigningKey` in the schema
+/// data. Cursors that were modified by the client are rejected with an error.
+///
+/// The signing key is only available with the context, so the cursors must be decoded with
+/// `connection::query_with_context`. Without the context, `encode_cursor` returns the unsigned
+/// cursor and `decode_cursor` always fails with `CursorError::ContextRequired`.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::connection::*;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn numbers(&self,
+///         ctx: &Context<'_>,
+///         after: Option<String>,
+///         before: Option<String>,
+///         first: Option<i32>,
+///         last: Option<i32>
+///     ) -> Result<Connection<SignedCursor<i32>, i32>> {
+///         query_with_context(ctx, after, before, first, last, |after, before, first, last| async move {
+///             Ok(Connection::from_sorted_keyset(0..10, |n| SignedCursor(*n), after, before, first, last))
+///         }).await
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///         .data(CursorSigningKey::new("secret"))
+///         .finish();
+///     let res = schema.execute("{ numbers(first: 1) { pageInfo { endCursor } } }").await;
+///     let cursor = res.data["numbers"]["pageInfo"]["endCursor"].as_str().unwrap().to_string();
+///
+///     let query = format!(r#"{{ numbers(after: "{}", first: 1) {{ edges {{ node }} }} }}"#, cursor);
+///     assert_eq!(schema.execute(&query).await.into_result().unwrap().data, serde_json::json!({
+///         "numbers": { "edges": [{ "node": 1 }] },
+///     }));
+///
+///     let query = format!(r#"{{ numbers(after: "{}", first: 1) {{ edges {{ node }} }} }}"#, cursor.replacen("MA", "OQ", 1));
+///     assert_eq!(
+///         schema.execute(&query).await.into_result().unwrap_err()[0].message,
+///         "Invalid cursor signature.",
+///     );
+/// });
+/// ```
+#[cfg(feature = "signed_cursor")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "signed_cursor")))]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct SignedCursor<T>(pub T);
+
+#[cfg(feature = "signed_cursor")]
+impl<T> Deref for SignedCursor<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "signed_cursor")]
+impl<T> DerefMut for SignedCursor<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "signed_cursor")]
+impl<T: Serialize + DeserializeOwned> CursorType for SignedCursor<T> {
+    type Error = CursorError;
+
+    fn decode_cursor(_s: &str) -> Result<Self, Self::Error> {
+        Err(CursorError::ContextRequired)
+    }
+
+    fn encode_cursor(&self) -> String {
+        encode_value(&self.0)
+    }
+
+    fn decode_cursor_with_context(ctx: &Context<'_>, s: &str) -> Result<Self, Self::Error> {
+        use hmac::Mac;
+
+        let key = ctx
+            .data_opt::<CursorSigningKey>()
+            .ok_or(CursorError::MissingSigningKey)?;
+        let mut parts = s.splitn(2, '.');
+        let (payload, signature) = match (parts.next(), parts.next()) {
+            (Some(payload), Some(signature)) => (payload, signature),
+            _ => return Err(CursorError::Invalid),
+        };
+        let signature = base64::decode_config(signature, base64::URL_SAFE_NO_PAD)
+            .map_err(|_| CursorError::Invalid)?;
+        key.mac(payload)
+            .verify(&signature)
+            .map_err(|_| CursorError::InvalidSignature)?;
+        decode_value(payload).map(SignedCursor)
+    }
+
+    fn encode_cursor_with_context(&self, ctx: &Context<'_>) -> Result<String, Self::Error> {
+        use hmac::Mac;
+
+        let key = ctx
+            .data_opt::<CursorSigningKey>()
+            .ok_or(CursorError::MissingSigningKey)?;
+        let payload = encode_value(&self.0);
+        let signature = key.mac(&payload).finalize().into_bytes();
+        Ok(format!(
+            "{}.{}",
+            payload,
+            base64::encode_config(signature, base64::URL_SAFE_NO_PAD)
+        ))
+    }
+}
@@ -0,0 +1,483 @@
+use crate::connection::{Connection, CursorType, Edge, EmptyFields};
+use crate::Result;
+use futures::{Stream, StreamExt, TryStreamExt};
+use std::collections::VecDeque;
+
+/// Applies the pagination arguments to a sorted sequence of edges, following the
+/// [Relay pagination algorithm](https://relay.dev/graphql/connections.htm#sec-Pagination-algorithm).
+///
+/// Edges with a cursor less than or equal to `after`, or greater than or equal to `before`, are
+/// removed, so a cursor is also meaningful when its edge no longer exists. The edges are consumed
+/// only as far as needed.
+struct Paginator<C, T> {
+    after: Option<C>,
+    before: Option<C>,
+    first: Option<usize>,
+    last: Option<usize>,
+    edges: VecDeque<Edge<C, T, EmptyFields>>,
+    count: usize,
+    has_prior: bool,
+    has_following: bool,
+}
+
+impl<C: CursorType + Ord, T> Paginator<C, T> {
+    fn new(after: Option<C>, before: Option<C>, first: Option<usize>, last: Option<usize>) -> Self {
+        Self {
+            after,
+            before,
+            first,
+            last,
+            edges: VecDeque::new(),
+            count: 0,
+            has_prior: false,
+            has_following: false,
+        }
+    }
+
+    /// Adds the next edge, and returns `false` if no more edges are needed.
+    fn push(&mut self, cursor: C, node: T) -> bool {
+        if matches!(&self.after, Some(after) if cursor <= *after) {
+            self.has_prior = true;
+            return true;
+        }
+        if matches!(&self.before, Some(before) if cursor >= *before) {
+            self.has_following = true;
+            return false;
+        }
+
+        self.count += 1;
+        if let Some(first) = self.first {
+            if self.count > first {
+                // `hasPreviousPage` needs the number of all edges between the cursors if `last`
+                // is set.
+                return self.last.is_some();
+            }
+        }
+
+        self.edges.push_back(Edge::new(cursor, node));
+        if let Some(last) = self.last {
+            if self.edges.len() > last {
+                self.edges.pop_front();
+            }
+        }
+        true
+    }
+
+    fn finish<Name, EdgeName>(self) -> Connection<C, T, EmptyFields, EmptyFields, Name, EdgeName> {
+        let has_previous_page = match self.last {
+            Some(last) => self.count > last,
+            None => self.after.is_some() && self.has_prior,
+        };
+        let has_next_page = match self.first {
+            Some(first) => self.count > first,
+            None => self.before.is_some() && self.has_following,
+        };
+        let mut connection = Connection::<C, T>::new(has_previous_page, has_next_page);
+        connection.append(self.edges);
+        connection.rename()
+    }
+}
+
+impl<T, Name, EdgeName> Connection<usize, T, EmptyFields, EmptyFields, Name, EdgeName> {
+    /// Create a connection from a page of a slice, using the indices of the items as cursors.
+    ///
+    /// The parameters are the ones `connection::query` passes to its closure.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    /// use async_graphql::connection::*;
+    ///
+    /// struct QueryRoot;
+    ///
+    /// #[Object]
+    /// impl QueryRoot {
+    ///     async fn numbers(&self,
+    ///         after: Option<String>,
+    ///         before: Option<String>,
+    ///         first: Option<i32>,
+    ///         last: Option<i32>
+    ///     ) -> Result<Connection<usize, i32>> {
+    ///         let numbers = (0..100).collect::<Vec<i32>>();
+    ///         query(after, before, first, last, |after, before, first, last| async move {
+    ///             Ok(Connection::from_slice(&numbers, after, before, first, last))
+    ///         }).await
+    ///     }
+    /// }
+    ///
+    /// async_std::task::block_on(async move {
+    ///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    ///     let res = schema.execute(r#"{
+    ///         numbers(after: "9", first: 2) {
+    ///             edges { cursor node }
+    ///             pageInfo { hasPreviousPage hasNextPage }
+    ///         }
+    ///     }"#).await.into_result().unwrap().data;
+    ///     assert_eq!(res, serde_json::json!({
+    ///         "numbers": {
+    ///             "edges": [
+    ///                 { "cursor": "10", "node": 10 },
+    ///                 { "cursor": "11", "node": 11 },
+    ///             ],
+    ///             "pageInfo": { "hasPreviousPage": true, "hasNextPage": true },
+    ///         },
+    ///     }));
+    /// });
+    /// ```
+    pub fn from_slice(
+        items: &[T],
+        after: Option<usize>,
+        before: Option<usize>,
+        first: Option<usize>,
+        last: Option<usize>,
+    ) -> Self
+    where
+        T: Clone,
+    {
+        // Skip the items before the cursors instead of visiting them.
+        let start = match after {
+            Some(after) => after.saturating_add(1).min(items.len()),
+            None => 0,
+        };
+        let end = match before {
+            Some(before) => before.max(start).min(items.len()),
+            None => items.len(),
+        };
+
+        let mut paginator = Paginator::new(after, before, first, last);
+        paginator.has_prior = start > 0;
+        paginator.has_following = end < items.len();
+        for (idx, item) in items[start..end].iter().enumerate() {
+            if !paginator.push(start + idx, item.clone()) {
+                break;
+            }
+        }
+        paginator.finish()
+    }
+
+    /// Create a connection from a page of a stream, using the positions of the items as cursors.
+    ///
+    /// The stream is consumed only as far as the page needs.
+    pub async fn from_stream<S>(
+        stream: S,
+        after: Option<usize>,
+        before: Option<usize>,
+        first: Option<usize>,
+        last: Option<usize>,
+    ) -> Self
+    where
+        S: Stream<Item = T> + Unpin,
+    {
+        let mut paginator = Paginator::new(after, before, first, last);
+        let mut stream = stream.enumerate();
+        while let Some((idx, item)) = stream.next().await {
+            if !paginator.push(idx, item) {
+                break;
+            }
+        }
+        paginator.finish()
+    }
+
+    /// Create a connection from a page of a fallible stream, using the positions of the items as
+    /// cursors.
+    ///
+    /// The stream is consumed only as far as the page needs, and the first error is returned.
+    pub async fn try_from_stream<S>(
+        mut stream: S,
+        after: Option<usize>,
+        before: Option<usize>,
+        first: Option<usize>,
+        last: Option<usize>,
+    ) -> Result<Self>
+    where
+        S: Stream<Item = Result<T>> + Unpin,
+    {
+        let mut paginator = Paginator::new(after, before, first, last);
+        let mut idx = 0;
+        while let Some(item) = stream.try_next().await? {
+            if !paginator.push(idx, item) {
+                break;
+            }
+            idx += 1;
+        }
+        Ok(paginator.finish())
+    }
+}
+
+impl<C, T, Name, EdgeName> Connection<C, T, EmptyFields, EmptyFields, Name, EdgeName>
+where
+    C: CursorType + Ord,
+{
+    /// Create a connection from a page of items sorted by a unique key, using the keys as cursors.
+    ///
+    /// This is keyset pagination: the `after` and `before` cursors are compared with the keys, so
+    /// pages stay stable when items are inserted or removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    /// use async_graphql::connection::*;
+    ///
+    /// struct QueryRoot;
+    ///
+    /// #[Object]
+    /// impl QueryRoot {
+    ///     async fn names(&self,
+    ///         after: Option<String>,
+    ///         before: Option<String>,
+    ///         first: Option<i32>,
+    ///         last: Option<i32>
+    ///     ) -> Result<Connection<String, &'static str>> {
+    ///         let names = vec!["alice", "bob", "carol", "dave"];
+    ///         query(after, before, first, last, |after, before, first, last| async move {
+    ///             Ok(Connection::from_sorted_keyset(names, |name| name.to_string(), after, before, first, last))
+    ///         }).await
+    ///     }
+    /// }
+    ///
+    /// async_std::task::block_on(async move {
+    ///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    ///     let res = schema.execute(r#"{
+    ///         names(after: "bill", first: 2) {
+    ///             edges { node }
+    ///             pageInfo { hasPreviousPage hasNextPage }
+    ///         }
+    ///     }"#).await.into_result().unwrap().data;
+    ///     assert_eq!(res, serde_json::json!({
+    ///         "names": {
+    ///             "edges": [{ "node": "bob" }, { "node": "carol" }],
+    ///             "pageInfo": { "hasPreviousPage": true, "hasNextPage": true },
+    ///         },
+    ///     }));
+    /// });
+    /// ```
+    pub fn from_sorted_keyset<I, F>(
+        items: I,
+        mut key_fn: F,
+        after: Option<C>,
+        before: Option<C>,
+        first: Option<usize>,
+        last: Option<usize>,
+    ) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        F: FnMut(&T) -> C,
+    {
+        let mut paginator = Paginator::new(after, before, first, last);
+        for item in items {
+            if !paginator.push(key_fn(&item), item) {
+                break;
+            }
+        }
+        paginator.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::Connection;
+    use futures::stream;
+
+    type Page = (Vec<usize>, bool, bool);
+
+    /// The pagination algorithm of the Relay specification, with the items as their own cursors.
+    fn spec(
+        all_edges: &[usize],
+        after: Option<usize>,
+        before: Option<usize>,
+        first: Option<usize>,
+        last: Option<usize>,
+    ) -> Page {
+        let mut edges = all_edges.to_vec();
+        if let Some(after) = after {
+            if let Some(pos) = edges.iter().position(|edge| *edge == after) {
+                edges.drain(..=pos);
+            }
+        }
+        if let Some(before) = before {
+            if let Some(pos) = edges.iter().position(|edge| *edge == before) {
+                edges.truncate(pos);
+            }
+        }
+        let cursor_edges = edges.clone();
+
+        if let Some(first) = first {
+            edges.truncate(first);
+        }
+        if let Some(last) = last {
+            if edges.len() > last {
+                edges.drain(..edges.len() - last);
+            }
+        }
+
+        let has_previous_page = match last {
+            Some(last) => cursor_edges.len() > last,
+            None => matches!(after, Some(after) if all_edges.contains(&after)),
+        };
+        let has_next_page = match first {
+            Some(first) => cursor_edges.len() > first,
+            None => matches!(before, Some(before) if all_edges.contains(&before)),
+        };
+        (edges, has_previous_page, has_next_page)
+    }
+
+    fn page<C>(connection: Connection<C, usize>) -> Page {
+        (
+            connection.edges.into_iter().map(|edge| edge.node).collect(),
+            connection.has_previous_page,
+            connection.has_next_page,
+        )
+    }
+
+    fn key(n: usize) -> String {
+        format!("{:03}", n * 2)
+    }
+
+    #[async_std::test]
+    async fn test_pagination_algorithm() {
+        for len in 0..6 {
+            let items = (0..len).collect::<Vec<usize>>();
+            let cursors = std::iter::once(None).chain((0..len).map(Some));
+            let counts = std::iter::once(None)
+                .chain((0..len + 2).map(Some))
+                .collect::<Vec<_>>();
+
+            for after in cursors.clone() {
+                for before in cursors.clone() {
+                    if matches!((after, before), (Some(after), Some(before)) if before <= after) {
+                        continue;
+                    }
+                    for first in counts.iter().copied() {
+                        for last in counts.iter().copied() {
+                            let expected = spec(&items, after, before, first, last);
+                            let args = (after, before, first, last);
+
+                            assert_eq!(
+                                page(Connection::from_slice(&items, after, before, first, last)),
+                                expected,
+                                "from_slice {:?}",
+                                args
+                            );
+                            assert_eq!(
+                                page(
+                                    Connection::from_stream(
+                                        stream::iter(items.clone()),
+                                        after,
+                                        before,
+                                        first,
+                                        last
+                                    )
+                                    .await
+                                ),
+                                expected,
+                                "from_stream {:?}",
+                                args
+                            );
+                            assert_eq!(
+                                page(
+                                    Connection::try_from_stream(
+                                        stream::iter(items.clone().into_iter().map(Ok)),
+                                        after,
+                                        before,
+                                        first,
+                                        last
+                                    )
+                                    .await
+                                    .unwrap()
+                                ),
+                                expected,
+                                "try_from_stream {:?}",
+                                args
+                            );
+                            assert_eq!(
+                                page(Connection::from_sorted_keyset(
+                                    items.clone(),
+                                    |n| key(*n),
+                                    after.map(key),
+                                    before.map(key),
+                                    first,
+                                    last
+                                )),
+                                expected,
+                                "from_sorted_keyset {:?}",
+                                args
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pagination_missing_cursors() {
+        let items = (0..5).collect::<Vec<usize>>();
+
+        assert_eq!(
+            page(Connection::from_slice(&items, Some(10), None, None, None)),
+            (vec![], true, false)
+        );
+        assert_eq!(
+            page(Connection::from_slice(
+                &items,
+                None,
+                Some(10),
+                Some(2),
+                None
+            )),
+            (vec![0, 1], false, true)
+        );
+        assert_eq!(
+            page(Connection::from_slice(&[], Some(0), Some(0), None, None)),
+            (vec![], false, false)
+        );
+
+        // Keys between the existing keys select the same pages as the next smaller keys.
+        assert_eq!(
+            page(Connection::from_sorted_keyset(
+                items.clone(),
+                |n| key(*n),
+                Some("003".to_string()),
+                Some("007".to_string()),
+                None,
+                None
+            )),
+            (vec![2, 3], true, true)
+        );
+        assert_eq!(
+            page(Connection::from_sorted_keyset(
+                items,
+                |n| key(*n),
+                Some("".to_string()),
+                None,
+                None,
+                Some(1)
+            )),
+            (vec![4], true, false)
+        );
+    }
+
+    #[async_std::test]
+    async fn test_pagination_stream_error() {
+        let items = vec![Ok(0), Ok(1), Err("error".into()), Ok(3)];
+        assert!(Connection::<usize, usize>::try_from_stream(
+            stream::iter(items.clone()),
+            None,
+            None,
+            Some(5),
+            None
+        )
+        .await
+        .is_err());
+        assert_eq!(
+            page(
+                Connection::try_from_stream(stream::iter(items), None, None, Some(1), None)
+                    .await
+                    .unwrap()
+            ),
+            (vec![0], false, true)
+        );
+    }
+}
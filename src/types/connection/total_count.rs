@@ -0,0 +1,221 @@
+use crate::connection::EmptyFields;
+use crate::parser::types::Field;
+use crate::registry::{MetaField, MetaType, Registry};
+use crate::resolver_utils::resolve_container;
+use crate::{
+    ContainerType, Context, ContextSelectionSet, Error, ObjectType, OutputValueType, Positioned,
+    Result, ServerResult, Type,
+};
+use futures::future::BoxFuture;
+use futures::lock::Mutex;
+use futures::{Future, FutureExt};
+use indexmap::IndexMap;
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+enum State {
+    Pending(Option<Box<dyn FnOnce() -> BoxFuture<'static, Result<usize>> + Send>>),
+    Done(Result<usize>),
+}
+
+/// Lazily computed `totalCount` field of a connection
+///
+/// Use it as the additional fields of a connection to add a `totalCount: Int!` field. The closure
+/// is only called when the field is selected, and at most once, so an expensive count is skipped
+/// for the queries that do not need it. A resolver that computes the count together with the
+/// page instead can check whether it is needed with
+/// [`TotalCount::is_selected`](#method.is_selected).
+///
+/// `TotalCount<T>` adds the field to the other additional fields `T`, which is what
+/// `Connection::with_total_count` creates from the current additional fields.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::connection::*;
+///
+/// #[derive(SimpleObject)]
+/// struct Diff {
+///     diff: i32,
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn numbers(&self,
+///         after: Option<String>,
+///         before: Option<String>,
+///         first: Option<i32>,
+///         last: Option<i32>
+///     ) -> Result<Connection<usize, i32, TotalCount>> {
+///         query(after, before, first, last, |after, before, first, last| async move {
+///             let numbers = (0..100).collect::<Vec<i32>>();
+///             let total_count = numbers.len();
+///             Ok(Connection::from_slice(&numbers, after, before, first, last)
+///                 .with_total_count(move || async move { Ok(total_count) }))
+///         }).await
+///     }
+///
+///     async fn diffs(&self) -> Connection<usize, String, TotalCount<Diff>> {
+///         Connection::with_additional_fields(false, false, Diff { diff: 1 })
+///             .with_total_count(|| async move { Ok(0) })
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///     let res = schema.execute("{ numbers(first: 1) { totalCount edges { node } } }").await;
+///     assert_eq!(res.into_result().unwrap().data, serde_json::json!({
+///         "numbers": { "totalCount": 100, "edges": [{ "node": 0 }] },
+///     }));
+///
+///     let res = schema.execute("{ diffs { totalCount diff } }").await;
+///     assert_eq!(res.into_result().unwrap().data, serde_json::json!({
+///         "diffs": { "totalCount": 0, "diff": 1 },
+///     }));
+/// });
+/// ```
+pub struct TotalCount<T = EmptyFields> {
+    state: Mutex<State>,
+    fields: T,
+}
+
+impl TotalCount {
+    /// Create a `totalCount` field that is computed by `f` when it is selected.
+    pub fn new<F, R>(f: F) -> Self
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Future<Output = Result<usize>> + Send + 'static,
+    {
+        Self {
+            state: Mutex::new(State::Pending(Some(Box::new(move || f().boxed())))),
+            fields: EmptyFields,
+        }
+    }
+
+    /// Create a `totalCount` field with a known value.
+    pub fn with_value(total_count: usize) -> Self {
+        Self {
+            state: Mutex::new(State::Done(Ok(total_count))),
+            fields: EmptyFields,
+        }
+    }
+
+    /// Returns `true` if the `totalCount` field of the connection that is being resolved is
+    /// selected.
+    pub fn is_selected(ctx: &Context<'_>) -> bool {
+        ctx.look_ahead().field("totalCount").exists()
+    }
+}
+
+impl<T> TotalCount<T> {
+    /// Add the `totalCount` field to other additional fields.
+    pub fn with_fields<U>(self, fields: U) -> TotalCount<U> {
+        TotalCount {
+            state: self.state,
+            fields,
+        }
+    }
+
+    /// The other additional fields.
+    pub fn fields(&self) -> &T {
+        &self.fields
+    }
+
+    async fn total_count(&self) -> Result<i32> {
+        let mut state = self.state.lock().await;
+        let total_count = match &mut *state {
+            State::Done(total_count) => total_count.clone(),
+            State::Pending(f) => match f.take() {
+                Some(f) => {
+                    let total_count = f().await;
+                    *state = State::Done(total_count.clone());
+                    total_count
+                }
+                None => return Err(Error::new("The total count is not available.")),
+            },
+        };
+        Ok(i32::try_from(total_count?)?)
+    }
+}
+
+impl<T: ObjectType> Type for TotalCount<T> {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("TotalCount_{}", T::type_name()))
+    }
+
+    fn create_type_info(registry: &mut Registry) -> String {
+        registry.create_type::<Self, _>(|registry| {
+            let mut fields = IndexMap::new();
+
+            fields.insert(
+                "totalCount".to_string(),
+                MetaField {
+                    name: "totalCount".to_string(),
+                    description: Some("Identifies the total count of items in the connection."),
+                    args: Default::default(),
+                    ty: i32::create_type_info(registry),
+                    deprecation: None,
+                    cache_control: Default::default(),
+                    external: false,
+                    requires: None,
+                    provides: None,
+                    visible: None,
+                },
+            );
+
+            T::create_type_info(registry);
+            if let Some(MetaType::Object {
+                fields: other_fields,
+                ..
+            }) = registry.types.remove(&*T::type_name())
+            {
+                fields.extend(other_fields);
+            }
+
+            MetaType::Object {
+                name: Self::type_name().to_string(),
+                description: None,
+                fields,
+                cache_control: Default::default(),
+                extends: false,
+                keys: None,
+                visible: None,
+            }
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: ObjectType + Send + Sync> ContainerType for TotalCount<T> {
+    async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<serde_json::Value>> {
+        if ctx.item.node.name.node == "totalCount" {
+            return self
+                .total_count()
+                .await
+                .map(|total_count| Some(total_count.into()))
+                .map_err(|err| ctx.convert_error(err, ctx.item.pos));
+        }
+        self.fields.resolve_field(ctx).await
+    }
+
+    async fn check_guard(&self, ctx: &Context<'_>) -> ServerResult<()> {
+        self.fields.check_guard(ctx).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: ObjectType + Send + Sync> OutputValueType for TotalCount<T> {
+    async fn resolve(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        field: &Positioned<Field>,
+    ) -> ServerResult<serde_json::Value> {
+        self.check_guard(&ctx.with_parent_field(field)).await?;
+        resolve_container(ctx, self).await
+    }
+}
+
+impl<T: ObjectType + Send + Sync> ObjectType for TotalCount<T> {}
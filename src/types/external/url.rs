@@ -1,7 +1,7 @@
 use crate::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
 use url::Url;
 
-#[Scalar(internal)]
+#[Scalar(internal, specified_by_url = "https://url.spec.whatwg.org/")]
 impl ScalarType for Url {
     fn parse(value: Value) -> InputValueResult<Self> {
         match value {
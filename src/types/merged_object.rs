@@ -2,8 +2,8 @@ use crate::parser::types::Field;
 use crate::registry::{MetaType, Registry};
 use crate::resolver_utils::resolve_container;
 use crate::{
-    CacheControl, ContainerType, Context, ContextSelectionSet, ObjectType, OutputValueType,
-    Positioned, ServerResult, SimpleObject, Type,
+    CacheControl, ContainerType, Context, ContextSelectionSet, GlobalId, ObjectType,
+    OutputValueType, Positioned, ServerResult, SimpleObject, Type,
 };
 use indexmap::IndexMap;
 use std::borrow::Cow;
@@ -50,6 +50,7 @@ impl<A: Type, B: Type> Type for MergedObject<A, B> {
                 cache_control: cc,
                 extends: false,
                 keys: None,
+                visible: None,
             }
         })
     }
@@ -68,6 +69,23 @@ where
             Err(err) => Err(err),
         }
     }
+
+    async fn check_guard(&self, ctx: &Context<'_>) -> ServerResult<()> {
+        self.0.check_guard(ctx).await?;
+        self.1.check_guard(ctx).await
+    }
+
+    async fn find_node(
+        &self,
+        ctx: &Context<'_>,
+        id: &GlobalId,
+    ) -> ServerResult<Option<serde_json::Value>> {
+        match self.0.find_node(ctx, id).await {
+            Ok(Some(value)) => Ok(Some(value)),
+            Ok(None) => self.1.find_node(ctx, id).await,
+            Err(err) => Err(err),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -79,8 +97,9 @@ where
     async fn resolve(
         &self,
         ctx: &ContextSelectionSet<'_>,
-        _field: &Positioned<Field>,
+        field: &Positioned<Field>,
     ) -> ServerResult<serde_json::Value> {
+        self.check_guard(&ctx.with_parent_field(field)).await?;
         resolve_container(ctx, self).await
     }
 }
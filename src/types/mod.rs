@@ -1,6 +1,7 @@
 //! Useful GraphQL types.
 
 pub mod connection;
+pub mod pagination;
 
 mod any;
 mod empty_mutation;
@@ -9,7 +10,9 @@ mod id;
 mod json;
 mod maybe_undefined;
 mod merged_object;
+mod node;
 mod query_root;
+mod result_union;
 #[cfg(feature = "string_number")]
 mod string_number;
 mod upload;
@@ -23,8 +26,12 @@ pub use id::ID;
 pub use json::{Json, OutputJson};
 pub use maybe_undefined::MaybeUndefined;
 pub use merged_object::{MergedObject, MergedObjectTail};
+pub use node::GlobalId;
+pub use result_union::ResultUnion;
 #[cfg(feature = "string_number")]
 pub use string_number::StringNumber;
 pub use upload::Upload;
+#[cfg(feature = "multipart")]
+pub use upload::{StreamedUpload, UploadContent, UploadStream};
 
 pub(crate) use query_root::QueryRoot;
@@ -0,0 +1,166 @@
+use crate::parser::types::Field;
+use crate::registry::Registry;
+use crate::{
+    ContextSelectionSet, InputValueError, InputValueResult, InputValueType, OutputValueType,
+    Positioned, ScalarType, ServerResult, Type, Value, ID,
+};
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// Relay global object identifier
+///
+/// A globally unique `ID` made of the name of an object type and the id of the object within that
+/// type. It is serialized as the base64 encoding of `Type:id`.
+///
+/// Objects get a global id with the `global_id` attribute on their `id` field, which also makes
+/// them implement the `Node` interface. The objects are found again by the `node` and `nodes`
+/// fields of the query root, which dispatch to the query methods with the `node` attribute.
+/// *[See also the Relay specification](https://relay.dev/graphql/objectidentification.htm).*
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(SimpleObject)]
+/// struct User {
+///     #[graphql(global_id)]
+///     id: i32,
+///     name: String,
+/// }
+///
+/// struct Query;
+///
+/// #[Object]
+/// impl Query {
+///     async fn me(&self) -> User {
+///         User { id: 1, name: "Alice".to_string() }
+///     }
+///
+///     #[graphql(node)]
+///     async fn find_user(&self, id: i32) -> Option<User> {
+///         if id == 1 {
+///             Some(User { id: 1, name: "Alice".to_string() })
+///         } else {
+///             None
+///         }
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+///     let id = GlobalId::new("User", 1).to_id();
+///     assert_eq!(id, "VXNlcjox");
+///
+///     let res = schema.execute(r#"{
+///         me { id }
+///         node(id: "VXNlcjox") { __typename ... on User { name } }
+///     }"#).await.into_result().unwrap().data;
+///     assert_eq!(res, serde_json::json!({
+///         "me": { "id": "VXNlcjox" },
+///         "node": { "__typename": "User", "name": "Alice" },
+///     }));
+/// });
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GlobalId {
+    type_name: String,
+    id: String,
+}
+
+impl GlobalId {
+    /// Create a global id of the object with the id `id` and the type `type_name`.
+    pub fn new(type_name: impl Into<String>, id: impl Display) -> Self {
+        Self {
+            type_name: type_name.into(),
+            id: id.to_string(),
+        }
+    }
+
+    /// The name of the object type.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// The id of the object within its type.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Parse the id of the object within its type.
+    pub fn parse_id<T: FromStr>(&self) -> Result<T, T::Err> {
+        self.id.parse()
+    }
+
+    /// Encode the global id to an `ID`.
+    pub fn to_id(&self) -> ID {
+        ID(base64::encode(self.to_string()))
+    }
+
+    /// Decode a global id from an `ID`.
+    pub fn from_id(id: &ID) -> Option<Self> {
+        let decoded = String::from_utf8(base64::decode(id.as_str()).ok()?).ok()?;
+        let mut parts = decoded.splitn(2, ':');
+        match (parts.next(), parts.next()) {
+            (Some(type_name), Some(id)) if !type_name.is_empty() => Some(Self::new(type_name, id)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for GlobalId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.type_name, self.id)
+    }
+}
+
+impl ScalarType for GlobalId {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => GlobalId::from_id(&ID(s.clone()))
+                .ok_or_else(|| InputValueError::custom("Invalid global id.")),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        <ID as ScalarType>::is_valid(value)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_id().0)
+    }
+}
+
+/// A global id is an `ID` on the wire, so it shares the `ID` scalar instead of registering its own.
+impl Type for GlobalId {
+    fn type_name() -> Cow<'static, str> {
+        ID::type_name()
+    }
+
+    fn create_type_info(registry: &mut Registry) -> String {
+        ID::create_type_info(registry)
+    }
+}
+
+impl InputValueType for GlobalId {
+    fn parse(value: Option<Value>) -> InputValueResult<Self> {
+        <GlobalId as ScalarType>::parse(value.unwrap_or_default())
+    }
+
+    fn to_value(&self) -> Value {
+        <GlobalId as ScalarType>::to_value(self)
+    }
+}
+
+#[async_trait::async_trait]
+impl OutputValueType for GlobalId {
+    async fn resolve(
+        &self,
+        _ctx: &ContextSelectionSet<'_>,
+        _field: &Positioned<Field>,
+    ) -> ServerResult<serde_json::Value> {
+        Ok(serde_json::Value::String(self.to_id().0))
+    }
+}
@@ -0,0 +1,303 @@
+//! Types for offset and limit pagination
+
+use crate::connection::TotalCount;
+use crate::parser::types::Field;
+use crate::resolver_utils::{resolve_container, ContainerType};
+use crate::{
+    registry, Context, ContextSelectionSet, Error, InputObject, ObjectType, OutputValueType,
+    Positioned, Result, ServerError, ServerResult, SimpleObject, Type,
+};
+use futures::Future;
+use indexmap::map::IndexMap;
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+/// The arguments of an offset and limit paginated query
+///
+/// Use it as a field argument and pass it to `pagination::query`, which validates it.
+#[derive(InputObject, Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[graphql(internal)]
+pub struct PageArgs {
+    /// The number of items to skip.
+    #[graphql(default)]
+    pub offset: i32,
+
+    /// The maximum number of items to return.
+    pub limit: Option<i32>,
+}
+
+impl PageArgs {
+    /// Validate the arguments and return the offset and the limit.
+    ///
+    /// `default_limit` is used if no limit is given, and the limit cannot be greater than
+    /// `max_limit`.
+    pub fn validate(self, default_limit: usize, max_limit: usize) -> Result<(usize, usize)> {
+        if self.offset < 0 {
+            return Err(Error::new(
+                "The \"offset\" parameter must be a non-negative number",
+            ));
+        }
+
+        let limit = match self.limit {
+            Some(limit) if limit < 0 => {
+                return Err(Error::new(
+                    "The \"limit\" parameter must be a non-negative number",
+                ));
+            }
+            Some(limit) if limit as usize > max_limit => {
+                return Err(format!(
+                    "The \"limit\" parameter must not be greater than {}",
+                    max_limit
+                )
+                .into());
+            }
+            Some(limit) => limit as usize,
+            None => default_limit.min(max_limit),
+        };
+
+        Ok((self.offset as usize, limit))
+    }
+}
+
+/// Information about pagination in a page
+#[derive(SimpleObject)]
+#[graphql(internal)]
+pub struct OffsetPageInfo {
+    /// The number of items skipped before this page.
+    pub offset: i32,
+
+    /// The maximum number of items in this page.
+    pub limit: i32,
+
+    /// Are there more items after this page?
+    pub has_more: bool,
+}
+
+/// Page type
+///
+/// Page is the result of a query for `pagination::query`. Its GraphQL name is `{ItemType}Page`.
+pub struct Page<T> {
+    items: Vec<T>,
+    total_count: Option<TotalCount>,
+    offset: usize,
+    limit: usize,
+    has_more: bool,
+}
+
+impl<T> Page<T> {
+    /// Create a new page.
+    pub fn new(items: Vec<T>, offset: usize, limit: usize, has_more: bool) -> Self {
+        Self {
+            items,
+            total_count: None,
+            offset,
+            limit,
+            has_more,
+        }
+    }
+
+    /// Create a page of `items`, taking at most `limit` items after skipping `offset` items.
+    pub fn from_slice(items: &[T], offset: usize, limit: usize) -> Self
+    where
+        T: Clone,
+    {
+        let start = offset.min(items.len());
+        let end = offset.saturating_add(limit).min(items.len());
+        Self {
+            items: items[start..end].to_vec(),
+            total_count: Some(TotalCount::with_value(items.len())),
+            offset,
+            limit,
+            has_more: end < items.len(),
+        }
+    }
+
+    /// Add a lazily computed `totalCount` field, see `TotalCount`.
+    ///
+    /// The field is `null` if no total count is given.
+    pub fn with_total_count<F, R>(self, f: F) -> Self
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Future<Output = Result<usize>> + Send + 'static,
+    {
+        Self {
+            total_count: Some(TotalCount::new(f)),
+            ..self
+        }
+    }
+
+    /// The items of the current page.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+}
+
+/// Validates the arguments and executes the query.
+///
+/// The closure is called with the offset and the limit, see `PageArgs::validate`.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::pagination::*;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn numbers(&self, #[graphql(default)] page: PageArgs) -> Result<Page<i32>> {
+///         query(page, 10, 100, |offset, limit| async move {
+///             let numbers = (0..1000).collect::<Vec<i32>>();
+///             Ok(Page::from_slice(&numbers, offset, limit))
+///         }).await
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///
+///     let res = schema.execute("{ numbers(page: { offset: 998 }) { items totalCount pageInfo { offset limit hasMore } } }").await;
+///     assert_eq!(res.into_result().unwrap().data, serde_json::json!({
+///         "numbers": {
+///             "items": [998, 999],
+///             "totalCount": 1000,
+///             "pageInfo": { "offset": 998, "limit": 10, "hasMore": false },
+///         },
+///     }));
+///
+///     let res = schema.execute("{ numbers(page: { limit: 101 }) { items } }").await;
+///     assert_eq!(
+///         res.into_result().unwrap_err()[0].message,
+///         "The \"limit\" parameter must not be greater than 100",
+///     );
+/// });
+/// ```
+pub async fn query<T, F, R>(
+    args: PageArgs,
+    default_limit: usize,
+    max_limit: usize,
+    f: F,
+) -> Result<Page<T>>
+where
+    F: FnOnce(usize, usize) -> R,
+    R: Future<Output = Result<Page<T>>>,
+{
+    let (offset, limit) = args.validate(default_limit, max_limit)?;
+    f(offset, limit).await
+}
+
+impl<T: OutputValueType + Send + Sync> Type for Page<T> {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("{}Page", T::type_name()))
+    }
+
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        registry.create_type::<Self, _>(|registry| registry::MetaType::Object {
+            name: Self::type_name().to_string(),
+            description: None,
+            fields: {
+                let mut fields = IndexMap::new();
+
+                fields.insert(
+                    "items".to_string(),
+                    registry::MetaField {
+                        name: "items".to_string(),
+                        description: Some("The items of the current page."),
+                        args: Default::default(),
+                        ty: <Vec<T> as Type>::create_type_info(registry),
+                        deprecation: None,
+                        cache_control: Default::default(),
+                        external: false,
+                        requires: None,
+                        provides: None,
+                        visible: None,
+                    },
+                );
+
+                fields.insert(
+                    "totalCount".to_string(),
+                    registry::MetaField {
+                        name: "totalCount".to_string(),
+                        description: Some("Identifies the total count of items."),
+                        args: Default::default(),
+                        ty: <Option<i32> as Type>::create_type_info(registry),
+                        deprecation: None,
+                        cache_control: Default::default(),
+                        external: false,
+                        requires: None,
+                        provides: None,
+                        visible: None,
+                    },
+                );
+
+                fields.insert(
+                    "pageInfo".to_string(),
+                    registry::MetaField {
+                        name: "pageInfo".to_string(),
+                        description: Some("Information to aid in pagination."),
+                        args: Default::default(),
+                        ty: OffsetPageInfo::create_type_info(registry),
+                        deprecation: None,
+                        cache_control: Default::default(),
+                        external: false,
+                        requires: None,
+                        provides: None,
+                        visible: None,
+                    },
+                );
+
+                fields
+            },
+            cache_control: Default::default(),
+            extends: false,
+            keys: None,
+            visible: None,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: OutputValueType + Send + Sync> ContainerType for Page<T> {
+    async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<serde_json::Value>> {
+        if ctx.item.node.name.node == "items" {
+            let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+            return OutputValueType::resolve(&self.items, &ctx_obj, ctx.item)
+                .await
+                .map(Some);
+        } else if ctx.item.node.name.node == "totalCount" {
+            return match &self.total_count {
+                Some(total_count) => total_count.resolve_field(ctx).await,
+                None => Ok(Some(serde_json::Value::Null)),
+            };
+        } else if ctx.item.node.name.node == "pageInfo" {
+            let to_int = |n: usize| {
+                i32::try_from(n).map_err(|err| ServerError::new(err.to_string()).at(ctx.item.pos))
+            };
+            let page_info = OffsetPageInfo {
+                offset: to_int(self.offset)?,
+                limit: to_int(self.limit)?,
+                has_more: self.has_more,
+            };
+            let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+            return OutputValueType::resolve(&page_info, &ctx_obj, ctx.item)
+                .await
+                .map(Some);
+        }
+
+        Ok(None)
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: OutputValueType + Send + Sync> OutputValueType for Page<T> {
+    async fn resolve(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        _field: &Positioned<Field>,
+    ) -> ServerResult<serde_json::Value> {
+        resolve_container(ctx, self).await
+    }
+}
+
+impl<T: OutputValueType + Send + Sync> ObjectType for Page<T> {}
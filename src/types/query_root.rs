@@ -2,8 +2,8 @@ use crate::model::{__Schema, __Type};
 use crate::parser::types::Field;
 use crate::resolver_utils::{resolve_container, ContainerType};
 use crate::{
-    registry, Any, Context, ContextSelectionSet, ObjectType, OutputValueType, Positioned,
-    ServerError, ServerResult, SimpleObject, Type,
+    registry, Any, Context, ContextSelectionSet, GlobalId, IntrospectionMode, ObjectType,
+    OutputValueType, Positioned, ServerError, ServerResult, SimpleObject, Type,
 };
 
 use indexmap::map::IndexMap;
@@ -18,7 +18,6 @@ struct Service {
 
 pub(crate) struct QueryRoot<T> {
     pub(crate) inner: T,
-    pub(crate) disable_introspection: bool,
 }
 
 impl<T: Type> Type for QueryRoot<T> {
@@ -44,6 +43,7 @@ impl<T: Type> Type for QueryRoot<T> {
                     external: false,
                     requires: None,
                     provides: None,
+                    visible: None,
                 },
             );
 
@@ -61,6 +61,7 @@ impl<T: Type> Type for QueryRoot<T> {
                                 description: None,
                                 ty: "String!".to_string(),
                                 default_value: None,
+                                deprecation: None,
                                 validator: None,
                             },
                         );
@@ -72,6 +73,7 @@ impl<T: Type> Type for QueryRoot<T> {
                     external: false,
                     requires: None,
                     provides: None,
+                    visible: None,
                 },
             );
         }
@@ -82,11 +84,24 @@ impl<T: Type> Type for QueryRoot<T> {
 #[async_trait::async_trait]
 impl<T: ObjectType + Send + Sync> ContainerType for QueryRoot<T> {
     async fn resolve_field(&self, ctx: &Context<'_>) -> ServerResult<Option<serde_json::Value>> {
-        if ctx.item.node.name.node == "__schema" {
-            if self.disable_introspection {
-                return Ok(None);
+        // The federation `_service` field is still available when introspection is disabled, and
+        // schema registries can pull the SDL through it in introspection only mode.
+        let field_name = ctx.item.node.name.node.as_str();
+        match ctx.query_env.introspection_mode {
+            IntrospectionMode::Disabled if matches!(field_name, "__schema" | "__type") => {
+                return Err(ServerError::new("Introspection is disabled.").at(ctx.item.pos));
+            }
+            IntrospectionMode::IntrospectionOnly
+                if !matches!(field_name, "__schema" | "__type" | "_service") =>
+            {
+                return Err(
+                    ServerError::new("Only introspection queries are allowed.").at(ctx.item.pos)
+                );
             }
+            _ => {}
+        }
 
+        if ctx.item.node.name.node == "__schema" {
             let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
             return OutputValueType::resolve(
                 &__Schema {
@@ -105,6 +120,7 @@ impl<T: ObjectType + Send + Sync> ContainerType for QueryRoot<T> {
                     .registry
                     .types
                     .get(&type_name)
+                    .filter(|ty| ty.is_visible(&ctx.visible_context()))
                     .map(|ty| __Type::new_simple(&ctx.schema_env.registry, ty)),
                 &ctx_obj,
                 ctx.item,
@@ -127,7 +143,11 @@ impl<T: ObjectType + Send + Sync> ContainerType for QueryRoot<T> {
             let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
             return OutputValueType::resolve(
                 &Service {
-                    sdl: Some(ctx.schema_env.registry.export_sdl(true)),
+                    sdl: Some(
+                        ctx.schema_env
+                            .registry
+                            .export_sdl_with_context(true, &ctx.visible_context()),
+                    ),
                 },
                 &ctx_obj,
                 ctx.item,
@@ -136,7 +156,35 @@ impl<T: ObjectType + Send + Sync> ContainerType for QueryRoot<T> {
             .map(Some);
         }
 
-        self.inner.resolve_field(ctx).await
+        let value = self.inner.resolve_field(ctx).await?;
+        if value.is_some() {
+            return Ok(value);
+        }
+
+        // The Relay `node` and `nodes` fields, unless the query root defines them itself.
+        if ctx.item.node.name.node == "node" {
+            let id: GlobalId = ctx.param_value("id", None)?;
+            let node = self.inner.find_node(ctx, &id).await?;
+            return Ok(Some(node.unwrap_or(serde_json::Value::Null)));
+        } else if ctx.item.node.name.node == "nodes" {
+            let ids: Vec<GlobalId> = ctx.param_value("ids", None)?;
+            let mut res = Vec::with_capacity(ids.len());
+            for id in &ids {
+                res.push(
+                    self.inner
+                        .find_node(ctx, id)
+                        .await?
+                        .unwrap_or(serde_json::Value::Null),
+                );
+            }
+            return Ok(Some(res.into()));
+        }
+
+        Ok(None)
+    }
+
+    async fn check_guard(&self, ctx: &Context<'_>) -> ServerResult<()> {
+        self.inner.check_guard(ctx).await
     }
 }
 
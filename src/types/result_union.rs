@@ -0,0 +1,193 @@
+use crate::parser::types::Field;
+use crate::registry::{MetaType, Registry};
+use crate::resolver_utils::{resolve_container, Fields};
+use crate::{
+    ContainerType, Context, ContextSelectionSet, ObjectType, OutputValueType, Positioned,
+    ServerResult, Type, UnionType,
+};
+use indexmap::IndexSet;
+use std::borrow::Cow;
+
+/// The value of a field that returns `Result<T, E>`, where `E` is a union of error objects.
+///
+/// It is registered as a union named `{T}OrError` which contains `T` and all the members of `E`,
+/// so clients select the payload and the errors with fragments. Building a schema that uses the
+/// same `T` with two error unions of different members panics, because both would be named
+/// `{T}OrError`.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(SimpleObject)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// #[derive(SimpleObject)]
+/// struct EmailTaken {
+///     email: String,
+/// }
+///
+/// #[derive(SimpleObject)]
+/// struct RateLimited {
+///     retry_after: i32,
+/// }
+///
+/// #[derive(Union)]
+/// enum SignUpError {
+///     EmailTaken(EmailTaken),
+///     RateLimited(RateLimited),
+/// }
+///
+/// struct MutationRoot;
+///
+/// #[Object]
+/// impl MutationRoot {
+///     async fn sign_up(&self, email: String) -> Result<User, SignUpError> {
+///         Err(SignUpError::EmailTaken(EmailTaken { email }))
+///     }
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::new(QueryRoot, MutationRoot, EmptySubscription);
+///     let query = r#"mutation {
+///         signUp(email: "a@b.c") {
+///             __typename
+///             ... on User { name }
+///             ... on EmailTaken { email }
+///         }
+///     }"#;
+///     assert_eq!(schema.execute(query).await.into_result().unwrap().data, serde_json::json!({
+///         "signUp": { "__typename": "EmailTaken", "email": "a@b.c" },
+///     }));
+/// });
+/// ```
+pub enum ResultUnion<T, E> {
+    /// The payload.
+    Ok(T),
+    /// The error.
+    Err(E),
+}
+
+impl<T: ObjectType, E: UnionType> Type for ResultUnion<T, E> {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("{}OrError", T::type_name()))
+    }
+
+    fn introspection_type_name(&self) -> Cow<'static, str> {
+        match self {
+            ResultUnion::Ok(obj) => obj.introspection_type_name(),
+            ResultUnion::Err(err) => err.introspection_type_name(),
+        }
+    }
+
+    fn create_type_info(registry: &mut Registry) -> String {
+        let name = registry.create_type::<Self, _>(|registry| MetaType::Union {
+            name: Self::type_name().to_string(),
+            description: None,
+            possible_types: Self::possible_types(registry),
+            visible: None,
+        });
+
+        // Another `ResultUnion` with the same payload type may have registered the union with a
+        // different set of errors.
+        let possible_types = Self::possible_types(registry);
+        if let Some(MetaType::Union {
+            possible_types: registered,
+            ..
+        }) = registry.types.get(&*Self::type_name())
+        {
+            if *registered != possible_types {
+                panic!(
+                    "Union \"{}\" is registered with different error types, wrap one of the results in a named union instead.",
+                    Self::type_name()
+                );
+            }
+        }
+
+        name
+    }
+}
+
+impl<T: ObjectType, E: UnionType> ResultUnion<T, E> {
+    fn possible_types(registry: &mut Registry) -> IndexSet<String> {
+        let mut possible_types = IndexSet::new();
+
+        T::create_type_info(registry);
+        possible_types.insert(T::type_name().to_string());
+
+        E::create_type_info(registry);
+        if let Some(MetaType::Union {
+            possible_types: error_types,
+            ..
+        }) = registry.types.get(&*E::type_name())
+        {
+            possible_types.extend(error_types.iter().cloned());
+        }
+
+        possible_types
+    }
+}
+
+#[async_trait::async_trait]
+impl<T, E> ContainerType for ResultUnion<T, E>
+where
+    T: ObjectType + Send + Sync,
+    E: UnionType + Send + Sync,
+{
+    async fn resolve_field(&self, _ctx: &Context<'_>) -> ServerResult<Option<serde_json::Value>> {
+        Ok(None)
+    }
+
+    fn collect_all_fields<'a>(
+        &'a self,
+        ctx: &ContextSelectionSet<'a>,
+        fields: &mut Fields<'a>,
+    ) -> ServerResult<()> {
+        match self {
+            ResultUnion::Ok(obj) => obj.collect_all_fields(ctx, fields),
+            ResultUnion::Err(err) => err.collect_all_fields(ctx, fields),
+        }
+    }
+
+    async fn check_guard(&self, ctx: &Context<'_>) -> ServerResult<()> {
+        match self {
+            ResultUnion::Ok(obj) => obj.check_guard(ctx).await,
+            ResultUnion::Err(err) => err.check_guard(ctx).await,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<T, E> OutputValueType for ResultUnion<T, E>
+where
+    T: ObjectType + Send + Sync,
+    E: UnionType + Send + Sync,
+{
+    async fn resolve(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        field: &Positioned<Field>,
+    ) -> ServerResult<serde_json::Value> {
+        self.check_guard(&ctx.with_parent_field(field)).await?;
+        resolve_container(ctx, self).await
+    }
+}
+
+impl<T, E> UnionType for ResultUnion<T, E>
+where
+    T: ObjectType + Send + Sync,
+    E: UnionType + Send + Sync,
+{
+}
@@ -3,6 +3,27 @@ use crate::{registry, InputValueError, InputValueResult, InputValueType, Type, V
 use std::borrow::Cow;
 use std::io::Read;
 
+#[cfg(feature = "multipart")]
+use crate::Context;
+#[cfg(feature = "multipart")]
+use bytes::Bytes;
+#[cfg(feature = "multipart")]
+use futures::future::BoxFuture;
+#[cfg(feature = "multipart")]
+use futures::stream::{BoxStream, Stream, StreamExt};
+#[cfg(feature = "multipart")]
+use std::any::Any;
+#[cfg(feature = "multipart")]
+use std::fs::File;
+#[cfg(feature = "multipart")]
+use std::io::{self, Cursor, Seek, SeekFrom};
+#[cfg(feature = "multipart")]
+use std::pin::Pin;
+#[cfg(feature = "multipart")]
+use std::sync::Arc;
+#[cfg(feature = "multipart")]
+use std::task::Poll;
+
 /// Uploaded file
 ///
 /// **Reference:** <https://github.com/jaydenseric/graphql-multipart-request-spec>
@@ -12,6 +33,9 @@ use std::io::Read;
 /// Enable this feature by accepting an argument of type `Upload` (single file) or
 /// `Vec<Upload>` (multiple files) in your mutation like in the example blow.
 ///
+/// The files that are not stored in files by the `UploadStorage` of `MultipartOptions` are read
+/// with the methods that take the context, such as `Upload::try_into_read`.
+///
 ///
 /// # Example
 /// *[Full Example](<https://github.com/async-graphql/examples/blob/master/models/files/src/lib.rs>)*
@@ -42,36 +66,169 @@ use std::io::Read;
 /// --form 'map={ "0": ["variables.file"] }' \
 /// --form '0=@myFile.txt'
 /// ```
-pub struct Upload(UploadValue);
+pub struct Upload(UploadInner);
+
+enum UploadInner {
+    File(UploadValue),
+    #[cfg(feature = "multipart")]
+    Stored {
+        index: usize,
+        filename: String,
+        content_type: Option<String>,
+    },
+}
 
 impl Upload {
     /// Filename
     pub fn filename(&self) -> &str {
-        self.0.filename.as_str()
+        match &self.0 {
+            UploadInner::File(upload) => upload.filename.as_str(),
+            #[cfg(feature = "multipart")]
+            UploadInner::Stored { filename, .. } => filename.as_str(),
+        }
     }
 
     /// Content type, such as `application/json`, `image/jpg` ...
     pub fn content_type(&self) -> Option<&str> {
-        self.0.content_type.as_deref()
+        match &self.0 {
+            UploadInner::File(upload) => upload.content_type.as_deref(),
+            #[cfg(feature = "multipart")]
+            UploadInner::Stored { content_type, .. } => content_type.as_deref(),
+        }
     }
 
     /// Returns the size of the file, in bytes.
+    ///
+    /// This fails for the files that are not stored in files, use
+    /// [`Upload::try_size`](#method.try_size) for them.
     pub fn size(&self) -> std::io::Result<u64> {
-        self.0.content.metadata().map(|meta| meta.len())
+        match &self.0 {
+            UploadInner::File(upload) => upload.content.metadata().map(|meta| meta.len()),
+            #[cfg(feature = "multipart")]
+            UploadInner::Stored { .. } => Err(not_in_file()),
+        }
     }
 
     /// Convert to a `Read`.
     ///
     /// **Note**: this is a *synchronous/blocking* reader.
+    ///
+    /// Reading fails if the file is not stored in a file, use
+    /// [`Upload::try_into_read`](#method.try_into_read) for the other storages.
     pub fn into_read(self) -> impl Read + Sync + Send + 'static {
-        self.0.content
+        match self.0 {
+            UploadInner::File(upload) => UploadRead::File(upload.content),
+            #[cfg(feature = "multipart")]
+            UploadInner::Stored { .. } => UploadRead::NotInFile,
+        }
     }
 
     #[cfg(feature = "unblock")]
     #[cfg_attr(feature = "nightly", doc(cfg(feature = "unblock")))]
     /// Convert to a `AsyncRead`.
+    ///
+    /// Reading fails if the file is not stored in a file, use
+    /// [`Upload::try_into_async_read`](#method.try_into_async_read) for the other storages.
     pub fn into_async_read(self) -> impl futures::AsyncRead + Sync + Send + 'static {
-        blocking::Unblock::new(self.0.content)
+        blocking::Unblock::new(self.into_read())
+    }
+}
+
+enum UploadRead {
+    File(std::fs::File),
+    #[cfg(feature = "multipart")]
+    NotInFile,
+}
+
+impl Read for UploadRead {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            UploadRead::File(file) => file.read(buf),
+            #[cfg(feature = "multipart")]
+            UploadRead::NotInFile => Err(not_in_file()),
+        }
+    }
+}
+
+#[cfg(feature = "multipart")]
+impl Upload {
+    fn content(&self, ctx: &Context<'_>) -> io::Result<UploadContent> {
+        match &self.0 {
+            UploadInner::File(upload) => Ok(UploadContent::file(upload.content.try_clone()?)),
+            UploadInner::Stored { index, .. } => stored_content(ctx, *index),
+        }
+    }
+
+    /// Returns the size of the file, in bytes.
+    ///
+    /// This fails for the files that are stored with `UploadContent::custom`.
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
+    pub fn try_size(&self, ctx: &Context<'_>) -> io::Result<u64> {
+        match &*self.content(ctx)?.0 {
+            UploadData::File(file) => file.metadata().map(|meta| meta.len()),
+            UploadData::Memory(data) => Ok(data.len() as u64),
+            _ => Err(unsupported("size")),
+        }
+    }
+
+    /// Returns the content stored by a custom `UploadStorage` with `UploadContent::custom`.
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
+    pub fn custom<T: Any + Send + Sync>(&self, ctx: &Context<'_>) -> Option<Arc<T>> {
+        match &*self.content(ctx).ok()?.0 {
+            UploadData::Custom(value) => value.clone().downcast().ok(),
+            _ => None,
+        }
+    }
+
+    /// Convert to a `Read`.
+    ///
+    /// This fails for the files that are stored with `UploadContent::custom`.
+    ///
+    /// **Note**: this is a *synchronous/blocking* reader.
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
+    pub fn try_into_read(self, ctx: &Context<'_>) -> io::Result<impl Read + Sync + Send + 'static> {
+        let reader: Box<dyn Read + Sync + Send> = match &*self.content(ctx)?.0 {
+            UploadData::File(file) => {
+                let mut file = file.try_clone()?;
+                file.seek(SeekFrom::Start(0))?;
+                Box::new(file)
+            }
+            UploadData::Memory(data) => Box::new(Cursor::new(data.clone())),
+            _ => return Err(unsupported("read")),
+        };
+        Ok(reader)
+    }
+
+    #[cfg(feature = "unblock")]
+    #[cfg_attr(
+        feature = "nightly",
+        doc(cfg(all(feature = "multipart", feature = "unblock")))
+    )]
+    /// Convert to a `AsyncRead`.
+    ///
+    /// This fails for the files that are stored with `UploadContent::custom`.
+    pub fn try_into_async_read(
+        self,
+        ctx: &Context<'_>,
+    ) -> io::Result<impl futures::AsyncRead + Sync + Send + 'static> {
+        self.try_into_read(ctx).map(blocking::Unblock::new)
+    }
+
+    /// Convert to an async stream of the content.
+    ///
+    /// This fails for the files that are stored with `UploadContent::custom`.
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
+    pub async fn into_stream(self, ctx: &Context<'_>) -> io::Result<UploadStream> {
+        let content = self.content(ctx)?;
+        let (filename, content_type) = match self.0 {
+            UploadInner::File(upload) => (upload.filename, upload.content_type),
+            UploadInner::Stored {
+                filename,
+                content_type,
+                ..
+            } => (filename, content_type),
+        };
+        content.open(filename, content_type).await
     }
 }
 
@@ -84,7 +241,8 @@ impl Type for Upload {
         registry.create_type::<Self, _>(|_| registry::MetaType::Scalar {
             name: Self::type_name().to_string(),
             description: None,
-            is_valid: |value| matches!(value, Value::Upload(_)),
+            is_valid: is_upload,
+            specified_by_url: None,
         })
     }
 }
@@ -93,13 +251,264 @@ impl InputValueType for Upload {
     fn parse(value: Option<Value>) -> InputValueResult<Self> {
         let value = value.unwrap_or_default();
         if let Value::Upload(upload) = value {
-            Ok(Upload(upload))
-        } else {
-            Err(InputValueError::expected_type(value))
+            return Ok(Upload(UploadInner::File(upload)));
+        }
+        #[cfg(feature = "multipart")]
+        {
+            if let Value::StoredUpload(upload) = value {
+                return match upload.filename {
+                    Some(filename) => Ok(Upload(UploadInner::Stored {
+                        index: upload.index,
+                        filename,
+                        content_type: upload.content_type,
+                    })),
+                    None => Err(InputValueError::custom(
+                        "The file is streamed, receive it as a `StreamedUpload`.",
+                    )),
+                };
+            }
+        }
+        Err(InputValueError::expected_type(value))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Null
+    }
+}
+
+#[cfg(not(feature = "multipart"))]
+fn is_upload(value: &Value) -> bool {
+    matches!(value, Value::Upload(_))
+}
+
+#[cfg(feature = "multipart")]
+fn is_upload(value: &Value) -> bool {
+    matches!(value, Value::Upload(_) | Value::StoredUpload(_))
+}
+
+#[cfg(feature = "multipart")]
+fn not_in_file() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        "The upload is not stored in a file, it must be read with the context.",
+    )
+}
+
+#[cfg(feature = "multipart")]
+fn unsupported(operation: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        format!("The upload content does not support {}.", operation),
+    )
+}
+
+#[cfg(feature = "multipart")]
+fn stored_content(ctx: &Context<'_>, index: usize) -> io::Result<UploadContent> {
+    ctx.query_env.uploads.get(index).cloned().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "The upload is missing from the request.",
+        )
+    })
+}
+
+/// Uploaded file that can be streamed from the request body
+///
+/// Use it instead of `Upload` to receive the files that are streamed with
+/// `MultipartOptions::stream_uploads`, it also accepts the files that are stored before the
+/// request is executed.
+///
+/// A streamed file is read from the request body while its stream is polled, and can only be
+/// opened once.
+#[cfg(feature = "multipart")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
+pub struct StreamedUpload(StreamedUploadInner);
+
+#[cfg(feature = "multipart")]
+enum StreamedUploadInner {
+    Upload(Upload),
+    Streamed(usize),
+}
+
+#[cfg(feature = "multipart")]
+impl StreamedUpload {
+    /// Open the file as an async stream of its content.
+    pub async fn open(self, ctx: &Context<'_>) -> io::Result<UploadStream> {
+        match self.0 {
+            StreamedUploadInner::Upload(upload) => upload.into_stream(ctx).await,
+            StreamedUploadInner::Streamed(index) => match &*stored_content(ctx, index)?.0 {
+                UploadData::Lazy(open) => open().await,
+                _ => Err(unsupported("stream")),
+            },
         }
     }
+}
+
+#[cfg(feature = "multipart")]
+impl Type for StreamedUpload {
+    fn type_name() -> Cow<'static, str> {
+        Upload::type_name()
+    }
+
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        Upload::create_type_info(registry)
+    }
+}
+
+#[cfg(feature = "multipart")]
+impl InputValueType for StreamedUpload {
+    fn parse(value: Option<Value>) -> InputValueResult<Self> {
+        let value = value.unwrap_or_default();
+        if let Value::StoredUpload(upload) = &value {
+            if upload.filename.is_none() {
+                return Ok(StreamedUpload(StreamedUploadInner::Streamed(upload.index)));
+            }
+        }
+        if !is_upload(&value) {
+            return Err(InputValueError::expected_type(value));
+        }
+        Upload::parse(Some(value))
+            .map(|upload| StreamedUpload(StreamedUploadInner::Upload(upload)))
+            .map_err(InputValueError::propogate)
+    }
 
     fn to_value(&self) -> Value {
         Value::Null
     }
 }
+
+#[cfg(feature = "multipart")]
+enum UploadData {
+    File(File),
+    Memory(Bytes),
+    Custom(Arc<dyn Any + Send + Sync>),
+    Lazy(Box<dyn Fn() -> BoxFuture<'static, io::Result<UploadStream>> + Send + Sync>),
+}
+
+/// The content of an uploaded file
+///
+/// It is created by an `UploadStorage` when a multipart request is received.
+#[cfg(feature = "multipart")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
+#[derive(Clone)]
+pub struct UploadContent(Arc<UploadData>);
+
+#[cfg(feature = "multipart")]
+impl UploadContent {
+    /// Create a content that is stored in a file.
+    ///
+    /// The file is received as an `Upload` that can be read without the context.
+    pub fn file(file: File) -> Self {
+        Self(Arc::new(UploadData::File(file)))
+    }
+
+    /// Create a content that is stored in memory.
+    pub fn memory(data: impl Into<Bytes>) -> Self {
+        Self(Arc::new(UploadData::Memory(data.into())))
+    }
+
+    /// Create a content that is stored elsewhere, such as the key of an object in an object
+    /// storage. It can be got with `Upload::custom`.
+    pub fn custom<T: Any + Send + Sync>(value: T) -> Self {
+        Self(Arc::new(UploadData::Custom(Arc::new(value))))
+    }
+
+    /// Create a content that is read from the request body when it is opened.
+    pub(crate) fn lazy<F>(f: F) -> Self
+    where
+        F: Fn() -> BoxFuture<'static, io::Result<UploadStream>> + Send + Sync + 'static,
+    {
+        Self(Arc::new(UploadData::Lazy(Box::new(f))))
+    }
+
+    /// Returns the file if the content is stored in a file.
+    pub(crate) fn try_clone_file(&self) -> Option<io::Result<File>> {
+        match &*self.0 {
+            UploadData::File(file) => Some(file.try_clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) async fn open(
+        &self,
+        filename: String,
+        content_type: Option<String>,
+    ) -> io::Result<UploadStream> {
+        let content = match &*self.0 {
+            UploadData::File(file) => {
+                let mut file = file.try_clone()?;
+                file.seek(SeekFrom::Start(0))?;
+                futures::stream::try_unfold(file, |mut file| async move {
+                    let mut buf = vec![0; 8192];
+                    match file.read(&mut buf)? {
+                        0 => Ok(None),
+                        size => {
+                            buf.truncate(size);
+                            Ok(Some((Bytes::from(buf), file)))
+                        }
+                    }
+                })
+                .boxed()
+            }
+            UploadData::Memory(data) => {
+                futures::stream::once(futures::future::ready(Ok(data.clone()))).boxed()
+            }
+            UploadData::Custom(_) => return Err(unsupported("stream")),
+            UploadData::Lazy(open) => return open().await,
+        };
+        Ok(UploadStream::new(filename, content_type, content))
+    }
+}
+
+#[cfg(feature = "multipart")]
+impl From<File> for UploadContent {
+    fn from(file: File) -> Self {
+        Self::file(file)
+    }
+}
+
+/// The content of an uploaded file as an async stream, see `Upload::into_stream`.
+#[cfg(feature = "multipart")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "multipart")))]
+pub struct UploadStream {
+    filename: String,
+    content_type: Option<String>,
+    content: BoxStream<'static, io::Result<Bytes>>,
+}
+
+#[cfg(feature = "multipart")]
+impl UploadStream {
+    pub(crate) fn new(
+        filename: String,
+        content_type: Option<String>,
+        content: BoxStream<'static, io::Result<Bytes>>,
+    ) -> Self {
+        Self {
+            filename,
+            content_type,
+            content,
+        }
+    }
+
+    /// Filename
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// Content type, such as `application/json`, `image/jpg` ...
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+}
+
+#[cfg(feature = "multipart")]
+impl Stream for UploadStream {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.content.poll_next_unpin(cx)
+    }
+}
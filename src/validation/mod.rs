@@ -10,13 +10,15 @@ mod visitors;
 
 use crate::parser::types::ExecutableDocument;
 use crate::registry::Registry;
-use crate::{CacheControl, ServerError, Variables};
-use visitor::{visit, VisitorContext, VisitorNil};
+use crate::{CacheControl, ContextSelectionSet, ServerError, Variables};
+use visitor::{visit, visit_operation, VisitorContext, VisitorNil};
 
 pub struct CheckResult {
     pub cache_control: CacheControl,
     pub complexity: usize,
     pub depth: usize,
+    pub introspection_depth: usize,
+    pub introspection_breadth: usize,
 }
 
 /// Validation mode
@@ -34,11 +36,17 @@ pub fn check_rules(
     doc: &ExecutableDocument,
     variables: Option<&Variables>,
     mode: ValidationMode,
+    limit_introspection_depth: Option<usize>,
+    limit_introspection_breadth: Option<usize>,
 ) -> Result<CheckResult, Vec<ServerError>> {
     let mut ctx = VisitorContext::new(registry, doc, variables);
     let mut cache_control = CacheControl::default();
     let mut complexity = 0;
     let mut depth = 0;
+    let mut introspection_depth = 0;
+    let mut introspection_breadth = 0;
+    let skip_introspection =
+        limit_introspection_depth.is_some() || limit_introspection_breadth.is_some();
 
     match mode {
         ValidationMode::Strict => {
@@ -69,8 +77,18 @@ pub fn check_rules(
                 })
                 .with(visitors::ComplexityCalculate {
                     complexity: &mut complexity,
+                    skip_introspection,
                 })
-                .with(visitors::DepthCalculate::new(&mut depth));
+                .with(visitors::DepthCalculate::new(
+                    &mut depth,
+                    skip_introspection,
+                ))
+                .with(visitors::IntrospectionCalculate::new(
+                    &mut introspection_depth,
+                    &mut introspection_breadth,
+                    limit_introspection_depth,
+                    limit_introspection_breadth,
+                ));
             visit(&mut visitor, &mut ctx, doc);
         }
         ValidationMode::Fast => {
@@ -82,8 +100,18 @@ pub fn check_rules(
                 })
                 .with(visitors::ComplexityCalculate {
                     complexity: &mut complexity,
+                    skip_introspection,
                 })
-                .with(visitors::DepthCalculate::new(&mut depth));
+                .with(visitors::DepthCalculate::new(
+                    &mut depth,
+                    skip_introspection,
+                ))
+                .with(visitors::IntrospectionCalculate::new(
+                    &mut introspection_depth,
+                    &mut introspection_breadth,
+                    limit_introspection_depth,
+                    limit_introspection_breadth,
+                ));
             visit(&mut visitor, &mut ctx, doc);
         }
     }
@@ -96,5 +124,23 @@ pub fn check_rules(
         cache_control,
         complexity,
         depth: depth as usize,
+        introspection_depth,
+        introspection_breadth,
     })
 }
+
+/// Reject the fields of the operation being executed that are not visible to the request.
+pub(crate) fn check_visibility(ctx: &ContextSelectionSet<'_>) -> Result<(), Vec<ServerError>> {
+    let mut visitor_ctx = VisitorContext::with_query_ctx(ctx);
+    visit_operation(
+        &mut rules::FieldsOnCorrectType,
+        &mut visitor_ctx,
+        &ctx.query_env.fragments,
+        &ctx.query_env.operation,
+    );
+
+    if !visitor_ctx.errors.is_empty() {
+        return Err(visitor_ctx.errors.into_iter().map(Into::into).collect());
+    }
+    Ok(())
+}
@@ -17,10 +17,20 @@ impl<'a> Visitor<'a> for FieldsOnCorrectType {
                 }
             }
 
-            if parent_type
+            // Without the context of the request every field is visible.
+            let registry = ctx.registry;
+            let query_ctx = ctx.query_ctx;
+            let is_visible = |meta_field: &registry::MetaField| match query_ctx {
+                Some(query_ctx) => {
+                    registry.is_visible_field(&query_ctx.visible_context(), meta_field)
+                }
+                None => true,
+            };
+
+            if !parent_type
                 .fields()
                 .and_then(|fields| fields.get(field.node.name.node.as_str()))
-                .is_none()
+                .map_or(false, is_visible)
                 && !field
                     .node
                     .directives
@@ -38,9 +48,10 @@ impl<'a> Visitor<'a> for FieldsOnCorrectType {
                             parent_type
                                 .fields()
                                 .iter()
-                                .map(|fields| fields.keys())
+                                .map(|fields| fields.values())
                                 .flatten()
-                                .map(|s| s.as_str()),
+                                .filter(|meta_field| is_visible(meta_field))
+                                .map(|meta_field| meta_field.name.as_str()),
                             &field.node.name.node,
                         )
                         .unwrap_or_default()
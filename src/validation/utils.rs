@@ -117,6 +117,7 @@ pub fn is_valid_input_value(
                 registry::MetaType::InputObject {
                     input_fields,
                     name: object_name,
+                    validator,
                     ..
                 } => match value {
                     ConstValue::Object(values) => {
@@ -171,6 +172,12 @@ pub fn is_valid_input_value(
                             ));
                         }
 
+                        if let Some(validator) = validator {
+                            if let Err(reason) = validator.is_valid(value) {
+                                return Some(valid_error(&path_node, reason));
+                            }
+                        }
+
                         None
                     }
                     _ => None,
@@ -4,7 +4,7 @@ use crate::parser::types::{
     VariableDefinition,
 };
 use crate::registry::{self, MetaType, MetaTypeName};
-use crate::{Pos, Positioned, ServerError, Variables};
+use crate::{ContextSelectionSet, Pos, Positioned, ServerError, Variables};
 use std::collections::HashMap;
 use std::fmt::{self, Display, Formatter};
 
@@ -12,6 +12,8 @@ pub(crate) struct VisitorContext<'a> {
     pub(crate) registry: &'a registry::Registry,
     pub(crate) variables: Option<&'a Variables>,
     pub(crate) errors: Vec<RuleError>,
+    /// The context of the request, used to decide which fields are visible.
+    pub(crate) query_ctx: Option<&'a ContextSelectionSet<'a>>,
     type_stack: Vec<Option<&'a registry::MetaType>>,
     input_type: Vec<Option<MetaTypeName<'a>>>,
     fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
@@ -27,12 +29,25 @@ impl<'a> VisitorContext<'a> {
             registry,
             variables,
             errors: Default::default(),
+            query_ctx: None,
             type_stack: Default::default(),
             input_type: Default::default(),
             fragments: &doc.fragments,
         }
     }
 
+    pub(crate) fn with_query_ctx(query_ctx: &'a ContextSelectionSet<'a>) -> Self {
+        Self {
+            registry: &query_ctx.schema_env.registry,
+            variables: Some(&query_ctx.query_env.variables),
+            errors: Default::default(),
+            query_ctx: Some(query_ctx),
+            type_stack: Default::default(),
+            input_type: Default::default(),
+            fragments: &query_ctx.query_env.fragments,
+        }
+    }
+
     pub(crate) fn report_error<T: Into<String>>(&mut self, locations: Vec<Pos>, msg: T) {
         self.errors.push(RuleError {
             locations,
@@ -462,8 +477,32 @@ pub(crate) fn visit<'a, V: Visitor<'a>>(
     doc: &'a ExecutableDocument,
 ) {
     v.enter_document(ctx, doc);
+    visit_fragment_definitions(v, ctx, &doc.fragments);
+
+    for (name, operation) in doc.operations.iter() {
+        visit_operation_definition(v, ctx, name, operation);
+    }
+
+    v.exit_document(ctx, doc);
+}
 
-    for (name, fragment) in &doc.fragments {
+/// Visit only the operation being executed and the fragments of the request.
+pub(crate) fn visit_operation<'a, V: Visitor<'a>>(
+    v: &mut V,
+    ctx: &mut VisitorContext<'a>,
+    fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
+    operation: &'a Positioned<OperationDefinition>,
+) {
+    visit_fragment_definitions(v, ctx, fragments);
+    visit_operation_definition(v, ctx, None, operation);
+}
+
+fn visit_fragment_definitions<'a, V: Visitor<'a>>(
+    v: &mut V,
+    ctx: &mut VisitorContext<'a>,
+    fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
+) {
+    for (name, fragment) in fragments {
         ctx.with_type(
             ctx.registry
                 .types
@@ -471,12 +510,6 @@ pub(crate) fn visit<'a, V: Visitor<'a>>(
             |ctx| visit_fragment_definition(v, ctx, name, fragment),
         )
     }
-
-    for (name, operation) in doc.operations.iter() {
-        visit_operation_definition(v, ctx, name, operation);
-    }
-
-    v.exit_document(ctx, doc);
 }
 
 fn visit_operation_definition<'a, V: Visitor<'a>>(
@@ -507,7 +540,7 @@ fn visit_operation_definition<'a, V: Visitor<'a>>(
     v.exit_operation_definition(ctx, name, operation);
 }
 
-fn visit_selection_set<'a, V: Visitor<'a>>(
+pub(crate) fn visit_selection_set<'a, V: Visitor<'a>>(
     v: &mut V,
     ctx: &mut VisitorContext<'a>,
     selection_set: &'a Positioned<SelectionSet>,
@@ -1,13 +1,21 @@
 use crate::parser::types::Field;
 use crate::validation::visitor::{Visitor, VisitorContext};
+use crate::validation::visitors::is_introspection_type;
 use crate::Positioned;
 
 pub struct ComplexityCalculate<'a> {
     pub complexity: &'a mut usize,
+    pub skip_introspection: bool,
 }
 
 impl<'ctx, 'a> Visitor<'ctx> for ComplexityCalculate<'a> {
-    fn enter_field(&mut self, _ctx: &mut VisitorContext<'_>, _field: &Positioned<Field>) {
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'_>, _field: &Positioned<Field>) {
+        if self.skip_introspection
+            && (is_introspection_type(ctx.parent_type())
+                || is_introspection_type(ctx.current_type()))
+        {
+            return;
+        }
         *self.complexity += 1;
     }
 }
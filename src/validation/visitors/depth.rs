@@ -1,37 +1,50 @@
 use crate::parser::types::{FragmentSpread, InlineFragment, SelectionSet};
 use crate::validation::visitor::{Visitor, VisitorContext};
+use crate::validation::visitors::is_introspection_type;
 use crate::Positioned;
 
 pub struct DepthCalculate<'a> {
     max_depth: &'a mut i32,
     current_depth: i32,
+    skip_introspection: bool,
 }
 
 impl<'a> DepthCalculate<'a> {
-    pub fn new(max_depth: &'a mut i32) -> Self {
+    pub fn new(max_depth: &'a mut i32, skip_introspection: bool) -> Self {
         *max_depth = -1;
         Self {
             max_depth,
             current_depth: -1,
+            skip_introspection,
         }
     }
+
+    fn is_skipped(&self, ctx: &VisitorContext<'_>) -> bool {
+        self.skip_introspection && is_introspection_type(ctx.current_type())
+    }
 }
 
 impl<'ctx, 'a> Visitor<'ctx> for DepthCalculate<'a> {
     fn enter_selection_set(
         &mut self,
-        _ctx: &mut VisitorContext<'ctx>,
+        ctx: &mut VisitorContext<'ctx>,
         _selection_set: &'ctx Positioned<SelectionSet>,
     ) {
+        if self.is_skipped(ctx) {
+            return;
+        }
         self.current_depth += 1;
         *self.max_depth = (*self.max_depth).max(self.current_depth);
     }
 
     fn exit_selection_set(
         &mut self,
-        _ctx: &mut VisitorContext<'ctx>,
+        ctx: &mut VisitorContext<'ctx>,
         _selection_set: &'ctx Positioned<SelectionSet>,
     ) {
+        if self.is_skipped(ctx) {
+            return;
+        }
         self.current_depth -= 1;
     }
 
@@ -53,17 +66,23 @@ impl<'ctx, 'a> Visitor<'ctx> for DepthCalculate<'a> {
 
     fn enter_inline_fragment(
         &mut self,
-        _ctx: &mut VisitorContext<'ctx>,
+        ctx: &mut VisitorContext<'ctx>,
         _inline_fragment: &'ctx Positioned<InlineFragment>,
     ) {
+        if self.is_skipped(ctx) {
+            return;
+        }
         self.current_depth -= 1;
     }
 
     fn exit_inline_fragment(
         &mut self,
-        _ctx: &mut VisitorContext<'ctx>,
+        ctx: &mut VisitorContext<'ctx>,
         _inline_fragment: &'ctx Positioned<InlineFragment>,
     ) {
+        if self.is_skipped(ctx) {
+            return;
+        }
         self.current_depth += 1;
     }
 }
@@ -0,0 +1,139 @@
+use crate::parser::types::{Field, FragmentDefinition, FragmentSpread, Name, OperationDefinition};
+use crate::validation::visitor::{visit_selection_set, Visitor, VisitorContext};
+use crate::validation::visitors::is_introspection_type;
+use crate::Positioned;
+
+/// Calculates the depth and breadth of the introspection fields of the operations.
+///
+/// The depth is the number of nested fields returning `__Type`, such as `ofType` or `type`, and
+/// the breadth is the largest number of introspection fields selected on a single object.
+///
+/// Fragment spreads are expanded in place so that nested fragments can't be used to get around
+/// the limits. The expansion stops as soon as one of the limits is exceeded.
+pub struct IntrospectionCalculate<'ctx, 'a> {
+    depth: &'a mut usize,
+    breadth: &'a mut usize,
+    limit_depth: Option<usize>,
+    limit_breadth: Option<usize>,
+    current_depth: usize,
+    breadth_stack: Vec<usize>,
+    fragment_stack: Vec<&'ctx str>,
+    in_fragment_definition: bool,
+}
+
+impl<'ctx, 'a> IntrospectionCalculate<'ctx, 'a> {
+    pub fn new(
+        depth: &'a mut usize,
+        breadth: &'a mut usize,
+        limit_depth: Option<usize>,
+        limit_breadth: Option<usize>,
+    ) -> Self {
+        *depth = 0;
+        *breadth = 0;
+        Self {
+            depth,
+            breadth,
+            limit_depth,
+            limit_breadth,
+            current_depth: 0,
+            breadth_stack: vec![0],
+            fragment_stack: Vec::new(),
+            in_fragment_definition: false,
+        }
+    }
+
+    fn is_exceeded(&self) -> bool {
+        self.limit_depth.map_or(false, |limit| *self.depth > limit)
+            || self
+                .limit_breadth
+                .map_or(false, |limit| *self.breadth > limit)
+    }
+}
+
+impl<'ctx, 'a> Visitor<'ctx> for IntrospectionCalculate<'ctx, 'a> {
+    fn enter_operation_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        _name: Option<&'ctx Name>,
+        _operation_definition: &'ctx Positioned<OperationDefinition>,
+    ) {
+        self.current_depth = 0;
+        self.breadth_stack = vec![0];
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        _name: &'ctx Name,
+        _fragment_definition: &'ctx Positioned<FragmentDefinition>,
+    ) {
+        // Fragments are calculated where they are spread.
+        self.in_fragment_definition = true;
+    }
+
+    fn exit_fragment_definition(
+        &mut self,
+        _ctx: &mut VisitorContext<'ctx>,
+        _name: &'ctx Name,
+        _fragment_definition: &'ctx Positioned<FragmentDefinition>,
+    ) {
+        self.in_fragment_definition = false;
+    }
+
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'ctx>, _field: &'ctx Positioned<Field>) {
+        if self.in_fragment_definition {
+            return;
+        }
+
+        if is_introspection_type(ctx.parent_type()) || is_introspection_type(ctx.current_type()) {
+            if let Some(count) = self.breadth_stack.last_mut() {
+                *count += 1;
+                *self.breadth = (*self.breadth).max(*count);
+            }
+        }
+        if ctx.current_type().map(|ty| ty.name()) == Some("__Type") {
+            self.current_depth += 1;
+            *self.depth = (*self.depth).max(self.current_depth);
+        }
+        self.breadth_stack.push(0);
+    }
+
+    fn exit_field(&mut self, ctx: &mut VisitorContext<'ctx>, _field: &'ctx Positioned<Field>) {
+        if self.in_fragment_definition {
+            return;
+        }
+
+        self.breadth_stack.pop();
+        if ctx.current_type().map(|ty| ty.name()) == Some("__Type") {
+            self.current_depth -= 1;
+        }
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        ctx: &mut VisitorContext<'ctx>,
+        fragment_spread: &'ctx Positioned<FragmentSpread>,
+    ) {
+        if self.in_fragment_definition
+            || (self.limit_depth.is_none() && self.limit_breadth.is_none())
+            || self.is_exceeded()
+        {
+            return;
+        }
+
+        let name = fragment_spread.node.fragment_name.node.as_str();
+        if self.fragment_stack.contains(&name) {
+            return;
+        }
+        if let Some(fragment) = ctx.fragment(name) {
+            self.fragment_stack.push(name);
+            ctx.with_type(
+                ctx.registry
+                    .types
+                    .get(fragment.node.type_condition.node.on.node.as_str()),
+                |ctx| visit_selection_set(self, ctx, &fragment.node.selection_set),
+            );
+            self.fragment_stack.pop();
+        }
+    }
+}
@@ -1,7 +1,16 @@
 mod cache_control;
 mod complexity;
 mod depth;
+mod introspection;
+
+use crate::registry::MetaType;
 
 pub use cache_control::CacheControlCalculate;
 pub use complexity::ComplexityCalculate;
 pub use depth::DepthCalculate;
+pub use introspection::IntrospectionCalculate;
+
+/// Introspection fields are skipped by the other calculations when they are limited separately.
+fn is_introspection_type(ty: Option<&MetaType>) -> bool {
+    ty.map_or(false, |ty| ty.name().starts_with("__"))
+}
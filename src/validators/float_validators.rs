@@ -0,0 +1,27 @@
+use crate::validators::InputValueValidator;
+use crate::Value;
+
+/// Float range validator
+pub struct FloatRange {
+    /// Minimum value, including this value.
+    pub min: f64,
+
+    /// Maximum value, including this value.
+    pub max: f64,
+}
+
+impl InputValueValidator for FloatRange {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::Number(n) = value {
+            if let Some(n) = n.as_f64() {
+                if n < self.min || n > self.max {
+                    return Err(format!(
+                        "the value is {}, must be between {} and {}",
+                        n, self.min, self.max
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -48,3 +48,38 @@ impl InputValueValidator for ListMaxLength {
         }
     }
 }
+
+/// List unique validator
+pub struct ListUnique {}
+
+impl InputValueValidator for ListUnique {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::List(values) = value {
+            for (idx, value) in values.iter().enumerate() {
+                if values[..idx].contains(value) {
+                    return Err(format!("the item at index {} is a duplicate", idx));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// List items validator, checks every item of the list with another validator
+pub struct ListItems<V>(
+    /// The validator of the items.
+    pub V,
+);
+
+impl<V: InputValueValidator> InputValueValidator for ListItems<V> {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::List(values) = value {
+            for (idx, value) in values.iter().enumerate() {
+                self.0.is_valid(value).map_err(|reason| {
+                    format!("the item at index {} is invalid, {}", idx, reason)
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
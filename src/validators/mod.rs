@@ -1,14 +1,23 @@
 //! Input value validators
 
+mod float_validators;
 mod int_validators;
 mod list_validators;
 mod string_validators;
 
-use crate::Value;
+use crate::{Context, InputValueType, Value};
 
+pub use float_validators::FloatRange;
 pub use int_validators::{IntEqual, IntGreaterThan, IntLessThan, IntNonZero, IntRange};
-pub use list_validators::{ListMaxLength, ListMinLength};
-pub use string_validators::{Email, StringMaxLength, StringMinLength, MAC};
+pub use list_validators::{ListItems, ListMaxLength, ListMinLength, ListUnique};
+#[cfg(feature = "url")]
+pub use string_validators::Url;
+#[cfg(feature = "uuid")]
+pub use string_validators::Uuid;
+pub use string_validators::{
+    Charset, Cidr, Email, IpAddr, Regex, RegexPattern, StringCharset, StringMaxLength,
+    StringMinLength, MAC,
+};
 
 /// Input value validator
 ///
@@ -18,7 +27,7 @@ pub use string_validators::{Email, StringMaxLength, StringMinLength, MAC};
 ///
 /// ```no_run
 /// use async_graphql::*;
-/// use async_graphql::validators::{Email, MAC, IntRange};
+/// use async_graphql::validators::{Email, MAC, IntRange, ListItems, ListUnique, Regex};
 ///
 /// struct QueryRoot;
 ///
@@ -38,6 +47,11 @@ pub use string_validators::{Email, StringMaxLength, StringMinLength, MAC};
 ///     async fn value3(&self, #[graphql(validator(IntRange(min = "100", max = "200")))] value: i32) -> i32 {
 ///         unimplemented!()
 ///     }
+///
+///     // Input is a list of distinct lowercase words
+///     async fn value4(&self, #[graphql(validator(and(ListUnique, ListItems(Regex("[a-z]+")))))] words: Vec<String>) -> i32 {
+///         unimplemented!()
+///     }
 /// }
 /// ```
 pub trait InputValueValidator
@@ -50,6 +64,59 @@ where
     fn is_valid(&self, value: &Value) -> Result<(), String>;
 }
 
+/// Asynchronous input value validator
+///
+/// It runs while the arguments of a field are parsed, so unlike
+/// [`InputValueValidator`](trait.InputValueValidator.html) it can access the context and
+/// receives the parsed value. Attach it to an argument with `#[graphql(validator(async = "..."))]`.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::validators::AsyncInputValueValidator;
+///
+/// struct Slugs(Vec<String>);
+///
+/// struct SlugNotTaken;
+///
+/// #[async_trait::async_trait]
+/// impl AsyncInputValueValidator<String> for SlugNotTaken {
+///     async fn is_valid(&self, ctx: &Context<'_>, value: &String) -> Result<(), String> {
+///         if ctx.data_unchecked::<Slugs>().0.contains(value) {
+///             Err(format!("the slug \"{}\" is already taken", value))
+///         } else {
+///             Ok(())
+///         }
+///     }
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn create(&self, #[graphql(validator(async = "SlugNotTaken"))] slug: String) -> String {
+///         slug
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///         .data(Slugs(vec!["abc".to_string()]))
+///         .finish();
+///     assert!(schema.execute(r#"{ create(slug: "def") }"#).await.is_ok());
+///     assert_eq!(
+///         schema.execute(r#"{ create(slug: "abc") }"#).await.errors[0].message,
+///         "Invalid value for argument \"slug\", the slug \"abc\" is already taken",
+///     );
+/// });
+/// ```
+#[async_trait::async_trait]
+pub trait AsyncInputValueValidator<T: Sync>: Send + Sync {
+    /// Check value is valid, returns the reason for the error if it fails.
+    async fn is_valid(&self, ctx: &Context<'_>, value: &T) -> Result<(), String>;
+}
+
 /// An extension trait for `InputValueValidator`
 pub trait InputValueValidatorExt: InputValueValidator + Sized {
     /// Merge the two validators and return None only if both validators are successful.
@@ -113,3 +180,33 @@ where
         self.0.is_valid(value).map_err(&self.1)
     }
 }
+
+/// Validator that inverts another validator, the value is valid only if the other validator fails
+pub struct Not<V>(
+    /// The validator to invert.
+    pub V,
+);
+
+impl<V: InputValueValidator> InputValueValidator for Not<V> {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if self.0.is_valid(value).is_ok() {
+            Err("the value is not allowed".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Validator for input objects that checks the parsed object with a function
+///
+/// This is what `#[graphql(validator = "...")]` on `#[derive(InputObject)]` generates.
+pub struct InputObjectValidator<T>(pub fn(&T) -> Result<(), String>);
+
+impl<T: InputValueType> InputValueValidator for InputObjectValidator<T> {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        match T::parse(Some(value.clone())) {
+            Ok(obj) => (self.0)(&obj),
+            Err(_) => Ok(()),
+        }
+    }
+}
@@ -1,7 +1,6 @@
 use crate::validators::InputValueValidator;
 use crate::Value;
 use once_cell::sync::Lazy;
-use regex::Regex;
 
 /// String minimum length validator
 pub struct StringMinLength {
@@ -51,8 +50,8 @@ impl InputValueValidator for StringMaxLength {
     }
 }
 
-static EMAIL_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new("^(([0-9A-Za-z!#$%&'*+-/=?^_`{|}~&&[^@]]+)|(\"([0-9A-Za-z!#$%&'*+-/=?^_`{|}~ \"(),:;<>@\\[\\\\\\]]+)\"))@").unwrap()
+static EMAIL_RE: Lazy<regex::Regex> = Lazy::new(|| {
+    regex::Regex::new("^(([0-9A-Za-z!#$%&'*+-/=?^_`{|}~&&[^@]]+)|(\"([0-9A-Za-z!#$%&'*+-/=?^_`{|}~ \"(),:;<>@\\[\\\\\\]]+)\"))@").unwrap()
 });
 
 /// Email validator
@@ -72,10 +71,10 @@ impl InputValueValidator for Email {
     }
 }
 
-static MAC_ADDRESS_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new("^([0-9a-fA-F]{2}:){5}[0-9a-fA-F]{2}$").unwrap());
-static MAC_ADDRESS_NO_COLON_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new("^[0-9a-fA-F]{12}$").unwrap());
+static MAC_ADDRESS_RE: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new("^([0-9a-fA-F]{2}:){5}[0-9a-fA-F]{2}$").unwrap());
+static MAC_ADDRESS_NO_COLON_RE: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new("^[0-9a-fA-F]{12}$").unwrap());
 
 /// MAC address validator
 pub struct MAC {
@@ -102,3 +101,206 @@ impl InputValueValidator for MAC {
         }
     }
 }
+
+/// Regular expression validator
+pub struct Regex(
+    /// The pattern that the whole value must match.
+    pub RegexPattern,
+);
+
+impl InputValueValidator for Regex {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::String(s) = value {
+            if !self.0.regex.is_match(s) {
+                Err(format!(
+                    "the value does not match the pattern \"{}\"",
+                    self.0.pattern
+                ))
+            } else {
+                Ok(())
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A compiled pattern of the [`Regex`](struct.Regex.html) validator.
+///
+/// The pattern is compiled when the validator is created, so an invalid pattern panics while the
+/// schema is built.
+pub struct RegexPattern {
+    pattern: String,
+    regex: regex::Regex,
+}
+
+impl RegexPattern {
+    /// Compile a pattern that the whole value must match.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern is not a valid regular expression.
+    pub fn new(pattern: impl Into<String>) -> Self {
+        let pattern = pattern.into();
+        let regex = regex::Regex::new(&format!("^(?:{})$", pattern))
+            .unwrap_or_else(|err| panic!("invalid regular expression \"{}\": {}", pattern, err));
+        Self { pattern, regex }
+    }
+
+    /// Returns the source of the pattern.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+}
+
+impl From<&str> for RegexPattern {
+    fn from(pattern: &str) -> Self {
+        Self::new(pattern)
+    }
+}
+
+impl From<String> for RegexPattern {
+    fn from(pattern: String) -> Self {
+        Self::new(pattern)
+    }
+}
+
+/// URL validator
+#[cfg(feature = "url")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "url")))]
+pub struct Url {}
+
+#[cfg(feature = "url")]
+impl InputValueValidator for Url {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::String(s) = value {
+            if url::Url::parse(s).is_err() {
+                Err("invalid URL format".to_string())
+            } else {
+                Ok(())
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// UUID validator
+#[cfg(feature = "uuid")]
+#[cfg_attr(feature = "nightly", doc(cfg(feature = "uuid")))]
+pub struct Uuid {}
+
+#[cfg(feature = "uuid")]
+impl InputValueValidator for Uuid {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::String(s) = value {
+            if uuid::Uuid::parse_str(s).is_err() {
+                Err("invalid UUID format".to_string())
+            } else {
+                Ok(())
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// IP address validator
+pub struct IpAddr {}
+
+impl InputValueValidator for IpAddr {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::String(s) = value {
+            if s.parse::<std::net::IpAddr>().is_err() {
+                Err("invalid IP address format".to_string())
+            } else {
+                Ok(())
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// CIDR notation validator, such as `192.168.0.0/16` or `2001:db8::/32`
+pub struct Cidr {}
+
+impl InputValueValidator for Cidr {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::String(s) = value {
+            let valid = match s.find('/') {
+                Some(idx) => {
+                    let max_prefix = match s[..idx].parse::<std::net::IpAddr>() {
+                        Ok(std::net::IpAddr::V4(_)) => 32,
+                        Ok(std::net::IpAddr::V6(_)) => 128,
+                        Err(_) => 0,
+                    };
+                    match s[idx + 1..].parse::<u8>() {
+                        Ok(prefix) => max_prefix > 0 && prefix <= max_prefix,
+                        Err(_) => false,
+                    }
+                }
+                None => false,
+            };
+            if !valid {
+                Err("invalid CIDR format".to_string())
+            } else {
+                Ok(())
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A set of characters for the [`StringCharset`](struct.StringCharset.html) validator
+pub enum Charset {
+    /// ASCII characters.
+    Ascii,
+    /// ASCII letters.
+    AsciiAlphabetic,
+    /// ASCII letters and digits.
+    AsciiAlphanumeric,
+    /// ASCII digits.
+    AsciiDigit,
+    /// ASCII hexadecimal digits.
+    AsciiHexDigit,
+    /// Unicode letters.
+    Alphabetic,
+    /// Unicode letters and numbers.
+    Alphanumeric,
+}
+
+impl Charset {
+    fn contains(&self, c: char) -> bool {
+        match self {
+            Charset::Ascii => c.is_ascii(),
+            Charset::AsciiAlphabetic => c.is_ascii_alphabetic(),
+            Charset::AsciiAlphanumeric => c.is_ascii_alphanumeric(),
+            Charset::AsciiDigit => c.is_ascii_digit(),
+            Charset::AsciiHexDigit => c.is_ascii_hexdigit(),
+            Charset::Alphabetic => c.is_alphabetic(),
+            Charset::Alphanumeric => c.is_alphanumeric(),
+        }
+    }
+}
+
+/// String charset validator
+pub struct StringCharset {
+    /// All characters must be in this set.
+    pub charset: Charset,
+}
+
+impl InputValueValidator for StringCharset {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::String(s) = value {
+            if let Some(c) = s.chars().find(|c| !self.charset.contains(*c)) {
+                Err(format!("the value contains the invalid character {:?}", c))
+            } else {
+                Ok(())
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -137,3 +137,196 @@ pub async fn test_complexity_and_depth() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_introspection_depth_and_breadth() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            1
+        }
+    }
+
+    let introspection_query = r#"
+        query IntrospectionQuery {
+            __schema {
+                queryType { name }
+                mutationType { name }
+                subscriptionType { name }
+                types { ...FullType }
+                directives {
+                    name
+                    description
+                    locations
+                    args { ...InputValue }
+                }
+            }
+        }
+
+        fragment FullType on __Type {
+            kind
+            name
+            description
+            fields(includeDeprecated: true) {
+                name
+                description
+                args { ...InputValue }
+                type { ...TypeRef }
+                isDeprecated
+                deprecationReason
+            }
+            inputFields { ...InputValue }
+            interfaces { ...TypeRef }
+            enumValues(includeDeprecated: true) {
+                name
+                description
+                isDeprecated
+                deprecationReason
+            }
+            possibleTypes { ...TypeRef }
+        }
+
+        fragment InputValue on __InputValue {
+            name
+            description
+            type { ...TypeRef }
+            defaultValue
+        }
+
+        fragment TypeRef on __Type {
+            kind
+            name
+            ofType {
+                kind
+                name
+                ofType {
+                    kind
+                    name
+                    ofType {
+                        kind
+                        name
+                        ofType {
+                            kind
+                            name
+                            ofType {
+                                kind
+                                name
+                                ofType {
+                                    kind
+                                    name
+                                    ofType {
+                                        kind
+                                        name
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    "#;
+
+    // introspection fields are counted by the depth and complexity limits by default
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_depth(1)
+        .finish();
+    assert_eq!(
+        schema
+            .execute(introspection_query)
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Query is nested too deep.".to_owned(),
+            locations: Vec::new(),
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_complexity(2)
+        .finish();
+    assert!(schema.execute(introspection_query).await.is_err());
+
+    // they are not counted once an introspection limit is set
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_depth(1)
+        .limit_complexity(2)
+        .limit_introspection_depth(9)
+        .finish();
+    assert!(schema.execute(introspection_query).await.is_ok());
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_introspection_depth(9)
+        .limit_introspection_breadth(8)
+        .finish();
+    assert!(schema.execute(introspection_query).await.is_ok());
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_introspection_depth(8)
+        .finish();
+    assert_eq!(
+        schema
+            .execute(introspection_query)
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Introspection query is nested too deep.".to_owned(),
+            locations: Vec::new(),
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_introspection_breadth(7)
+        .finish();
+    assert_eq!(
+        schema
+            .execute(introspection_query)
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Introspection query is too broad.".to_owned(),
+            locations: Vec::new(),
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .limit_introspection_depth(2)
+        .limit_introspection_breadth(3)
+        .finish();
+    assert!(schema
+        .execute(r#"{ __type(name: "Query") { fields { type { name } } } }"#)
+        .await
+        .is_ok());
+    assert!(schema
+        .execute(r#"{ __type(name: "Query") { fields { type { fields { type { name } } } } } }"#)
+        .await
+        .is_err());
+    assert!(schema
+        .execute(r#"{ a: __type(name: "Query") { name } b: __type(name: "Query") { name } c: __type(name: "Query") { name } d: __type(name: "Query") { name } }"#)
+        .await
+        .is_err());
+
+    // nested fragments are expanded
+    assert!(schema
+        .execute(
+            r#"
+            { __type(name: "Query") { ...A } }
+            fragment A on __Type { ofType { ...B } }
+            fragment B on __Type { ofType { ...C } }
+            fragment C on __Type { ofType { name } }
+            "#
+        )
+        .await
+        .is_err());
+}
@@ -92,3 +92,428 @@ pub async fn test_connection_additional_fields() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_opaque_cursor() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn opaque(
+            &self,
+            after: Option<String>,
+            before: Option<String>,
+            first: Option<i32>,
+            last: Option<i32>,
+        ) -> Result<Connection<OpaqueCursor<(i32, String)>, i32>> {
+            connection::query(
+                after,
+                before,
+                first,
+                last,
+                |after, before, first, last| async move {
+                    Ok(Connection::from_sorted_keyset(
+                        0..10,
+                        |n| OpaqueCursor((*n, n.to_string())),
+                        after,
+                        before,
+                        first,
+                        last,
+                    ))
+                },
+            )
+            .await
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+
+    let cursor = OpaqueCursor((3, "3".to_string())).encode_cursor();
+    let query = format!(
+        r#"{{ opaque(after: "{}", first: 2) {{ edges {{ cursor node }} }} }}"#,
+        cursor
+    );
+    assert_eq!(
+        schema.execute(&query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "opaque": {
+                "edges": [
+                    { "cursor": OpaqueCursor((4, "4".to_string())).encode_cursor(), "node": 4 },
+                    { "cursor": OpaqueCursor((5, "5".to_string())).encode_cursor(), "node": 5 },
+                ],
+            },
+        })
+    );
+    assert_eq!(
+        schema
+            .execute(r#"{ opaque(after: "abc") { edges { node } } }"#)
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "Invalid cursor."
+    );
+
+    // An invalid cursor is the client's mistake, so the error is not masked.
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .mask_errors(ErrorMaskPolicy::new())
+        .finish();
+    assert_eq!(
+        schema
+            .execute(r#"{ opaque(after: "abc") { edges { node } } }"#)
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "Invalid cursor."
+    );
+}
+
+#[cfg(feature = "signed_cursor")]
+#[async_std::test]
+pub async fn test_signed_cursor() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn signed(
+            &self,
+            ctx: &Context<'_>,
+            after: Option<String>,
+            before: Option<String>,
+            first: Option<i32>,
+            last: Option<i32>,
+        ) -> Result<Connection<SignedCursor<i32>, i32>> {
+            connection::query_with_context(
+                ctx,
+                after,
+                before,
+                first,
+                last,
+                |after, before, first, last| async move {
+                    Ok(Connection::from_sorted_keyset(
+                        0..10,
+                        |n| SignedCursor(*n),
+                        after,
+                        before,
+                        first,
+                        last,
+                    ))
+                },
+            )
+            .await
+        }
+    }
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(CursorSigningKey::new("secret"))
+        .finish();
+
+    let res = schema
+        .execute("{ signed(first: 2) { pageInfo { endCursor } } }")
+        .await
+        .into_result()
+        .unwrap();
+    let cursor = res.data["signed"]["pageInfo"]["endCursor"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let query = format!(
+        r#"{{ signed(after: "{}", first: 1) {{ edges {{ node }} }} }}"#,
+        cursor
+    );
+    assert_eq!(
+        schema.execute(&query).await.into_result().unwrap().data,
+        serde_json::json!({ "signed": { "edges": [{ "node": 2 }] } })
+    );
+
+    // A cursor that was modified or not signed is rejected.
+    let (_, signature) = cursor.split_at(cursor.find('.').unwrap());
+    let query = format!(
+        r#"{{ signed(after: "{}{}") {{ edges {{ node }} }} }}"#,
+        SignedCursor(5).encode_cursor(),
+        signature
+    );
+    assert_eq!(
+        schema.execute(&query).await.into_result().unwrap_err()[0].message,
+        "Invalid cursor signature."
+    );
+    let query = format!(
+        r#"{{ signed(after: "{}") {{ edges {{ node }} }} }}"#,
+        SignedCursor(5).encode_cursor()
+    );
+    assert_eq!(
+        schema.execute(&query).await.into_result().unwrap_err()[0].message,
+        "Invalid cursor."
+    );
+
+    // The signing key must be in the schema data.
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ signed(first: 1) { edges { cursor } } }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "Missing cursor signing key."
+    );
+
+    // Without the context the signature cannot be checked.
+    assert_eq!(
+        SignedCursor::<i32>::decode_cursor(&cursor),
+        Err(CursorError::ContextRequired)
+    );
+}
+
+#[async_std::test]
+pub async fn test_connection_names_and_total_count() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct FollowersName;
+
+    impl ConnectionNameType for FollowersName {
+        fn type_name<T: OutputValueType>() -> String {
+            "FollowersConnection".to_string()
+        }
+    }
+
+    struct FollowerEdgeName;
+
+    impl EdgeNameType for FollowerEdgeName {
+        fn type_name<T: OutputValueType>() -> String {
+            "FollowerEdge".to_string()
+        }
+    }
+
+    struct QueryRoot {
+        counted: Arc<AtomicUsize>,
+    }
+
+    #[Object]
+    impl QueryRoot {
+        async fn friends(
+            &self,
+            after: Option<String>,
+            before: Option<String>,
+            first: Option<i32>,
+            last: Option<i32>,
+        ) -> Result<Connection<usize, String, TotalCount>> {
+            let counted = self.counted.clone();
+            connection::query(
+                after,
+                before,
+                first,
+                last,
+                |after, before, first, last| async move {
+                    let names = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+                    let total_count = names.len();
+                    Ok(
+                        Connection::from_slice(&names, after, before, first, last)
+                            .with_total_count(move || async move {
+                                counted.fetch_add(1, Ordering::SeqCst);
+                                Ok(total_count)
+                            }),
+                    )
+                },
+            )
+            .await
+        }
+
+        async fn followers(
+            &self,
+        ) -> Connection<usize, String, EmptyFields, EmptyFields, FollowersName, FollowerEdgeName>
+        {
+            let mut connection = Connection::new(false, false);
+            connection.append(vec![Edge::new(0, "d".to_string())]);
+            connection
+        }
+    }
+
+    let counted = Arc::new(AtomicUsize::new(0));
+    let schema = Schema::new(
+        QueryRoot {
+            counted: counted.clone(),
+        },
+        EmptyMutation,
+        EmptySubscription,
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ friends(first: 1) { edges { node } } followers { edges { node } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "friends": { "edges": [{ "node": "a" }] },
+            "followers": { "edges": [{ "node": "d" }] },
+        })
+    );
+    assert_eq!(counted.load(Ordering::SeqCst), 0);
+
+    assert_eq!(
+        schema
+            .execute("{ friends(first: 1) { totalCount count: totalCount } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "friends": { "totalCount": 3, "count": 3 },
+        })
+    );
+    assert_eq!(counted.load(Ordering::SeqCst), 1);
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    friends: __type(name: "StringConnection") { fields { name } }
+                    friendEdge: __type(name: "StringEdge") { name }
+                    followers: __type(name: "FollowersConnection") { name }
+                    followerEdge: __type(name: "FollowerEdge") { name }
+                }"#
+            )
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "friends": {
+                "fields": [{ "name": "pageInfo" }, { "name": "edges" }, { "name": "totalCount" }],
+            },
+            "friendEdge": { "name": "StringEdge" },
+            "followers": { "name": "FollowersConnection" },
+            "followerEdge": { "name": "FollowerEdge" },
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_total_count_with_fields() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(SimpleObject)]
+    struct Diff {
+        diff: i32,
+    }
+
+    struct QueryRoot {
+        counted: Arc<AtomicUsize>,
+    }
+
+    #[Object]
+    impl QueryRoot {
+        async fn numbers(&self, ctx: &Context<'_>) -> Connection<usize, i32, TotalCount<Diff>> {
+            let counted = self.counted.clone();
+            let diff = if TotalCount::is_selected(ctx) { 1 } else { 0 };
+            let mut connection = Connection::with_additional_fields(false, false, Diff { diff })
+                .with_total_count(move || async move {
+                    counted.fetch_add(1, Ordering::SeqCst);
+                    Ok(1)
+                });
+            connection.append(vec![Edge::new(0, 0)]);
+            connection
+        }
+    }
+
+    let counted = Arc::new(AtomicUsize::new(0));
+    let schema = Schema::new(
+        QueryRoot {
+            counted: counted.clone(),
+        },
+        EmptyMutation,
+        EmptySubscription,
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ numbers { diff edges { node } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "numbers": { "diff": 0, "edges": [{ "node": 0 }] },
+        })
+    );
+    assert_eq!(counted.load(Ordering::SeqCst), 0);
+
+    assert_eq!(
+        schema
+            .execute("{ numbers { diff totalCount } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "numbers": { "diff": 1, "totalCount": 1 },
+        })
+    );
+    assert_eq!(counted.load(Ordering::SeqCst), 1);
+
+    let resp = schema
+        .execute(r#"{ numbers { diff } __type(name: "IntConnection") { fields { name } } }"#)
+        .await;
+    assert_eq!(
+        resp.data["__type"],
+        serde_json::json!({
+            "fields": [
+                { "name": "pageInfo" },
+                { "name": "edges" },
+                { "name": "totalCount" },
+                { "name": "diff" },
+            ],
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_total_count_error() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct QueryRoot {
+        total_count: TotalCount,
+    }
+
+    #[Object]
+    impl QueryRoot {
+        async fn count(&self) -> &TotalCount {
+            &self.total_count
+        }
+    }
+
+    let counted = Arc::new(AtomicUsize::new(0));
+    let schema = Schema::new(
+        QueryRoot {
+            total_count: TotalCount::new({
+                let counted = counted.clone();
+                move || async move {
+                    counted.fetch_add(1, Ordering::SeqCst);
+                    Err("Timeout".into())
+                }
+            }),
+        },
+        EmptyMutation,
+        EmptySubscription,
+    );
+
+    // A failed count is not computed again, and its error is kept.
+    for _ in 0..2 {
+        assert_eq!(
+            schema
+                .execute("{ count { totalCount } }")
+                .await
+                .into_result()
+                .unwrap_err()[0]
+                .message,
+            "Timeout"
+        );
+    }
+    assert_eq!(counted.load(Ordering::SeqCst), 1);
+}
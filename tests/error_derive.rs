@@ -0,0 +1,122 @@
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_error_derive() {
+    #[derive(GraphQLError)]
+    enum MyError {
+        #[graphql(code = "NOT_FOUND", message = "User {id} not found")]
+        UserNotFound { id: i32, user_name: String },
+
+        #[graphql(message = "Invalid value: {reason}")]
+        InvalidValue {
+            #[graphql(skip)]
+            reason: String,
+        },
+
+        #[graphql(message = "Too many requests, retry in {0}s")]
+        RateLimited(u64, #[graphql(name = "limit")] u32),
+
+        #[graphql(message = "Access denied")]
+        Forbidden,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user(&self, id: i32) -> Result<i32, MyError> {
+            Err(MyError::UserNotFound {
+                id,
+                user_name: "abc".to_string(),
+            })
+        }
+
+        async fn invalid(&self) -> Result<i32, MyError> {
+            Err(MyError::InvalidValue {
+                reason: "too long".to_string(),
+            })
+        }
+
+        async fn rate_limited(&self) -> Result<i32, MyError> {
+            Err(MyError::RateLimited(30, 100))
+        }
+
+        async fn forbidden(&self) -> Result<i32> {
+            Err(MyError::Forbidden)?;
+            Ok(10)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute("{ user(id: 10) }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "User 10 not found".to_string(),
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("user".to_string())],
+            extensions: Some({
+                let mut extensions = serde_json::Map::new();
+                extensions.insert("code".to_string(), "NOT_FOUND".into());
+                extensions.insert("id".to_string(), 10.into());
+                extensions.insert("userName".to_string(), "abc".into());
+                extensions
+            }),
+        }]
+    );
+
+    assert_eq!(
+        schema.execute("{ invalid }").await.into_result().unwrap_err(),
+        vec![ServerError {
+            message: "Invalid value: too long".to_string(),
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("invalid".to_string())],
+            extensions: Some({
+                let mut extensions = serde_json::Map::new();
+                extensions.insert("code".to_string(), "INVALID_VALUE".into());
+                extensions
+            }),
+        }]
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ rateLimited }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Too many requests, retry in 30s".to_string(),
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("rateLimited".to_string())],
+            extensions: Some({
+                let mut extensions = serde_json::Map::new();
+                extensions.insert("code".to_string(), "RATE_LIMITED".into());
+                extensions.insert("limit".to_string(), 100.into());
+                extensions
+            }),
+        }]
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ forbidden }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Access denied".to_string(),
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("forbidden".to_string())],
+            extensions: Some({
+                let mut extensions = serde_json::Map::new();
+                extensions.insert("code".to_string(), "FORBIDDEN".into());
+                extensions
+            }),
+        }]
+    );
+}
@@ -296,3 +296,437 @@ pub async fn test_guard_forward_arguments() {
         }]
     );
 }
+
+#[async_std::test]
+pub async fn test_guard_combinators() {
+    struct ResourceOwner {
+        id: ID,
+    }
+
+    #[async_trait::async_trait]
+    impl Guard for ResourceOwner {
+        async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+            if ctx.data_opt::<Username>().map(|name| name.0.as_str()) == Some(&*self.id) {
+                Ok(())
+            } else {
+                Err("Not the owner".into())
+            }
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(guard(or(RoleGuard(role = "Role::Admin"), ResourceOwner(id = "arg:id"))))]
+        async fn resource(&self, id: ID) -> ID {
+            id
+        }
+
+        #[graphql(guard(not(RoleGuard(role = "Role::Admin"))))]
+        async fn guest_only(&self) -> i32 {
+            1
+        }
+
+        #[graphql(guard(any_of(
+            RoleGuard(role = "Role::Admin"),
+            all_of(RoleGuard(role = "Role::Guest"), UserGuard(username = r#""test""#))
+        )))]
+        async fn nested(&self) -> i32 {
+            2
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let execute = |query: &'static str, role: Role, username: &'static str| {
+        let schema = schema.clone();
+        async move {
+            schema
+                .execute(
+                    Request::new(query)
+                        .data(role)
+                        .data(Username(username.to_string())),
+                )
+                .await
+                .into_result()
+                .map(|resp| resp.data)
+                .map_err(|mut errors| errors.remove(0).message)
+        }
+    };
+
+    let query = r#"{ resource(id: "test") }"#;
+    assert_eq!(
+        execute(query, Role::Admin, "abc").await,
+        Ok(serde_json::json!({"resource": "test"}))
+    );
+    assert_eq!(
+        execute(query, Role::Guest, "test").await,
+        Ok(serde_json::json!({"resource": "test"}))
+    );
+    assert_eq!(
+        execute(query, Role::Guest, "abc").await,
+        Err("Forbidden".to_string())
+    );
+
+    let query = "{ guestOnly }";
+    assert_eq!(
+        execute(query, Role::Guest, "abc").await,
+        Ok(serde_json::json!({"guestOnly": 1}))
+    );
+    assert_eq!(
+        execute(query, Role::Admin, "abc").await,
+        Err("Forbidden".to_string())
+    );
+
+    let query = "{ nested }";
+    assert_eq!(
+        execute(query, Role::Admin, "abc").await,
+        Ok(serde_json::json!({"nested": 2}))
+    );
+    assert_eq!(
+        execute(query, Role::Guest, "test").await,
+        Ok(serde_json::json!({"nested": 2}))
+    );
+    assert_eq!(
+        execute(query, Role::Guest, "abc").await,
+        Err("Forbidden".to_string())
+    );
+}
+
+#[async_std::test]
+pub async fn test_guard_functions() {
+    use async_graphql::guard::{all_of, any_of, GuardExt};
+
+    struct AdminOrGuestTest;
+
+    #[async_trait::async_trait]
+    impl Guard for AdminOrGuestTest {
+        async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+            any_of(vec![
+                Box::new(RoleGuard { role: Role::Admin }),
+                Box::new(all_of(vec![
+                    Box::new(RoleGuard { role: Role::Guest }),
+                    Box::new(UserGuard {
+                        username: "test".to_string(),
+                    }),
+                ])),
+            ])
+            .check(ctx)
+            .await
+        }
+    }
+
+    struct NotTest;
+
+    #[async_trait::async_trait]
+    impl Guard for NotTest {
+        async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+            UserGuard {
+                username: "test".to_string(),
+            }
+            .not()
+            .check(ctx)
+            .await
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(guard(AdminOrGuestTest))]
+        async fn value1(&self) -> i32 {
+            1
+        }
+
+        #[graphql(guard(NotTest))]
+        async fn value2(&self) -> i32 {
+            2
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let execute = |query: &'static str, role: Role, username: &'static str| {
+        let schema = schema.clone();
+        async move {
+            schema
+                .execute(
+                    Request::new(query)
+                        .data(role)
+                        .data(Username(username.to_string())),
+                )
+                .await
+                .is_ok()
+        }
+    };
+
+    assert!(execute("{ value1 }", Role::Admin, "abc").await);
+    assert!(execute("{ value1 }", Role::Guest, "test").await);
+    assert!(!execute("{ value1 }", Role::Guest, "abc").await);
+    assert!(execute("{ value2 }", Role::Guest, "abc").await);
+    assert!(!execute("{ value2 }", Role::Guest, "test").await);
+}
+
+#[async_std::test]
+pub async fn test_type_guard() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct Counter(Arc<AtomicUsize>);
+
+    struct CountGuard;
+
+    #[async_trait::async_trait]
+    impl Guard for CountGuard {
+        async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+            ctx.data_unchecked::<Counter>()
+                .0
+                .fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    struct AdminPanel;
+
+    #[Object(guard(RoleGuard(role = "Role::Admin"), CountGuard))]
+    impl AdminPanel {
+        async fn a(&self) -> i32 {
+            1
+        }
+
+        async fn b(&self) -> i32 {
+            2
+        }
+
+        #[graphql(guard(UserGuard(username = r#""test""#)))]
+        async fn c(&self) -> i32 {
+            3
+        }
+    }
+
+    #[derive(SimpleObject)]
+    #[graphql(guard(RoleGuard(role = "Role::Admin")))]
+    struct BillingInfo {
+        amount: i32,
+    }
+
+    #[derive(SimpleObject)]
+    struct Public {
+        value: i32,
+    }
+
+    #[derive(Union)]
+    #[graphql(guard(UserGuard(username = r#""test""#)))]
+    enum SearchResult {
+        BillingInfo(BillingInfo),
+        Public(Public),
+    }
+
+    #[derive(Interface)]
+    #[graphql(guard(not(RoleGuard(role = "Role::Admin"))))]
+    #[graphql(field(name = "a", type = "i32"))]
+    enum GuestNode {
+        AdminPanel(AdminPanel),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn panel(&self) -> AdminPanel {
+            AdminPanel
+        }
+
+        async fn panels(&self) -> Vec<AdminPanel> {
+            vec![AdminPanel, AdminPanel]
+        }
+
+        async fn billing(&self) -> BillingInfo {
+            BillingInfo { amount: 10 }
+        }
+
+        async fn search(&self, billing: bool) -> SearchResult {
+            if billing {
+                BillingInfo { amount: 10 }.into()
+            } else {
+                Public { value: 1 }.into()
+            }
+        }
+
+        async fn node(&self) -> GuestNode {
+            AdminPanel.into()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let execute = |query: &'static str, role: Role, username: &'static str| {
+        let schema = schema.clone();
+        async move {
+            let counter = Counter::default();
+            let res = schema
+                .execute(
+                    Request::new(query)
+                        .data(role)
+                        .data(Username(username.to_string()))
+                        .data(counter.clone()),
+                )
+                .await
+                .into_result()
+                .map(|resp| resp.data)
+                .map_err(|errors| {
+                    errors
+                        .into_iter()
+                        .map(|err| (err.message, err.path))
+                        .collect::<Vec<_>>()
+                });
+            (res, counter.0.load(Ordering::SeqCst))
+        }
+    };
+
+    assert_eq!(
+        execute("{ panel { a b } }", Role::Admin, "abc").await,
+        (Ok(serde_json::json!({"panel": {"a": 1, "b": 2}})), 1)
+    );
+    assert_eq!(
+        execute("{ panels { a b } }", Role::Admin, "abc").await,
+        (
+            Ok(serde_json::json!({"panels": [{"a": 1, "b": 2}, {"a": 1, "b": 2}]})),
+            2
+        )
+    );
+    assert_eq!(
+        execute("{ panel { a } }", Role::Guest, "abc").await,
+        (
+            Err(vec![(
+                "Forbidden".to_string(),
+                vec![PathSegment::Field("panel".to_string())]
+            )]),
+            1
+        )
+    );
+    assert_eq!(
+        execute("{ panel { c } }", Role::Admin, "test").await,
+        (Ok(serde_json::json!({"panel": {"c": 3}})), 1)
+    );
+    assert_eq!(
+        execute("{ panel { c } }", Role::Admin, "abc").await,
+        (
+            Err(vec![(
+                "Forbidden".to_string(),
+                vec![
+                    PathSegment::Field("panel".to_string()),
+                    PathSegment::Field("c".to_string())
+                ]
+            )]),
+            1
+        )
+    );
+
+    assert_eq!(
+        execute("{ billing { amount } }", Role::Admin, "abc")
+            .await
+            .0,
+        Ok(serde_json::json!({"billing": {"amount": 10}}))
+    );
+    assert!(execute("{ billing { amount } }", Role::Guest, "abc")
+        .await
+        .0
+        .is_err());
+
+    let query = "{ search(billing: false) { ... on Public { value } } }";
+    assert_eq!(
+        execute(query, Role::Guest, "test").await.0,
+        Ok(serde_json::json!({"search": {"value": 1}}))
+    );
+    assert!(execute(query, Role::Guest, "abc").await.0.is_err());
+
+    let query = "{ search(billing: true) { ... on BillingInfo { amount } } }";
+    assert_eq!(
+        execute(query, Role::Admin, "test").await.0,
+        Ok(serde_json::json!({"search": {"amount": 10}}))
+    );
+    assert!(execute(query, Role::Guest, "test").await.0.is_err());
+
+    assert!(execute("{ node { a } }", Role::Admin, "abc")
+        .await
+        .0
+        .is_err());
+    assert!(execute("{ node { a } }", Role::Guest, "abc")
+        .await
+        .0
+        .is_err());
+}
+
+#[async_std::test]
+pub async fn test_root_type_guard() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct Counter(Arc<AtomicUsize>);
+
+    struct CountGuard;
+
+    #[async_trait::async_trait]
+    impl Guard for CountGuard {
+        async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+            ctx.data_unchecked::<Counter>()
+                .0
+                .fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    struct Query;
+
+    #[Object(guard(RoleGuard(role = "Role::Admin"), CountGuard))]
+    impl Query {
+        async fn value(&self) -> i32 {
+            1
+        }
+
+        async fn other(&self) -> i32 {
+            2
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let execute = |query: &'static str, role: Role| {
+        let schema = schema.clone();
+        async move {
+            let counter = Counter::default();
+            let resp = schema
+                .execute(Request::new(query).data(role).data(counter.clone()))
+                .await;
+            (resp, counter.0.load(Ordering::SeqCst))
+        }
+    };
+
+    let (resp, count) = execute("{ value other ... { value } }", Role::Admin).await;
+    assert_eq!(resp.data, serde_json::json!({"value": 1, "other": 2}));
+    assert_eq!(count, 1);
+
+    let (resp, count) = execute("{ __typename value }", Role::Guest).await;
+    assert_eq!(
+        resp.into_result().unwrap_err(),
+        vec![ServerError {
+            message: "Forbidden".to_string(),
+            locations: vec![Pos {
+                line: 1,
+                column: 14
+            }],
+            path: vec![PathSegment::Field("value".to_string())],
+            extensions: None,
+        }]
+    );
+    assert_eq!(count, 1);
+
+    let (resp, count) = execute(
+        "{ __typename __schema { queryType { name } } }",
+        Role::Guest,
+    )
+    .await;
+    assert!(resp.is_ok());
+    assert_eq!(count, 0);
+}
@@ -0,0 +1,150 @@
+use async_graphql::http::{
+    parse_query_string, receive_body, response_content_type, ResponseHead,
+    GRAPHQL_RESPONSE_CONTENT_TYPE, JSON_CONTENT_TYPE,
+};
+use async_graphql::*;
+
+#[test]
+pub fn test_parse_query_string() {
+    let request = parse_query_string(
+        "query=query%20A(%24a%3A%20Int!)%20%7B%20value(a%3A%20%24a)%20%7D%20mutation%20B%20%7B%20value%20%7D\
+         &operationName=A&variables=%7B%22a%22%3A10%7D&extensions=%7B%22b%22%3Atrue%7D&other=1",
+    )
+    .unwrap();
+    assert_eq!(
+        request.query,
+        "query A($a: Int!) { value(a: $a) } mutation B { value }"
+    );
+    assert_eq!(request.operation_name.as_deref(), Some("A"));
+    assert_eq!(
+        request.variables.into_value().into_json().unwrap(),
+        serde_json::json!({ "a": 10 })
+    );
+    assert_eq!(request.extensions["b"], serde_json::json!(true));
+
+    let request = parse_query_string("query=%7B%20value%20%7D&variables=").unwrap();
+    assert_eq!(request.query, "{ value }");
+    assert_eq!(request.operation_name, None);
+
+    assert!(matches!(
+        parse_query_string("query=mutation%20%7B%20value%20%7D"),
+        Err(ParseRequestError::MutationNotAllowed)
+    ));
+    assert!(matches!(
+        parse_query_string("query=query%20A%20%7B%20value%20%7D%20mutation%20B%20%7B%20value%20%7D&operationName=B"),
+        Err(ParseRequestError::MutationNotAllowed)
+    ));
+    assert!(matches!(
+        parse_query_string("query=%7B%20value%20%7D&variables=%7B"),
+        Err(ParseRequestError::InvalidRequest(_))
+    ));
+    assert!(parse_query_string("query=mutation%20%7B").is_ok());
+}
+
+#[async_std::test]
+pub async fn test_receive_graphql_body() {
+    let request = receive_body(
+        Some("application/graphql; charset=utf-8"),
+        futures::io::Cursor::new("{ value }"),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(request.query, "{ value }");
+
+    let request = receive_body(
+        Some("application/json"),
+        futures::io::Cursor::new(r#"{ "query": "{ value }" }"#),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(request.query, "{ value }");
+}
+
+#[test]
+pub fn test_response_content_type() {
+    assert_eq!(response_content_type(None), JSON_CONTENT_TYPE);
+    assert_eq!(response_content_type(Some("*/*")), JSON_CONTENT_TYPE);
+    assert_eq!(response_content_type(Some("text/html")), JSON_CONTENT_TYPE);
+    assert_eq!(
+        response_content_type(Some("application/graphql-response+json")),
+        GRAPHQL_RESPONSE_CONTENT_TYPE
+    );
+    assert_eq!(
+        response_content_type(Some("application/json, application/graphql-response+json")),
+        GRAPHQL_RESPONSE_CONTENT_TYPE
+    );
+    assert_eq!(
+        response_content_type(Some(
+            "application/graphql-response+json;q=0.9, application/json"
+        )),
+        JSON_CONTENT_TYPE
+    );
+    assert_eq!(
+        response_content_type(Some(
+            "application/json;q=0.5, application/graphql-response+json;q=0.9"
+        )),
+        GRAPHQL_RESPONSE_CONTENT_TYPE
+    );
+    assert_eq!(
+        response_content_type(Some("application/graphql-response+json;q=0")),
+        JSON_CONTENT_TYPE
+    );
+}
+
+#[async_std::test]
+pub async fn test_response_head() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> Result<i32> {
+            Err("error".into())
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let accept = Some(GRAPHQL_RESPONSE_CONTENT_TYPE);
+
+    let response = BatchResponse::Single(schema.execute("{ value").await);
+    assert_eq!(
+        ResponseHead::for_response(accept, &response),
+        ResponseHead {
+            status: 400,
+            content_type: GRAPHQL_RESPONSE_CONTENT_TYPE
+        }
+    );
+    assert_eq!(
+        ResponseHead::for_response(None, &response),
+        ResponseHead {
+            status: 200,
+            content_type: JSON_CONTENT_TYPE
+        }
+    );
+
+    let response = BatchResponse::Single(schema.execute("{ value }").await);
+    assert_eq!(
+        ResponseHead::for_response(accept, &response),
+        ResponseHead {
+            status: 200,
+            content_type: GRAPHQL_RESPONSE_CONTENT_TYPE
+        }
+    );
+
+    assert_eq!(
+        ResponseHead::for_error(accept, &ParseRequestError::MutationNotAllowed).status,
+        405
+    );
+    assert_eq!(
+        ResponseHead::for_error(None, &ParseRequestError::PayloadTooLarge).status,
+        413
+    );
+    assert_eq!(
+        ResponseHead::for_error(None, &ParseRequestError::MissingFiles),
+        ResponseHead {
+            status: 400,
+            content_type: JSON_CONTENT_TYPE
+        }
+    );
+}
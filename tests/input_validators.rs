@@ -1,6 +1,7 @@
 use async_graphql::validators::{
-    Email, IntEqual, IntGreaterThan, IntLessThan, IntNonZero, IntRange, ListMaxLength,
-    ListMinLength, StringMaxLength, StringMinLength, MAC,
+    Charset, Cidr, Email, FloatRange, IntEqual, IntGreaterThan, IntLessThan, IntNonZero, IntRange,
+    IpAddr, ListItems, ListMaxLength, ListMinLength, ListUnique, Not, Regex, StringCharset,
+    StringMaxLength, StringMinLength, Url, Uuid, MAC,
 };
 use async_graphql::*;
 use async_graphql_parser::types::Name;
@@ -1774,3 +1775,376 @@ pub async fn test_input_validator_variable() {
         }
     }
 }
+
+#[async_std::test]
+pub async fn test_input_validator_input_object() {
+    struct QueryRoot;
+
+    fn validate_range(range: &DateRange) -> Result<(), String> {
+        if range.end_date < range.start_date {
+            return Err("endDate must be after startDate".to_string());
+        }
+        Ok(())
+    }
+
+    #[derive(InputObject)]
+    #[graphql(validator = "validate_range")]
+    struct DateRange {
+        start_date: i32,
+        end_date: i32,
+    }
+
+    #[derive(InputObject)]
+    struct Filter {
+        range: DateRange,
+    }
+
+    #[Object]
+    impl QueryRoot {
+        async fn days(&self, range: DateRange) -> i32 {
+            range.end_date - range.start_date
+        }
+
+        async fn filter(&self, _filter: Filter) -> bool {
+            true
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute("{ days(range: {startDate: 10, endDate: 15}) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({"days": 5})
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ days(range: {startDate: 10, endDate: 5}) }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Invalid value for argument \"range\", endDate must be after startDate"
+                .to_string(),
+            locations: vec!(Pos { line: 1, column: 8 }),
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ filter(filter: {range: {startDate: 10, endDate: 5}}) }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message:
+                "Invalid value for argument \"filter.range\", endDate must be after startDate"
+                    .to_string(),
+            locations: vec!(Pos { line: 1, column: 10 }),
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+
+    let mut variables = Variables::default();
+    variables.0.insert(
+        Name::new("end".to_owned()).unwrap(),
+        Value::Number(5.into()),
+    );
+    assert_eq!(
+        schema
+            .execute(
+                Request::new("query($end: Int!) { days(range: {startDate: 10, endDate: $end}) }")
+                    .variables(variables)
+            )
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Invalid value for argument \"range\", endDate must be after startDate"
+                .to_string(),
+            locations: vec!(Pos { line: 1, column: 26 }),
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+}
+
+async fn validation_error<Query: ObjectType + Send + Sync + 'static>(
+    schema: &Schema<Query, EmptyMutation, EmptySubscription>,
+    query: &str,
+) -> Option<String> {
+    schema
+        .execute(query)
+        .await
+        .into_result()
+        .err()
+        .map(|mut errors| errors.remove(0).message)
+}
+
+#[async_std::test]
+pub async fn test_input_validator_string_formats() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn regex(&self, #[graphql(validator(Regex("[a-z]+")))] _value: String) -> bool {
+            true
+        }
+
+        async fn url(&self, #[graphql(validator(Url))] _value: String) -> bool {
+            true
+        }
+
+        async fn uuid(&self, #[graphql(validator(Uuid))] _value: String) -> bool {
+            true
+        }
+
+        async fn ip(&self, #[graphql(validator(IpAddr))] _value: String) -> bool {
+            true
+        }
+
+        async fn cidr(&self, #[graphql(validator(Cidr))] _value: String) -> bool {
+            true
+        }
+
+        async fn charset(
+            &self,
+            #[graphql(validator(StringCharset(charset = "Charset::AsciiHexDigit")))] _value: String,
+        ) -> bool {
+            true
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    let test_cases = [
+        (r#"{ regex(value: "abc") }"#, None),
+        (
+            r#"{ regex(value: "abc1") }"#,
+            Some("Invalid value for argument \"value\", the value does not match the pattern \"[a-z]+\""),
+        ),
+        (r#"{ url(value: "https://example.com/a?b=c") }"#, None),
+        (
+            r#"{ url(value: "example.com") }"#,
+            Some("Invalid value for argument \"value\", invalid URL format"),
+        ),
+        (r#"{ uuid(value: "67e55044-10b1-426f-9247-bb680e5fe0c8") }"#, None),
+        (
+            r#"{ uuid(value: "67e55044-10b1") }"#,
+            Some("Invalid value for argument \"value\", invalid UUID format"),
+        ),
+        (r#"{ ip(value: "192.168.0.1") }"#, None),
+        (r#"{ ip(value: "::1") }"#, None),
+        (
+            r#"{ ip(value: "192.168.0.256") }"#,
+            Some("Invalid value for argument \"value\", invalid IP address format"),
+        ),
+        (r#"{ cidr(value: "192.168.0.0/16") }"#, None),
+        (r#"{ cidr(value: "2001:db8::/32") }"#, None),
+        (
+            r#"{ cidr(value: "192.168.0.0/33") }"#,
+            Some("Invalid value for argument \"value\", invalid CIDR format"),
+        ),
+        (
+            r#"{ cidr(value: "192.168.0.0") }"#,
+            Some("Invalid value for argument \"value\", invalid CIDR format"),
+        ),
+        (r#"{ charset(value: "0fA9") }"#, None),
+        (
+            r#"{ charset(value: "0fG9") }"#,
+            Some("Invalid value for argument \"value\", the value contains the invalid character 'G'"),
+        ),
+    ];
+
+    for (query, expected) in &test_cases {
+        assert_eq!(
+            validation_error(&schema, query).await.as_deref(),
+            *expected,
+            "{}",
+            query
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "invalid regular expression \"[a-z\"")]
+pub fn test_input_validator_invalid_regex() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn regex(&self, #[graphql(validator(Regex("[a-z")))] _value: String) -> bool {
+            true
+        }
+    }
+
+    Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+}
+
+#[async_std::test]
+pub async fn test_input_validator_float_range() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(
+            &self,
+            #[graphql(validator(FloatRange(min = "0", max = "1.5")))] _value: f64,
+        ) -> bool {
+            true
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    assert_eq!(validation_error(&schema, "{ value(value: 0.5) }").await, None);
+    assert_eq!(validation_error(&schema, "{ value(value: 1) }").await, None);
+    assert_eq!(
+        validation_error(&schema, "{ value(value: 1.6) }")
+            .await
+            .as_deref(),
+        Some("Invalid value for argument \"value\", the value is 1.6, must be between 0 and 1.5")
+    );
+    assert_eq!(
+        validation_error(&schema, "{ value(value: -1) }")
+            .await
+            .as_deref(),
+        Some("Invalid value for argument \"value\", the value is -1, must be between 0 and 1.5")
+    );
+}
+
+#[async_std::test]
+pub async fn test_input_validator_list_items() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn unique(&self, #[graphql(validator(ListUnique))] _values: Vec<i32>) -> bool {
+            true
+        }
+
+        async fn emails(
+            &self,
+            #[graphql(validator(ListItems(Email)))] _values: Vec<String>,
+        ) -> bool {
+            true
+        }
+
+        async fn ranges(
+            &self,
+            #[graphql(validator(ListItems(IntRange(min = "1", max = "10"))))] _values: Vec<i32>,
+        ) -> bool {
+            true
+        }
+
+        async fn not_email(&self, #[graphql(validator(Not(Email)))] _value: String) -> bool {
+            true
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    let test_cases = [
+        ("{ unique(values: [1, 2, 3]) }", None),
+        (
+            "{ unique(values: [1, 2, 1]) }",
+            Some("Invalid value for argument \"values\", the item at index 2 is a duplicate"),
+        ),
+        (r#"{ emails(values: ["a@example.com", "b@example.com"]) }"#, None),
+        (
+            r#"{ emails(values: ["a@example.com", "b"]) }"#,
+            Some("Invalid value for argument \"values\", the item at index 1 is invalid, invalid email format"),
+        ),
+        ("{ ranges(values: [1, 10]) }", None),
+        (
+            "{ ranges(values: [1, 11]) }",
+            Some("Invalid value for argument \"values\", the item at index 1 is invalid, the value is 11, must be between 1 and 10"),
+        ),
+        (r#"{ notEmail(value: "abc") }"#, None),
+        (
+            r#"{ notEmail(value: "a@example.com") }"#,
+            Some("Invalid value for argument \"value\", the value is not allowed"),
+        ),
+    ];
+
+    for (query, expected) in &test_cases {
+        assert_eq!(
+            validation_error(&schema, query).await.as_deref(),
+            *expected,
+            "{}",
+            query
+        );
+    }
+}
+
+#[async_std::test]
+pub async fn test_input_validator_async() {
+    use async_graphql::validators::AsyncInputValueValidator;
+
+    struct MaxLimit(i32);
+
+    struct LimitForPlan;
+
+    #[async_trait::async_trait]
+    impl AsyncInputValueValidator<i32> for LimitForPlan {
+        async fn is_valid(&self, ctx: &Context<'_>, value: &i32) -> Result<(), String> {
+            let max = ctx.data_unchecked::<MaxLimit>().0;
+            if *value > max {
+                Err(format!("the limit of your plan is {}", max))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn items(
+            &self,
+            #[graphql(validator(IntGreaterThan(value = "0"), async = "LimitForPlan"))] limit: i32,
+        ) -> i32 {
+            limit
+        }
+    }
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(MaxLimit(10))
+        .finish();
+
+    assert_eq!(
+        schema
+            .execute("{ items(limit: 10) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({"items": 10})
+    );
+    assert_eq!(
+        schema
+            .execute("{ items(limit: 11) }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Invalid value for argument \"limit\", the limit of your plan is 10"
+                .to_string(),
+            locations: vec!(Pos { line: 1, column: 16 }),
+            path: vec![PathSegment::Field("items".to_string())],
+            extensions: None,
+        }]
+    );
+    assert_eq!(
+        validation_error(&schema, "{ items(limit: 0) }")
+            .await
+            .as_deref(),
+        Some("Invalid value for argument \"limit\", the value is 0, must be greater than 0")
+    );
+}
@@ -331,3 +331,125 @@ pub async fn test_interface_field_method() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_interface_implements_interface() {
+    #[derive(SimpleObject)]
+    struct User {
+        id: i32,
+        url: String,
+        name: String,
+    }
+
+    #[derive(SimpleObject)]
+    struct Comment {
+        id: i32,
+        text: String,
+    }
+
+    #[derive(Interface)]
+    #[graphql(
+        field(name = "id", type = "&i32"),
+        field(name = "url", type = "&String")
+    )]
+    enum Resource {
+        User(User),
+    }
+
+    #[derive(Interface)]
+    #[graphql(field(name = "id", type = "&i32"))]
+    enum Node {
+        Resource(Resource),
+        Comment(Comment),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn nodes(&self) -> Vec<Node> {
+            vec![
+                Resource::from(User {
+                    id: 1,
+                    url: "/users/1".to_string(),
+                    name: "abc".to_string(),
+                })
+                .into(),
+                Comment {
+                    id: 2,
+                    text: "hello".to_string(),
+                }
+                .into(),
+            ]
+        }
+
+        async fn sdl(&self, ctx: &Context<'_>) -> String {
+            ctx.schema_env.registry.export_sdl(false)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"{
+        nodes {
+            __typename
+            id
+            ... on Resource { url }
+            ... on User { name }
+            ... on Comment { text }
+        }
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "nodes": [
+                { "__typename": "User", "id": 1, "url": "/users/1", "name": "abc" },
+                { "__typename": "Comment", "id": 2, "text": "hello" },
+            ]
+        })
+    );
+
+    let query = r#"{
+        resource: __type(name: "Resource") {
+            kind
+            interfaces { name }
+            possibleTypes { name }
+        }
+        node: __type(name: "Node") {
+            possibleTypes { name }
+        }
+        user: __type(name: "User") {
+            interfaces { name }
+        }
+    }"#;
+    let data = schema.execute(query).await.into_result().unwrap().data;
+    let names = |value: &serde_json::Value| {
+        let mut names = value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|ty| ty["name"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    };
+    assert_eq!(data["resource"]["kind"], "INTERFACE");
+    assert_eq!(names(&data["resource"]["interfaces"]), vec!["Node"]);
+    assert_eq!(names(&data["resource"]["possibleTypes"]), vec!["User"]);
+    assert_eq!(
+        names(&data["node"]["possibleTypes"]),
+        vec!["Comment", "User"]
+    );
+    assert_eq!(names(&data["user"]["interfaces"]), vec!["Node", "Resource"]);
+
+    let sdl = schema.execute("{ sdl }").await.data["sdl"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(sdl.contains("interface Resource implements Node {"));
+
+    // the fragment on `Resource` can be spread on `Node`, but not on `Comment`
+    assert!(schema
+        .execute("{ nodes { ... on Comment { ... on Resource { url } } } }")
+        .await
+        .is_err());
+}
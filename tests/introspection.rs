@@ -1189,3 +1189,300 @@ pub async fn test_introspection_subscription() {
 //
 //     assert_eq!(res, res_json)
 // }
+
+#[async_std::test]
+pub async fn test_introspection_mode() {
+    struct Developer;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    struct Mutation;
+
+    #[Object]
+    impl Mutation {
+        async fn action(&self) -> bool {
+            true
+        }
+    }
+
+    let introspection = "{ __type(name: \"Query\") { name } }";
+    let is_ok = |schema: &Schema<Query, Mutation, EmptySubscription>, request: Request| {
+        let schema = schema.clone();
+        async move { schema.execute(request).await.is_ok() }
+    };
+
+    let schema = Schema::build(Query, Mutation, EmptySubscription)
+        .disable_introspection()
+        .finish();
+    assert!(!is_ok(&schema, Request::new(introspection)).await);
+    assert!(!is_ok(&schema, Request::new("{ __schema { queryType { name } } }")).await);
+    assert!(is_ok(&schema, Request::new("{ value }")).await);
+    assert!(
+        is_ok(
+            &schema,
+            Request::new(introspection).introspection_mode(IntrospectionMode::Enabled)
+        )
+        .await
+    );
+    assert_eq!(
+        schema
+            .execute(introspection)
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Introspection is disabled.".to_string(),
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("__type".to_string())],
+            extensions: None,
+        }]
+    );
+
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    assert!(is_ok(&schema, Request::new(introspection)).await);
+    assert!(!is_ok(&schema, Request::new(introspection).disable_introspection()).await);
+
+    let schema = Schema::build(Query, Mutation, EmptySubscription)
+        .introspection_only()
+        .finish();
+    assert!(is_ok(&schema, Request::new(introspection)).await);
+    assert!(!is_ok(&schema, Request::new("{ value }")).await);
+    assert!(!is_ok(&schema, Request::new("mutation { action }")).await);
+    assert!(!is_ok(&schema, Request::new(introspection).disable_introspection()).await);
+
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    assert!(is_ok(&schema, Request::new(introspection).introspection_only()).await);
+    assert!(!is_ok(&schema, Request::new("{ value }").introspection_only()).await);
+
+    let schema = Schema::build(Query, Mutation, EmptySubscription)
+        .introspection_policy(|data| {
+            if data.contains_key(&std::any::TypeId::of::<Developer>()) {
+                IntrospectionMode::Enabled
+            } else {
+                IntrospectionMode::Disabled
+            }
+        })
+        .finish();
+    assert!(is_ok(&schema, Request::new(introspection).data(Developer)).await);
+    assert!(!is_ok(&schema, Request::new(introspection)).await);
+    assert!(
+        is_ok(
+            &schema,
+            Request::new(introspection).introspection_mode(IntrospectionMode::Enabled)
+        )
+        .await
+    );
+}
+
+#[async_std::test]
+pub async fn test_introspection_input_value_deprecation() {
+    #[derive(InputObject)]
+    struct MyInput {
+        a: i32,
+        #[graphql(deprecation = "Use a")]
+        b: Option<i32>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(
+            &self,
+            a: i32,
+            #[graphql(deprecation = "Use input")] b: Option<i32>,
+            input: Option<MyInput>,
+        ) -> i32 {
+            a + b.unwrap_or_default() + input.map(|input| input.a).unwrap_or_default()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"{
+        query: __type(name: "Query") {
+            fields {
+                args { name }
+                allArgs: args(includeDeprecated: true) { name isDeprecated deprecationReason }
+            }
+        }
+        input: __type(name: "MyInput") {
+            inputFields { name }
+            allInputFields: inputFields(includeDeprecated: true) { name isDeprecated deprecationReason }
+        }
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "query": {
+                "fields": [{
+                    "args": [{ "name": "a" }, { "name": "input" }],
+                    "allArgs": [
+                        { "name": "a", "isDeprecated": false, "deprecationReason": null },
+                        { "name": "b", "isDeprecated": true, "deprecationReason": "Use input" },
+                        { "name": "input", "isDeprecated": false, "deprecationReason": null },
+                    ],
+                }],
+            },
+            "input": {
+                "inputFields": [{ "name": "a" }],
+                "allInputFields": [
+                    { "name": "a", "isDeprecated": false, "deprecationReason": null },
+                    { "name": "b", "isDeprecated": true, "deprecationReason": "Use a" },
+                ],
+            },
+        })
+    );
+
+    // deprecated arguments can still be used
+    assert_eq!(
+        schema
+            .execute("{ value(a: 1, b: 2) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({ "value": 3 })
+    );
+
+    let sdl = Schema::<Query, EmptyMutation, EmptySubscription>::sdl();
+    assert!(sdl.contains(r#"b: Int @deprecated(reason: "Use input")"#));
+    assert!(sdl.contains(r#"b: Int @deprecated(reason: "Use a")"#));
+}
+
+#[test]
+#[should_panic(expected = "\"Query.value.a\" is required and cannot be deprecated")]
+pub fn test_introspection_deprecated_required_argument() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, #[graphql(deprecation = "Use b")] a: i32, b: Option<i32>) -> i32 {
+            a + b.unwrap_or_default()
+        }
+    }
+
+    Schema::new(Query, EmptyMutation, EmptySubscription);
+}
+
+#[test]
+#[should_panic(expected = "\"MyInput.a\" is required and cannot be deprecated")]
+pub fn test_introspection_deprecated_required_input_field() {
+    #[derive(InputObject)]
+    struct MyInput {
+        #[graphql(deprecation = "Use b")]
+        a: i32,
+        #[graphql(default, deprecation = "Use c")]
+        b: i32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn value(&self, input: MyInput) -> i32 {
+            input.a + input.b
+        }
+    }
+
+    Schema::new(Query, EmptyMutation, EmptySubscription);
+}
+
+#[async_std::test]
+pub async fn test_introspection_specified_by_and_description() {
+    struct MyUrl(String);
+
+    #[Scalar(specified_by_url = "https://url.spec.whatwg.org/")]
+    impl ScalarType for MyUrl {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            match value {
+                Value::String(s) => Ok(MyUrl(s)),
+                _ => Err(InputValueError::expected_type(value)),
+            }
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.0.clone())
+        }
+    }
+
+    struct Quoted(String);
+
+    #[Scalar(specified_by_url = r#"https://example.com/"quoted"\spec"#)]
+    impl ScalarType for Quoted {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            match value {
+                Value::String(s) => Ok(Quoted(s)),
+                _ => Err(InputValueError::expected_type(value)),
+            }
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.0.clone())
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn quoted(&self) -> Quoted {
+            Quoted("abc".to_string())
+        }
+
+        async fn url(&self) -> MyUrl {
+            MyUrl("https://example.com".to_string())
+        }
+
+        async fn sdl(&self, ctx: &Context<'_>) -> String {
+            ctx.schema_env.registry.export_sdl(false)
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .description("My schema")
+        .finish();
+    let query = r#"{
+        __schema {
+            description
+            directives { name isRepeatable }
+        }
+        url: __type(name: "MyUrl") { specifiedByURL }
+        string: __type(name: "String") { specifiedByURL }
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "__schema": {
+                "description": "My schema",
+                "directives": [
+                    { "name": "deprecated", "isRepeatable": false },
+                    { "name": "ifdef", "isRepeatable": false },
+                    { "name": "include", "isRepeatable": false },
+                    { "name": "skip", "isRepeatable": false },
+                    { "name": "specifiedBy", "isRepeatable": false },
+                ],
+            },
+            "url": { "specifiedByURL": "https://url.spec.whatwg.org/" },
+            "string": { "specifiedByURL": null },
+        })
+    );
+
+    let sdl = schema.execute("{ sdl }").await.data["sdl"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(sdl.contains(r#"scalar MyUrl @specifiedBy(url: "https://url.spec.whatwg.org/")"#));
+    assert!(
+        sdl.contains(r#"scalar Quoted @specifiedBy(url: "https://example.com/\"quoted\"\\spec")"#)
+    );
+    assert!(sdl.contains("\"\"\"\nMy schema\n\"\"\"\nschema {"));
+    assert!(sdl.contains("directive @ifdef on FIELD"));
+    assert!(!sdl.contains("directive @skip"));
+}
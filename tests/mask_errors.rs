@@ -0,0 +1,177 @@
+use async_graphql::extensions::{Extension, ExtensionContext, ExtensionFactory};
+use async_graphql::*;
+use futures::{Stream, StreamExt};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct CollectErrors(Arc<Mutex<Vec<ServerError>>>);
+
+impl ExtensionFactory for CollectErrors {
+    fn create(&self) -> Box<dyn Extension> {
+        Box::new(self.clone())
+    }
+}
+
+impl Extension for CollectErrors {
+    fn error(&mut self, _ctx: &ExtensionContext<'_>, err: &ServerError) {
+        self.0.lock().unwrap().push(err.clone());
+    }
+}
+
+#[async_std::test]
+pub async fn test_mask_errors() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn internal(&self) -> Result<i32> {
+            Ok("abc".parse::<i32>()?)
+        }
+
+        async fn user_facing(&self) -> Result<i32> {
+            Err(Error::new("Not found").extend(serde_json::json!({ "code": "NOT_FOUND" })))
+        }
+
+        async fn allowed(&self) -> Result<i32> {
+            struct RateLimited;
+
+            impl std::fmt::Display for RateLimited {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str("Rate limited")
+                }
+            }
+
+            Err(RateLimited.into())
+        }
+    }
+
+    let errors = CollectErrors::default();
+    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+        .mask_errors(
+            ErrorMaskPolicy::new()
+                .allow(|err| err.message == "Rate limited")
+                .error_id(|| "abc123".to_string()),
+        )
+        .extension(errors.clone())
+        .finish();
+
+    assert_eq!(
+        serde_json::to_value(&schema.execute("{ internal }").await).unwrap(),
+        serde_json::json!({
+            "data": null,
+            "errors": [{
+                "message": "Internal server error",
+                "locations": [{"line": 1, "column": 3}],
+                "path": ["internal"],
+                "extensions": {
+                    "errorId": "abc123",
+                }
+            }]
+        })
+    );
+    assert_eq!(
+        errors.0.lock().unwrap()[0],
+        ServerError {
+            message: "invalid digit found in string".to_string(),
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("internal".to_string())],
+            extensions: Some({
+                let mut extensions = serde_json::Map::new();
+                extensions.insert("errorId".to_string(), "abc123".into());
+                extensions
+            }),
+        }
+    );
+    assert!(errors
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .all(|err| err.message != "Internal server error"));
+
+    assert_eq!(
+        schema
+            .execute("{ userFacing }")
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: "Not found".to_string(),
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("userFacing".to_string())],
+            extensions: Some({
+                let mut extensions = serde_json::Map::new();
+                extensions.insert("code".to_string(), "NOT_FOUND".into());
+                extensions
+            }),
+        }]
+    );
+
+    assert_eq!(
+        schema.execute("{ allowed }").await.into_result().unwrap_err(),
+        vec![ServerError {
+            message: "Rate limited".to_string(),
+            locations: vec![Pos { line: 1, column: 3 }],
+            path: vec![PathSegment::Field("allowed".to_string())],
+            extensions: None,
+        }]
+    );
+}
+
+#[async_std::test]
+pub async fn test_mask_errors_paths() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn message(&self) -> Result<i32> {
+            Err(format!("Item {} not found", 1).into())
+        }
+
+        async fn values(&self) -> Vec<Result<i32>> {
+            vec![Ok(1), Err(Error::from(std::fmt::Error))]
+        }
+    }
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        async fn values(&self) -> impl Stream<Item = Result<i32>> {
+            futures::stream::iter(vec![Ok(1), Err(Error::from(std::fmt::Error))])
+        }
+    }
+
+    let schema = Schema::build(Query, EmptyMutation, Subscription)
+        .mask_errors(ErrorMaskPolicy::new().error_id(|| "abc123".to_string()))
+        .finish();
+
+    // Errors converted from strings are internal too, only the errors created with `Error::new`
+    // are user-facing.
+    assert_eq!(
+        schema.execute("{ message }").await.errors[0].message,
+        "Internal server error"
+    );
+
+    let errors = schema.execute("{ values }").await.errors;
+    assert_eq!(errors[0].message, "Internal server error");
+    assert_eq!(
+        errors[0].path,
+        vec![
+            PathSegment::Field("values".to_string()),
+            PathSegment::Index(1)
+        ]
+    );
+
+    let mut stream = Box::pin(schema.execute_stream("subscription { values }"));
+    assert_eq!(
+        stream.next().await.unwrap().data,
+        serde_json::json!({ "values": 1 })
+    );
+    let errors = stream.next().await.unwrap().errors;
+    assert_eq!(errors[0].message, "Internal server error");
+    assert_eq!(
+        errors[0].extensions.as_ref().unwrap()["errorId"],
+        serde_json::json!("abc123")
+    );
+}
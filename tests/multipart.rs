@@ -0,0 +1,285 @@
+use async_graphql::http::{
+    receive_body, MemoryStorage, MultipartOptions, TempFileStorage, UploadStorage,
+};
+use async_graphql::*;
+use bytes::Bytes;
+use futures::stream::{BoxStream, TryStreamExt};
+use std::io::{self, Read};
+
+fn multipart_body(operations: &str, map: &str, files: &[(&str, &str, &str)]) -> Vec<u8> {
+    let mut body = String::new();
+    body += "--BOUNDARY\r\nContent-Disposition: form-data; name=\"operations\"\r\n\r\n";
+    body += operations;
+    body += "\r\n--BOUNDARY\r\nContent-Disposition: form-data; name=\"map\"\r\n\r\n";
+    body += map;
+    for (name, filename, content) in files {
+        body += &format!(
+            "\r\n--BOUNDARY\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: text/plain\r\n\r\n{}",
+            name, filename, content
+        );
+    }
+    body += "\r\n--BOUNDARY--\r\n";
+    body.into_bytes()
+}
+
+async fn receive(body: Vec<u8>, opts: MultipartOptions) -> Result<Request, ParseRequestError> {
+    receive_body(
+        Some("multipart/form-data; boundary=BOUNDARY"),
+        futures::io::Cursor::new(body),
+        opts,
+    )
+    .await
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct Mutation;
+
+#[Object]
+impl Mutation {
+    async fn read(&self, ctx: &Context<'_>, file: Upload) -> Result<String> {
+        let filename = file.filename().to_string();
+        let mut content = String::new();
+        file.try_into_read(ctx)?.read_to_string(&mut content)?;
+        Ok(format!("{}:{}", filename, content))
+    }
+
+    async fn read_file(&self, file: Upload) -> Result<String> {
+        let mut content = String::new();
+        file.into_read().read_to_string(&mut content)?;
+        Ok(content)
+    }
+
+    async fn size(&self, ctx: &Context<'_>, file: Upload) -> Result<i32> {
+        Ok(file.try_size(ctx)? as i32)
+    }
+
+    async fn custom(&self, ctx: &Context<'_>, file: Upload) -> Option<String> {
+        file.custom::<String>(ctx).map(|key| key.to_string())
+    }
+
+    async fn stream(&self, ctx: &Context<'_>, files: Vec<StreamedUpload>) -> Result<Vec<String>> {
+        let mut res = Vec::new();
+        for file in files.into_iter().rev() {
+            let stream = file.open(ctx).await?;
+            let filename = stream.filename().to_string();
+            let content = stream
+                .try_fold(Vec::new(), |mut content, chunk| async move {
+                    content.extend_from_slice(&chunk);
+                    Ok(content)
+                })
+                .await?;
+            res.push(format!("{}:{}", filename, String::from_utf8(content)?));
+        }
+        Ok(res)
+    }
+}
+
+#[async_std::test]
+pub async fn test_upload_storage() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let operations = r#"{ "query": "mutation($file: Upload!) { read(file: $file) size(file: $file) custom(file: $file) }", "variables": { "file": null } }"#;
+    let map = r#"{ "0": ["variables.file"] }"#;
+    let body = multipart_body(operations, map, &[("0", "a.txt", "hello")]);
+
+    for opts in vec![
+        MultipartOptions::default(),
+        MultipartOptions::default().storage(TempFileStorage),
+        MultipartOptions::default().storage(MemoryStorage),
+    ] {
+        let request = receive(body.clone(), opts).await.unwrap();
+        assert_eq!(
+            schema.execute(request).await.into_result().unwrap().data,
+            serde_json::json!({ "read": "a.txt:hello", "size": 5, "custom": null })
+        );
+    }
+
+    struct KeyStorage;
+
+    #[async_trait::async_trait]
+    impl UploadStorage for KeyStorage {
+        async fn store(
+            &self,
+            filename: &str,
+            _content_type: Option<&str>,
+            content: BoxStream<'static, io::Result<Bytes>>,
+        ) -> io::Result<UploadContent> {
+            let content = content
+                .try_fold(Vec::new(), |mut data, chunk| async move {
+                    data.extend_from_slice(&chunk);
+                    Ok(data)
+                })
+                .await?;
+            Ok(UploadContent::custom(format!(
+                "{}/{}",
+                filename,
+                content.len()
+            )))
+        }
+    }
+
+    let operations = r#"{ "query": "mutation($file: Upload!) { custom(file: $file) }", "variables": { "file": null } }"#;
+    let body = multipart_body(operations, map, &[("0", "a.txt", "hello")]);
+    let request = receive(body, MultipartOptions::default().storage(KeyStorage))
+        .await
+        .unwrap();
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        serde_json::json!({ "custom": "a.txt/5" })
+    );
+}
+
+#[async_std::test]
+pub async fn test_stream_uploads() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let operations = r#"{ "query": "mutation($files: [Upload!]!) { stream(files: $files) }", "variables": { "files": [null, null] } }"#;
+    let map = r#"{ "0": ["variables.files.0"], "1": ["variables.files.1"] }"#;
+    let body = multipart_body(
+        operations,
+        map,
+        &[("0", "a.txt", "hello"), ("1", "b.txt", "world")],
+    );
+
+    for opts in vec![
+        MultipartOptions::default(),
+        MultipartOptions::default().stream_uploads(true),
+        MultipartOptions::default()
+            .stream_uploads(true)
+            .storage(MemoryStorage),
+    ] {
+        let request = receive(body.clone(), opts).await.unwrap();
+        assert_eq!(
+            schema.execute(request).await.into_result().unwrap().data,
+            serde_json::json!({ "stream": ["b.txt:world", "a.txt:hello"] })
+        );
+    }
+
+    let operations = r#"{ "query": "mutation($file: Upload!) { read(file: $file) }", "variables": { "file": null } }"#;
+    let map = r#"{ "0": ["variables.file"] }"#;
+    let body = multipart_body(operations, map, &[("0", "a.txt", "hello")]);
+    let request = receive(body, MultipartOptions::default().stream_uploads(true))
+        .await
+        .unwrap();
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap_err()[0].message,
+        "Failed to parse \"Upload\": The file is streamed, receive it as a `StreamedUpload`."
+    );
+}
+
+#[async_std::test]
+pub async fn test_read_file_upload() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let operations = r#"{ "query": "mutation($file: Upload!) { readFile(file: $file) }", "variables": { "file": null } }"#;
+    let map = r#"{ "0": ["variables.file"] }"#;
+    let body = multipart_body(operations, map, &[("0", "a.txt", "hello")]);
+
+    let request = receive(body.clone(), MultipartOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap().data,
+        serde_json::json!({ "readFile": "hello" })
+    );
+
+    let request = receive(body, MultipartOptions::default().storage(MemoryStorage))
+        .await
+        .unwrap();
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap_err()[0].message,
+        "The upload is not stored in a file, it must be read with the context."
+    );
+}
+
+#[async_std::test]
+pub async fn test_forged_upload() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let request: Request = serde_json::from_value(serde_json::json!({
+        "query": "mutation($file: Upload!) { readFile(file: $file) }",
+        "variables": { "file": { "__upload": 0, "filename": "a.txt" } },
+    }))
+    .unwrap();
+    assert!(schema.execute(request).await.is_err());
+
+    let request =
+        Request::new(r#"mutation { readFile(file: { __upload: 0, filename: "a.txt" }) }"#);
+    assert!(schema.execute(request).await.is_err());
+}
+
+#[async_std::test]
+pub async fn test_multipart_limits() {
+    let operations = r#"{ "query": "mutation($file: Upload!) { size(file: $file) }", "variables": { "file": null } }"#;
+    let map = r#"{ "0": ["variables.file"] }"#;
+    let content = "x".repeat(300);
+    let body = multipart_body(operations, map, &[("0", "a.txt", &content)]);
+
+    assert!(
+        receive(body.clone(), MultipartOptions::default().max_file_size(300))
+            .await
+            .is_ok()
+    );
+    assert!(matches!(
+        receive(body.clone(), MultipartOptions::default().max_file_size(299)).await,
+        Err(ParseRequestError::PayloadTooLarge)
+    ));
+    assert!(matches!(
+        receive(
+            body.clone(),
+            MultipartOptions::default().max_request_size(500)
+        )
+        .await,
+        Err(ParseRequestError::PayloadTooLarge)
+    ));
+    assert!(matches!(
+        receive(
+            body.clone(),
+            MultipartOptions::default()
+                .max_file_size(300)
+                .max_num_files(1)
+        )
+        .await,
+        Err(ParseRequestError::PayloadTooLarge)
+    ));
+    assert!(receive(
+        body.clone(),
+        MultipartOptions::default()
+            .max_file_size(300)
+            .max_num_files(3)
+    )
+    .await
+    .is_ok());
+
+    let mut truncated = body;
+    truncated.truncate(truncated.len() - 20);
+    assert!(matches!(
+        receive(truncated, MultipartOptions::default()).await,
+        Err(ParseRequestError::InvalidMultipart(_))
+    ));
+}
+
+#[async_std::test]
+pub async fn test_missing_upload() {
+    let operations = r#"{ "query": "mutation($file: Upload!) { stream(files: [$file]) }", "variables": { "file": null } }"#;
+    let map = r#"{ "0": ["variables.file"] }"#;
+    let body = multipart_body(operations, map, &[]);
+
+    assert!(matches!(
+        receive(body.clone(), MultipartOptions::default()).await,
+        Err(ParseRequestError::MissingFiles)
+    ));
+
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let request = receive(body, MultipartOptions::default().stream_uploads(true))
+        .await
+        .unwrap();
+    assert_eq!(
+        schema.execute(request).await.into_result().unwrap_err()[0].message,
+        "The upload is missing from the request."
+    );
+}
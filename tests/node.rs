@@ -0,0 +1,223 @@
+use async_graphql::*;
+
+#[derive(SimpleObject)]
+struct User {
+    #[graphql(global_id)]
+    id: i32,
+    name: String,
+}
+
+struct Post {
+    id: String,
+}
+
+#[Object]
+impl Post {
+    #[graphql(global_id)]
+    async fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn title(&self) -> String {
+        format!("Post {}", self.id)
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn me(&self) -> User {
+        User {
+            id: 1,
+            name: "Alice".to_string(),
+        }
+    }
+
+    async fn post(&self) -> Post {
+        Post {
+            id: "hello".to_string(),
+        }
+    }
+
+    #[graphql(node)]
+    async fn find_user(&self, id: i32) -> Option<User> {
+        match id {
+            1 => Some(User {
+                id: 1,
+                name: "Alice".to_string(),
+            }),
+            2 => Some(User {
+                id: 2,
+                name: "Bob".to_string(),
+            }),
+            _ => None,
+        }
+    }
+
+    #[graphql(node)]
+    async fn find_post(&self, _ctx: &Context<'_>, id: String) -> Result<Post> {
+        Ok(Post { id })
+    }
+}
+
+#[async_std::test]
+pub async fn test_global_id() {
+    let id = GlobalId::new("User", 1);
+    assert_eq!(id.type_name(), "User");
+    assert_eq!(id.id(), "1");
+    assert_eq!(id.parse_id::<i32>(), Ok(1));
+    assert_eq!(id.to_id(), "VXNlcjox");
+    assert_eq!(GlobalId::from_id(&id.to_id()), Some(id));
+    assert_eq!(GlobalId::from_id(&ID::from("abc")), None);
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ me { id name } post { id } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "me": { "id": GlobalId::new("User", 1).to_id(), "name": "Alice" },
+            "post": { "id": GlobalId::new("Post", "hello").to_id() },
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_node_field() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = format!(
+        r#"{{
+            user: node(id: "{}") {{ id __typename ... on User {{ name }} }}
+            post: node(id: "{}") {{ ...PostFields }}
+            missing: node(id: "{}") {{ id }}
+            unknownType: node(id: "{}") {{ id }}
+        }}
+
+        fragment PostFields on Post {{ title }}"#,
+        GlobalId::new("User", 2).to_id().as_str(),
+        GlobalId::new("Post", "abc").to_id().as_str(),
+        GlobalId::new("User", 3).to_id().as_str(),
+        GlobalId::new("Comment", 1).to_id().as_str(),
+    );
+    assert_eq!(
+        schema.execute(&query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "user": {
+                "id": GlobalId::new("User", 2).to_id(),
+                "__typename": "User",
+                "name": "Bob",
+            },
+            "post": { "title": "Post abc" },
+            "missing": null,
+            "unknownType": null,
+        })
+    );
+
+    let query = format!(
+        r#"{{ nodes(ids: ["{}", "{}", "{}"]) {{ ... on User {{ name }} ... on Post {{ title }} }} }}"#,
+        GlobalId::new("User", 1).to_id().as_str(),
+        GlobalId::new("User", 4).to_id().as_str(),
+        GlobalId::new("Post", "xyz").to_id().as_str(),
+    );
+    assert_eq!(
+        schema.execute(&query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "nodes": [{ "name": "Alice" }, null, { "title": "Post xyz" }],
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ node(id: "abc") { id } }"#)
+            .await
+            .into_result()
+            .unwrap_err(),
+        vec![ServerError {
+            message: r#"Failed to parse "ID": Invalid global id."#.to_owned(),
+            locations: vec![Pos {
+                line: 1,
+                column: 12
+            }],
+            path: vec![PathSegment::Field("node".to_owned())],
+            extensions: None,
+        }]
+    );
+}
+
+#[async_std::test]
+pub async fn test_node_introspection() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"{
+        node: __type(name: "Node") { kind fields { name } possibleTypes { name } }
+        user: __type(name: "User") { interfaces { name } }
+        query: __type(name: "Query") { fields { name } }
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "node": {
+                "kind": "INTERFACE",
+                "fields": [{ "name": "id" }],
+                "possibleTypes": [{ "name": "User" }, { "name": "Post" }],
+            },
+            "user": { "interfaces": [{ "name": "Node" }] },
+            "query": {
+                "fields": [
+                    { "name": "me" },
+                    { "name": "post" },
+                    { "name": "node" },
+                    { "name": "nodes" },
+                ],
+            },
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_global_id_shares_id_scalar() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn global(&self, id: GlobalId) -> GlobalId {
+            id
+        }
+
+        async fn plain(&self, id: ID) -> ID {
+            id
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    // `GlobalId` is registered first, integer `ID` literals must still be valid.
+    assert_eq!(
+        schema
+            .execute("{ plain(id: 1) }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({ "plain": "1" })
+    );
+    assert_eq!(
+        schema
+            .execute(r#"{ global(id: "VXNlcjox") }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({ "global": "VXNlcjox" })
+    );
+    assert_eq!(
+        schema
+            .execute("{ __type(name: \"ID\") { kind } }")
+            .await
+            .data,
+        serde_json::json!({ "__type": { "kind": "SCALAR" } })
+    );
+}
@@ -0,0 +1,119 @@
+use async_graphql::pagination::*;
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_pagination() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn numbers(&self, #[graphql(default)] page: PageArgs) -> Result<Page<i32>> {
+            query(page, 2, 5, |offset, limit| async move {
+                let numbers = (0..10).collect::<Vec<i32>>();
+                Ok(Page::from_slice(&numbers, offset, limit))
+            })
+            .await
+        }
+
+        async fn lazy(&self, #[graphql(default)] page: PageArgs) -> Result<Page<i32>> {
+            query(page, 3, 3, |offset, limit| async move {
+                let items = (offset as i32..).take(limit).collect();
+                Ok(Page::new(items, offset, limit, true)
+                    .with_total_count(|| async move { Ok(1000) }))
+            })
+            .await
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        schema
+            .execute("{ numbers { items totalCount pageInfo { offset limit hasMore } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "numbers": {
+                "items": [0, 1],
+                "totalCount": 10,
+                "pageInfo": { "offset": 0, "limit": 2, "hasMore": true },
+            },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ numbers(page: { offset: 8, limit: 5 }) { items pageInfo { hasMore } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "numbers": { "items": [8, 9], "pageInfo": { "hasMore": false } },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ numbers(page: { offset: 20 }) { items pageInfo { hasMore } } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "numbers": { "items": [], "pageInfo": { "hasMore": false } },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ lazy(page: { offset: 4 }) { items totalCount } }")
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "lazy": { "items": [4, 5, 6], "totalCount": 1000 },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ numbers(page: { limit: 6 }) { items } }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "The \"limit\" parameter must not be greater than 5"
+    );
+
+    assert_eq!(
+        schema
+            .execute("{ numbers(page: { offset: -1 }) { items } }")
+            .await
+            .into_result()
+            .unwrap_err()[0]
+            .message,
+        "The \"offset\" parameter must be a non-negative number"
+    );
+
+    assert_eq!(
+        schema
+            .execute(r#"{ __type(name: "IntPage") { fields { name } } }"#)
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "__type": {
+                "fields": [
+                    { "name": "items" },
+                    { "name": "totalCount" },
+                    { "name": "pageInfo" },
+                ],
+            },
+        })
+    );
+}
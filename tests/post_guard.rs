@@ -325,3 +325,62 @@ pub async fn test_post_guard_generic() {
         }]
     );
 }
+
+#[async_std::test]
+pub async fn test_post_guard_combinators() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(post_guard(or(
+            RoleGuard(role = "Role::Admin"),
+            UserGuard(username = r#""test""#, value = "10")
+        )))]
+        async fn value(&self) -> i32 {
+            10
+        }
+
+        #[graphql(post_guard(not(RoleGuard(role = "Role::Admin"))))]
+        async fn guest_only(&self) -> i32 {
+            20
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let execute = |query: &'static str, role: Role, username: &'static str| {
+        let schema = schema.clone();
+        async move {
+            schema
+                .execute(
+                    Request::new(query)
+                        .data(role)
+                        .data(Username(username.to_string())),
+                )
+                .await
+                .into_result()
+                .map(|resp| resp.data)
+                .map_err(|mut errors| errors.remove(0).message)
+        }
+    };
+
+    assert_eq!(
+        execute("{ value }", Role::Admin, "abc").await,
+        Ok(serde_json::json!({"value": 10}))
+    );
+    assert_eq!(
+        execute("{ value }", Role::Guest, "test").await,
+        Ok(serde_json::json!({"value": 10}))
+    );
+    assert_eq!(
+        execute("{ value }", Role::Guest, "abc").await,
+        Err("Forbidden".to_string())
+    );
+    assert_eq!(
+        execute("{ guestOnly }", Role::Guest, "abc").await,
+        Ok(serde_json::json!({"guestOnly": 20}))
+    );
+    assert_eq!(
+        execute("{ guestOnly }", Role::Admin, "abc").await,
+        Err("Forbidden".to_string())
+    );
+}
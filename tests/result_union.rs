@@ -0,0 +1,149 @@
+use async_graphql::*;
+
+#[derive(SimpleObject)]
+struct User {
+    name: String,
+}
+
+#[derive(SimpleObject)]
+struct EmailTaken {
+    email: String,
+}
+
+#[derive(SimpleObject)]
+struct RateLimited {
+    retry_after: i32,
+}
+
+#[derive(Union)]
+enum SignUpError {
+    EmailTaken(EmailTaken),
+    RateLimited(RateLimited),
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn sign_up(&self, email: String) -> Result<User, SignUpError> {
+        match email.as_str() {
+            "taken@example.com" => Err(EmailTaken { email }.into()),
+            "limited@example.com" => Err(RateLimited { retry_after: 30 }.into()),
+            _ => Ok(User {
+                name: "abc".to_string(),
+            }),
+        }
+    }
+}
+
+#[async_std::test]
+pub async fn test_result_union() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = |email: &str| {
+        format!(
+            r#"{{
+                signUp(email: "{}") {{
+                    __typename
+                    ... on User {{ name }}
+                    ... on EmailTaken {{ email }}
+                    ... on RateLimited {{ retryAfter }}
+                }}
+            }}"#,
+            email
+        )
+    };
+
+    assert_eq!(
+        schema
+            .execute(query("user@example.com"))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "signUp": { "__typename": "User", "name": "abc" }
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(query("taken@example.com"))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "signUp": { "__typename": "EmailTaken", "email": "taken@example.com" }
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(query("limited@example.com"))
+            .await
+            .into_result()
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "signUp": { "__typename": "RateLimited", "retryAfter": 30 }
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_result_union_type() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"{
+        __type(name: "UserOrError") {
+            kind
+            possibleTypes { name }
+        }
+        query: __type(name: "Query") {
+            fields { name type { kind ofType { name } } }
+        }
+    }"#;
+
+    assert_eq!(
+        schema.execute(query).await.into_result().unwrap().data,
+        serde_json::json!({
+            "__type": {
+                "kind": "UNION",
+                "possibleTypes": [
+                    { "name": "User" },
+                    { "name": "EmailTaken" },
+                    { "name": "RateLimited" },
+                ]
+            },
+            "query": {
+                "fields": [{
+                    "name": "signUp",
+                    "type": { "kind": "NON_NULL", "ofType": { "name": "UserOrError" } }
+                }]
+            }
+        })
+    );
+}
+
+#[async_std::test]
+#[should_panic(expected = "Union \"UserOrError\" is registered with different error types")]
+pub async fn test_result_union_conflict() {
+    #[derive(Union)]
+    enum UpdateError {
+        RateLimited(RateLimited),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn sign_up(&self) -> Result<User, SignUpError> {
+            Err(RateLimited { retry_after: 30 }.into())
+        }
+
+        async fn update(&self) -> Result<User, UpdateError> {
+            Err(RateLimited { retry_after: 30 }.into())
+        }
+    }
+
+    Schema::new(Query, EmptyMutation, EmptySubscription);
+}
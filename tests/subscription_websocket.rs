@@ -1,6 +1,23 @@
+use async_graphql::http::{WebSocketOptions, WebSocketProtocols, WsMessage};
 use async_graphql::*;
 use futures::channel::mpsc;
 use futures::{SinkExt, Stream, StreamExt};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn unwrap_text(msg: WsMessage) -> String {
+    match msg {
+        WsMessage::Text(text) => text,
+        WsMessage::Close(_, _) => panic!("Not a text message"),
+    }
+}
+
+fn unwrap_close(msg: WsMessage) -> (u16, String) {
+    match msg {
+        WsMessage::Close(code, reason) => (code, reason),
+        WsMessage::Text(_) => panic!("Not a close message"),
+    }
+}
 
 #[async_std::test]
 pub async fn test_subscription_ws_transport() {
@@ -20,7 +37,7 @@ pub async fn test_subscription_ws_transport() {
 
     let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
     let (mut tx, rx) = mpsc::unbounded();
-    let mut stream = http::WebSocket::new(schema, rx);
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::SubscriptionsTransportWS);
 
     tx.send(
         serde_json::to_string(&serde_json::json!({
@@ -32,7 +49,8 @@ pub async fn test_subscription_ws_transport() {
     .unwrap();
 
     assert_eq!(
-        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap(),
+        serde_json::from_str::<serde_json::Value>(&unwrap_text(stream.next().await.unwrap()))
+            .unwrap(),
         serde_json::json!({
             "type": "connection_ack",
         }),
@@ -53,7 +71,8 @@ pub async fn test_subscription_ws_transport() {
 
     for i in 0..10 {
         assert_eq!(
-            serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&unwrap_text(stream.next().await.unwrap()))
+                .unwrap(),
             serde_json::json!({
                 "type": "data",
                 "id": "1",
@@ -63,7 +82,8 @@ pub async fn test_subscription_ws_transport() {
     }
 
     assert_eq!(
-        serde_json::from_str::<serde_json::Value>(&stream.next().await.unwrap()).unwrap(),
+        serde_json::from_str::<serde_json::Value>(&unwrap_text(stream.next().await.unwrap()))
+            .unwrap(),
         serde_json::json!({
             "type": "complete",
             "id": "1",
@@ -97,17 +117,18 @@ pub async fn test_subscription_ws_transport_with_token() {
     let mut stream = http::WebSocket::with_data(
         schema,
         rx,
-        Some(|value| {
+        Some(|value| async move {
             #[derive(serde::Deserialize)]
             struct Payload {
                 token: String,
             }
 
-            let payload: Payload = serde_json::from_value(value).unwrap();
+            let payload: Payload = serde_json::from_value(value)?;
             let mut data = Data::default();
             data.insert(Token(payload.token));
             Ok(data)
         }),
+        WebSocketProtocols::SubscriptionsTransportWS,
     );
 
     tx.send(
@@ -124,7 +145,7 @@ pub async fn test_subscription_ws_transport_with_token() {
         Some(serde_json::json!({
             "type": "connection_ack",
         })),
-        serde_json::from_str(&stream.next().await.unwrap()).unwrap()
+        serde_json::from_str(&unwrap_text(stream.next().await.unwrap())).unwrap()
     );
 
     tx.send(
@@ -147,7 +168,7 @@ pub async fn test_subscription_ws_transport_with_token() {
                 "id": "1",
                 "payload": { "data": { "values": i } },
             })),
-            serde_json::from_str(&stream.next().await.unwrap()).unwrap()
+            serde_json::from_str(&unwrap_text(stream.next().await.unwrap())).unwrap()
         );
     }
 
@@ -156,7 +177,7 @@ pub async fn test_subscription_ws_transport_with_token() {
             "type": "complete",
             "id": "1",
         })),
-        serde_json::from_str(&stream.next().await.unwrap()).unwrap()
+        serde_json::from_str(&unwrap_text(stream.next().await.unwrap())).unwrap()
     );
 }
 
@@ -193,7 +214,7 @@ pub async fn test_subscription_ws_transport_error() {
 
     let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
     let (mut tx, rx) = mpsc::unbounded();
-    let mut stream = http::WebSocket::new(schema, rx);
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::SubscriptionsTransportWS);
 
     tx.send(
         serde_json::to_string(&serde_json::json!({
@@ -208,7 +229,7 @@ pub async fn test_subscription_ws_transport_error() {
         Some(serde_json::json!({
             "type": "connection_ack",
         })),
-        serde_json::from_str(&stream.next().await.unwrap()).unwrap()
+        serde_json::from_str(&unwrap_text(stream.next().await.unwrap())).unwrap()
     );
 
     tx.send(
@@ -231,7 +252,7 @@ pub async fn test_subscription_ws_transport_error() {
                 "id": "1",
                 "payload": { "data": { "events": { "value": i } } },
             })),
-            serde_json::from_str(&stream.next().await.unwrap()).unwrap()
+            serde_json::from_str(&unwrap_text(stream.next().await.unwrap())).unwrap()
         );
     }
 
@@ -248,7 +269,40 @@ pub async fn test_subscription_ws_transport_error() {
                 }],
             },
         })),
-        serde_json::from_str(&stream.next().await.unwrap()).unwrap()
+        serde_json::from_str(&unwrap_text(stream.next().await.unwrap())).unwrap()
+    );
+
+    assert_eq!(
+        Some(serde_json::json!({
+            "type": "complete",
+            "id": "1",
+        })),
+        serde_json::from_str(&unwrap_text(stream.next().await.unwrap())).unwrap()
+    );
+
+    tx.send(
+        serde_json::to_string(&serde_json::json!({
+            "type": "start",
+            "id": "2",
+            "payload": {
+                "query": "subscription { events { unknown } }"
+            },
+        }))
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        Some(serde_json::json!({
+            "type": "error",
+            "id": "2",
+            "payload": [{
+                "message": "Unknown field \"unknown\" on type \"Event\".",
+                "locations": [{"line": 1, "column": 25}],
+            }],
+        })),
+        serde_json::from_str(&unwrap_text(stream.next().await.unwrap())).unwrap()
     );
 }
 
@@ -265,7 +319,7 @@ pub async fn test_query_over_websocket() {
 
     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
     let (mut tx, rx) = mpsc::unbounded();
-    let mut stream = http::WebSocket::new(schema, rx);
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::SubscriptionsTransportWS);
 
     tx.send(
         serde_json::to_string(&serde_json::json!({
@@ -280,7 +334,7 @@ pub async fn test_query_over_websocket() {
         Some(serde_json::json!({
         "type": "connection_ack",
         })),
-        serde_json::from_str(&stream.next().await.unwrap()).unwrap()
+        serde_json::from_str(&unwrap_text(stream.next().await.unwrap())).unwrap()
     );
 
     tx.send(
@@ -302,7 +356,7 @@ pub async fn test_query_over_websocket() {
             "id": "1",
             "payload": { "data": { "value": 999 } },
         })),
-        serde_json::from_str(&stream.next().await.unwrap()).unwrap()
+        serde_json::from_str(&unwrap_text(stream.next().await.unwrap())).unwrap()
     );
 
     assert_eq!(
@@ -310,6 +364,416 @@ pub async fn test_query_over_websocket() {
             "type": "complete",
             "id": "1",
         })),
-        serde_json::from_str(&stream.next().await.unwrap()).unwrap()
+        serde_json::from_str(&unwrap_text(stream.next().await.unwrap())).unwrap()
+    );
+}
+
+#[async_std::test]
+pub async fn test_subscription_graphql_transport_ws() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self) -> impl Stream<Item = i32> {
+            futures::stream::iter(0..3)
+        }
+
+        async fn forever(&self) -> impl Stream<Item = i32> {
+            futures::stream::pending()
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema.clone(), rx, WebSocketProtocols::GraphQLWS);
+
+    tx.send(serde_json::json!({ "type": "connection_init" }).to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&unwrap_text(stream.next().await.unwrap()))
+            .unwrap(),
+        serde_json::json!({ "type": "connection_ack" }),
+    );
+
+    tx.send(serde_json::json!({ "type": "ping" }).to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&unwrap_text(stream.next().await.unwrap()))
+            .unwrap(),
+        serde_json::json!({ "type": "pong" }),
+    );
+
+    tx.send(
+        serde_json::json!({
+            "type": "subscribe",
+            "id": "1",
+            "payload": { "query": "subscription { values }" },
+        })
+        .to_string(),
+    )
+    .await
+    .unwrap();
+    for i in 0..3 {
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&unwrap_text(stream.next().await.unwrap()))
+                .unwrap(),
+            serde_json::json!({
+                "type": "next",
+                "id": "1",
+                "payload": { "data": { "values": i } },
+            }),
+        );
+    }
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&unwrap_text(stream.next().await.unwrap()))
+            .unwrap(),
+        serde_json::json!({ "type": "complete", "id": "1" }),
+    );
+
+    // A subscription completed by the client is not acknowledged.
+    tx.send(
+        serde_json::json!({
+            "type": "subscribe",
+            "id": "2",
+            "payload": { "query": "subscription { forever }" },
+        })
+        .to_string(),
+    )
+    .await
+    .unwrap();
+    tx.send(serde_json::json!({ "type": "complete", "id": "2" }).to_string())
+        .await
+        .unwrap();
+    tx.send(serde_json::json!({ "type": "ping" }).to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&unwrap_text(stream.next().await.unwrap()))
+            .unwrap(),
+        serde_json::json!({ "type": "pong" }),
+    );
+
+    // Subscription ids must be unique.
+    for _ in 0..2 {
+        tx.send(
+            serde_json::json!({
+                "type": "subscribe",
+                "id": "3",
+                "payload": { "query": "subscription { forever }" },
+            })
+            .to_string(),
+        )
+        .await
+        .unwrap();
+    }
+    assert_eq!(
+        stream.next().await.unwrap(),
+        http::WsMessage::Close(4409, "Subscriber for 3 already exists".to_string())
+    );
+    assert!(stream.next().await.is_none());
+}
+
+#[async_std::test]
+pub async fn test_graphql_transport_ws_errors() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(&self) -> i32 {
+            10
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema.clone(), rx, WebSocketProtocols::GraphQLWS);
+    tx.send(
+        serde_json::json!({
+            "type": "subscribe",
+            "id": "1",
+            "payload": { "query": "{ value }" },
+        })
+        .to_string(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        unwrap_close(stream.next().await.unwrap()),
+        (4401, "Unauthorized".to_string())
+    );
+
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema.clone(), rx, WebSocketProtocols::GraphQLWS);
+    tx.send(serde_json::json!({ "type": "start", "id": "1" }).to_string())
+        .await
+        .unwrap();
+    assert_eq!(unwrap_close(stream.next().await.unwrap()).0, 4400);
+
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(schema.clone(), rx, WebSocketProtocols::GraphQLWS);
+    for _ in 0..2 {
+        tx.send(serde_json::json!({ "type": "connection_init" }).to_string())
+            .await
+            .unwrap();
+    }
+    unwrap_text(stream.next().await.unwrap());
+    assert_eq!(
+        unwrap_close(stream.next().await.unwrap()),
+        (4429, "Too many initialisation requests".to_string())
+    );
+
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::with_data(
+        schema,
+        rx,
+        Some(|_| async { Err("forbidden".into()) }),
+        WebSocketProtocols::GraphQLWS,
+    );
+    tx.send(serde_json::json!({ "type": "connection_init", "payload": {} }).to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        unwrap_close(stream.next().await.unwrap()),
+        (4403, "forbidden".to_string())
+    );
+}
+
+#[async_std::test]
+pub async fn test_websocket_options() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn forever(&self) -> impl Stream<Item = i32> {
+            futures::stream::pending()
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let start = |id: &str| {
+        serde_json::json!({
+            "type": "start",
+            "id": id,
+            "payload": { "query": "subscription { forever }" },
+        })
+        .to_string()
+    };
+
+    // The operations are rejected before the connection is initialized.
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::new(
+        schema.clone(),
+        rx,
+        WebSocketProtocols::SubscriptionsTransportWS,
+    )
+    .options(WebSocketOptions::default().max_operations(1));
+    tx.send(start("1")).await.unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&unwrap_text(stream.next().await.unwrap()))
+            .unwrap(),
+        serde_json::json!({
+            "type": "error",
+            "id": "1",
+            "payload": [{ "message": "The connection has not been initialized" }],
+        }),
+    );
+
+    tx.send(serde_json::json!({ "type": "connection_init" }).to_string())
+        .await
+        .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&unwrap_text(stream.next().await.unwrap()))
+            .unwrap(),
+        serde_json::json!({ "type": "connection_ack" }),
+    );
+
+    tx.send(start("1")).await.unwrap();
+    tx.send(start("2")).await.unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&unwrap_text(stream.next().await.unwrap()))
+            .unwrap(),
+        serde_json::json!({
+            "type": "error",
+            "id": "2",
+            "payload": [{ "message": "Too many active operations" }],
+        }),
+    );
+
+    // Keep-alive messages are sent after the connection is initialized.
+    for protocol in &[
+        WebSocketProtocols::SubscriptionsTransportWS,
+        WebSocketProtocols::GraphQLWS,
+    ] {
+        let (mut tx, rx) = mpsc::unbounded();
+        let mut stream = http::WebSocket::new(schema.clone(), rx, *protocol)
+            .options(WebSocketOptions::default().keepalive_interval(Duration::from_millis(10)));
+        tx.send(serde_json::json!({ "type": "connection_init" }).to_string())
+            .await
+            .unwrap();
+        unwrap_text(stream.next().await.unwrap());
+        let expected = match protocol {
+            WebSocketProtocols::SubscriptionsTransportWS => serde_json::json!({ "type": "ka" }),
+            WebSocketProtocols::GraphQLWS => serde_json::json!({ "type": "ping" }),
+        };
+        for _ in 0..2 {
+            assert_eq!(
+                serde_json::from_str::<serde_json::Value>(&unwrap_text(
+                    stream.next().await.unwrap()
+                ))
+                .unwrap(),
+                expected,
+            );
+        }
+    }
+
+    // The connection is closed if it is not initialized in time.
+    let (_tx, rx) = mpsc::unbounded::<String>();
+    let mut stream = http::WebSocket::new(schema, rx, WebSocketProtocols::GraphQLWS)
+        .options(WebSocketOptions::default().connection_init_timeout(Duration::from_millis(10)));
+    assert_eq!(
+        stream.next().await.unwrap(),
+        http::WsMessage::Close(4408, "Connection initialisation timeout".to_string())
+    );
+    assert!(stream.next().await.is_none());
+}
+
+#[async_std::test]
+pub async fn test_websocket_hooks() {
+    struct Token(String);
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {}
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn values(&self, ctx: &Context<'_>) -> impl Stream<Item = String> {
+            futures::stream::iter(vec![ctx.data_unchecked::<Token>().0.clone()])
+        }
+
+        async fn forever(&self) -> impl Stream<Item = i32> {
+            futures::stream::pending()
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Events(Arc<Mutex<Vec<String>>>);
+
+    impl Events {
+        fn push(&self, data: &Data, event: &str) {
+            let token = &data.data_opt::<Token>().unwrap().0;
+            self.0.lock().unwrap().push(format!("{}:{}", event, token));
+        }
+    }
+
+    impl http::WebSocketHooks for Events {
+        fn on_connect(&self, data: &Data) {
+            self.push(data, "connect");
+        }
+
+        fn on_operation_start(&self, data: &Data, id: &str, _request: &Request) {
+            self.push(data, &format!("start {}", id));
+        }
+
+        fn on_operation_complete(&self, data: &Data, id: &str) {
+            self.push(data, &format!("complete {}", id));
+        }
+
+        fn on_disconnect(&self, data: &Data) {
+            self.push(data, "disconnect");
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, SubscriptionRoot);
+    let events = Events::default();
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut stream = http::WebSocket::with_data(
+        schema,
+        rx,
+        Some(|value: serde_json::Value| async move {
+            async_std::task::sleep(Duration::from_millis(10)).await;
+            let mut data = Data::default();
+            data.insert(Token(
+                value["token"].as_str().unwrap_or_default().to_string(),
+            ));
+            Ok(data)
+        }),
+        WebSocketProtocols::SubscriptionsTransportWS,
+    )
+    .options(WebSocketOptions::default().hooks(events.clone()));
+
+    // The operation is started once the connection is initialized.
+    tx.send(
+        serde_json::json!({
+            "type": "connection_init",
+            "payload": { "token": "123456" },
+        })
+        .to_string(),
+    )
+    .await
+    .unwrap();
+    for (id, query) in &[
+        ("1", "subscription { values }"),
+        ("2", "subscription { forever }"),
+    ] {
+        tx.send(
+            serde_json::json!({
+                "type": "start",
+                "id": id,
+                "payload": { "query": query },
+            })
+            .to_string(),
+        )
+        .await
+        .unwrap();
+    }
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&unwrap_text(stream.next().await.unwrap()))
+            .unwrap(),
+        serde_json::json!({ "type": "connection_ack" }),
+    );
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&unwrap_text(stream.next().await.unwrap()))
+            .unwrap(),
+        serde_json::json!({
+            "type": "data",
+            "id": "1",
+            "payload": { "data": { "values": "123456" } },
+        }),
+    );
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&unwrap_text(stream.next().await.unwrap()))
+            .unwrap(),
+        serde_json::json!({ "type": "complete", "id": "1" }),
+    );
+    drop(stream);
+
+    assert_eq!(
+        *events.0.lock().unwrap(),
+        vec![
+            "connect:123456",
+            "start 1:123456",
+            "start 2:123456",
+            "complete 1:123456",
+            "complete 2:123456",
+            "disconnect:123456",
+        ]
     );
 }
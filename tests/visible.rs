@@ -0,0 +1,216 @@
+use async_graphql::*;
+
+struct IsInternal(bool);
+
+fn is_internal(ctx: &VisibleContext<'_>) -> bool {
+    ctx.data_unchecked::<IsInternal>().0
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+enum Plan {
+    Free,
+    Pro,
+    #[graphql(visible = "is_internal")]
+    Staff,
+}
+
+#[derive(SimpleObject)]
+#[graphql(visible = "is_internal")]
+struct AuditLog {
+    entries: Vec<String>,
+}
+
+#[derive(SimpleObject)]
+struct Account {
+    name: String,
+    plan: Plan,
+    #[graphql(visible = "is_internal")]
+    credit_limit: i32,
+    audit_log: AuditLog,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn account(&self) -> Account {
+        Account {
+            name: "abc".to_string(),
+            plan: Plan::Pro,
+            credit_limit: 100,
+            audit_log: AuditLog {
+                entries: vec!["created".to_string()],
+            },
+        }
+    }
+
+    #[graphql(visible = "is_internal")]
+    async fn accounts_count(&self) -> i32 {
+        10
+    }
+}
+
+type VisibleSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+async fn execute(schema: &VisibleSchema, query: &str, internal: bool) -> Response {
+    schema
+        .execute(Request::new(query).data(IsInternal(internal)))
+        .await
+}
+
+#[async_std::test]
+pub async fn test_visible_introspection() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    let query = r#"{
+        account: __type(name: "Account") { fields { name } }
+        plan: __type(name: "Plan") { enumValues { name } }
+        auditLog: __type(name: "AuditLog") { name }
+        query: __type(name: "Query") { fields { name } }
+    }"#;
+
+    assert_eq!(
+        execute(&schema, query, true).await.data,
+        serde_json::json!({
+            "account": { "fields": [
+                { "name": "name" },
+                { "name": "plan" },
+                { "name": "creditLimit" },
+                { "name": "auditLog" },
+            ] },
+            "plan": { "enumValues": [
+                { "name": "FREE" },
+                { "name": "PRO" },
+                { "name": "STAFF" },
+            ] },
+            "auditLog": { "name": "AuditLog" },
+            "query": { "fields": [
+                { "name": "account" },
+                { "name": "accountsCount" },
+            ] },
+        })
+    );
+
+    assert_eq!(
+        execute(&schema, query, false).await.data,
+        serde_json::json!({
+            "account": { "fields": [
+                { "name": "name" },
+                { "name": "plan" },
+            ] },
+            "plan": { "enumValues": [
+                { "name": "FREE" },
+                { "name": "PRO" },
+            ] },
+            "auditLog": null,
+            "query": { "fields": [
+                { "name": "account" },
+            ] },
+        })
+    );
+
+    let query = r#"{ __schema { types { name } } }"#;
+    let has_audit_log = |resp: Response| {
+        resp.data["__schema"]["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|ty| ty["name"] == "AuditLog")
+    };
+    assert!(has_audit_log(execute(&schema, query, true).await));
+    assert!(!has_audit_log(execute(&schema, query, false).await));
+}
+
+#[async_std::test]
+pub async fn test_visible_validation() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+
+    assert_eq!(
+        execute(
+            &schema,
+            "{ account { name creditLimit } accountsCount }",
+            true
+        )
+        .await
+        .data,
+        serde_json::json!({
+            "account": { "name": "abc", "creditLimit": 100 },
+            "accountsCount": 10,
+        })
+    );
+
+    assert_eq!(
+        execute(
+            &schema,
+            "{ account { name creditLimit } accountsCount }",
+            false
+        )
+        .await
+        .into_result()
+        .unwrap_err(),
+        vec![
+            ServerError {
+                message: r#"Unknown field "creditLimit" on type "Account"."#.to_string(),
+                locations: vec![Pos {
+                    line: 1,
+                    column: 18
+                }],
+                path: Vec::new(),
+                extensions: None,
+            },
+            ServerError {
+                message: r#"Unknown field "accountsCount" on type "Query"."#.to_string(),
+                locations: vec![Pos {
+                    line: 1,
+                    column: 32
+                }],
+                path: Vec::new(),
+                extensions: None,
+            }
+        ]
+    );
+
+    assert_eq!(
+        execute(
+            &schema,
+            "{ account { ...F } } fragment F on Account { auditLog { entries } }",
+            false
+        )
+        .await
+        .into_result()
+        .unwrap_err(),
+        vec![ServerError {
+            message: r#"Unknown field "auditLog" on type "Account"."#.to_string(),
+            locations: vec![Pos {
+                line: 1,
+                column: 46
+            }],
+            path: Vec::new(),
+            extensions: None,
+        }]
+    );
+}
+
+#[test]
+pub fn test_visible_sdl() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let sdl = |internal: bool| {
+        let mut data = Data::default();
+        data.insert(IsInternal(internal));
+        schema.sdl_with_data(data)
+    };
+
+    let full_sdl = VisibleSchema::sdl();
+    assert_eq!(sdl(true), full_sdl);
+
+    let partner_sdl = sdl(false);
+    assert!(full_sdl.contains("creditLimit"));
+    assert!(!partner_sdl.contains("creditLimit"));
+    assert!(full_sdl.contains("accountsCount"));
+    assert!(!partner_sdl.contains("accountsCount"));
+    assert!(full_sdl.contains("STAFF"));
+    assert!(!partner_sdl.contains("STAFF"));
+    assert!(full_sdl.contains("AuditLog"));
+    assert!(!partner_sdl.contains("AuditLog"));
+    assert!(partner_sdl.contains("type Account"));
+}